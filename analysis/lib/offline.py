@@ -28,6 +28,7 @@ def evaluate_1d(model: DataCenterModel, inp: List[List[int]]) -> Tuple[float, fl
     (
         xs,
         cost_optimal_graph_search,
+        _,
         runtime_optimal_graph_search,
     ) = optimal_graph_search_1d(model, inp, OptimalGraphSearch1dOptions(0), options)
     # xs, cost_optimal_graph_search, runtime_optimal_graph_search = optimal_graph_search(
@@ -67,7 +68,7 @@ def evaluate_static(
     _, cost_fractional, runtime_static_fractional = (0, (1,), 0)  # static_fractional(
     #     model, inp, options
     # )
-    x, cost_integral, runtime_static_integral = static_integral(model, inp, options)
+    x, cost_integral, _, runtime_static_integral = static_integral(model, inp, options)
     print(x)
 
     # sanity checks
@@ -91,7 +92,7 @@ def evaluate_approx_graph_search(
     costs = []
     runtimes = []
     for gamma in gammas:
-        _, cost, runtime = approx_graph_search(
+        _, cost, _, runtime = approx_graph_search(
             model, inp, ApproxGraphSearchOptions(gamma), options
         )
 
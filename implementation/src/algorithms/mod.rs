@@ -28,12 +28,24 @@
 //!
 //! Finds a shortest path in a graph using dynamic programming (**not** in polynomial time).
 //!
+//! ### Graph-Based Optimal Algorithm using Branch-and-Bound
+//!
+//! SSCO - Integral
+//!
+//! Extends the graph-based optimal algorithm by discarding vertices whose cost together with an admissible lower bound on the remaining cost exceeds an upper bound on the optimal cost. Lower bounds are obtained from uni-dimensional problems in each dimension and (optionally) from a fractional relaxation.
+//!
 //! ### Graph-Based Polynomial-Time Approximation Scheme \[9\]
 //!
 //! SSCO - Integral
 //!
 //! Extends the graph-based optimal algorithm by only considering a subset of the decision space to achieve a better performance.
 //!
+//! ### Scenario-Based Stochastic Convex Optimization
+//!
+//! SSCO - Fractional
+//!
+//! Solves a two-stage stochastic program where the initial time slots are decided once and all remaining time slots are decided separately for each sample (scenario) of the predicted hitting costs.
+//!
 //! ### Static Fractional Optimum
 //!
 //! SCO - Fractional
@@ -140,6 +152,18 @@
 //!
 //! SSCO - Fractional - $1 + \mathcal{O}(1/w)$-competitive
 //!
+//! ### Stochastic Receding Horizon Control
+//!
+//! SSCO - Fractional
+//!
+//! Decides the current time slot once and all time slots of the prediction window separately for each sample (scenario) of the predicted hitting costs.
+//!
+//! ### Stochastic Averaging Fixed Horizon Control
+//!
+//! SSCO - Fractional
+//!
+//! Averages the decisions of two-stage stochastic programs over all fixed horizons.
+//!
 //! # References
 //!
 //! 1. Minghong Lin and Adam Wierman and Lachlan L. H. Andrew and Eno Thereska. _Dynamic right-sizing for power-proportional data centers_. 2011.
@@ -158,6 +182,7 @@ use crate::{
 };
 
 mod capacity_provisioning;
+mod stochastic;
 
 pub mod offline;
 pub mod online;
@@ -1,20 +1,46 @@
 //! General data structures for graph search.
 
-use super::OfflineResult;
+use super::{Certificate, OfflineResult};
+use crate::result::{Failure, Result};
 use crate::schedule::IntegralSchedule;
+use serde::{Deserializer, Serializer};
 use serde_derive::{Deserialize, Serialize};
-use std::{collections::HashMap, hash::Hash};
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fs::{self, File},
+    io::{BufReader, BufWriter},
+    path::PathBuf,
+    process,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Number of spill directories created by this process, used to keep their names unique.
+static SPILL_DIRS: AtomicUsize = AtomicUsize::new(0);
 
 /// Resulting path alongside cache which can be used for subsequent iterations.
 #[derive(Clone, Debug)]
 pub struct CachedPath<C> {
     pub path: Path,
+    /// Lower bound on the optimal cost.
+    pub lower_bound: f64,
     pub cache: C,
 }
 impl<C> OfflineResult<i32> for CachedPath<C> {
     fn xs(self) -> IntegralSchedule {
         self.path.xs
     }
+
+    fn certificate(&self) -> Option<Certificate> {
+        Some(Certificate {
+            cost: self.path.cost,
+            lower_bound: self.lower_bound,
+        })
+    }
 }
 
 /// The minimal cost from some initial vertice alongside the shortest path to the final vertice.
@@ -29,31 +55,187 @@ impl OfflineResult<i32> for Path {
     fn xs(self) -> IntegralSchedule {
         self.xs
     }
+
+    fn certificate(&self) -> Option<Certificate> {
+        Some(Certificate::optimal(self.cost))
+    }
 }
 
 /// Data structure to cache results of the algorithm up to some time slot $t$.
 #[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct Cache<T>
-where
-    T: Eq + Hash,
-{
+pub struct Cache {
     /// Time slot.
     pub t: i32,
-    /// Computed paths up to time slot $t$.
-    pub paths: Paths<T>,
-}
-
-/// Returns next initial time slot $t_init$ and $paths$ from cache.
-pub fn read_cache<T>(
-    cache: Option<Cache<T>>,
-    default: impl Fn() -> (i32, Paths<T>),
-) -> (i32, Paths<T>)
-where
-    T: Eq + Hash,
-{
-    match cache {
-        Some(Cache { t: prev_t, paths }) => (prev_t + 1, paths),
-        None => default(),
+    /// Minimal costs of reaching the vertices of the final layer (i.e. at time slot $t$), indexed by vertice.
+    pub costs: Vec<f64>,
+    /// Back-pointers of all layers up to time slot $t$.
+    pub layers: Layers,
+}
+
+/// Back-pointer of a vertice of the final (powering down) phase of some layer.
+/// Both vertices are referred to by their index in the layer.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct BackPointer {
+    /// Vertice at which the hitting cost was paid, i.e. the config at the time slot of the layer.
+    pub x: u32,
+    /// Vertice of the previous layer the shortest path originates from.
+    pub from: u32,
+}
+
+/// Back-pointers of all vertices of a layer.
+pub type Layer = Vec<BackPointer>;
+
+/// Directory to which layers are spilled.
+/// It is removed (including all spilled layers) once the last reference to it is dropped if and only if it is owned, i.e. was created by this run.
+#[derive(Debug)]
+struct DirHandle {
+    path: PathBuf,
+    owned: bool,
+}
+impl Drop for DirHandle {
+    fn drop(&mut self) {
+        if self.owned {
+            let _ = fs::remove_dir_all(&self.path);
+        }
+    }
+}
+
+/// Unique directory of a single run to which layers are spilled.
+/// Shared by all clones of the layers (e.g. within caches) and removed once the last of them is dropped.
+///
+/// Deserialized directories are never removed as they may name arbitrary paths or still be read by the original.
+#[derive(Clone, Debug)]
+pub struct SpillDir(Arc<DirHandle>);
+impl SpillDir {
+    /// Creates a new unique subdirectory of `parent`.
+    fn new(parent: &std::path::Path) -> Result<Self> {
+        fs::create_dir_all(parent)
+            .map_err(|e| Failure::LayerStorage(e.to_string()))?;
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_nanos());
+        let path = parent.join(format!(
+            "layers_{}_{}_{}",
+            process::id(),
+            nanos,
+            SPILL_DIRS.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir(&path)
+            .map_err(|e| Failure::LayerStorage(e.to_string()))?;
+        Ok(SpillDir(Arc::new(DirHandle { path, owned: true })))
+    }
+
+    /// Path of the directory.
+    pub fn path(&self) -> &std::path::Path {
+        &self.0.path
+    }
+}
+impl serde::Serialize for SpillDir {
+    fn serialize<S: Serializer>(
+        &self,
+        s: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        serde::Serialize::serialize(self.path(), s)
+    }
+}
+impl<'a> serde::Deserialize<'a> for SpillDir {
+    fn deserialize<D: Deserializer<'a>>(
+        d: D,
+    ) -> std::result::Result<Self, D::Error> {
+        <PathBuf as serde::Deserialize>::deserialize(d)
+            .map(|path| SpillDir(Arc::new(DirHandle { path, owned: false })))
+    }
+}
+
+/// Back-pointers of all layers which are either kept in memory or spilled to disk.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub enum Layers {
+    /// All layers are kept in memory.
+    Memory(Vec<Layer>),
+    /// Each layer is written to a separate file within a unique directory of the run.
+    Disk { dir: SpillDir, len: i32 },
+}
+impl Layers {
+    /// Creates an empty collection of layers which is spilled to a new unique subdirectory of `dir` if a directory is given.
+    pub fn new(dir: Option<PathBuf>) -> Result<Self> {
+        match dir {
+            None => Ok(Layers::Memory(vec![])),
+            Some(dir) => Ok(Layers::Disk {
+                dir: SpillDir::new(&dir)?,
+                len: 0,
+            }),
+        }
+    }
+
+    /// Number of layers.
+    pub fn len(&self) -> i32 {
+        match self {
+            Layers::Memory(layers) => layers.len() as i32,
+            Layers::Disk { len, .. } => *len,
+        }
+    }
+
+    /// Whether there are no layers.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Appends the layer of the next time slot.
+    pub fn push(&mut self, layer: Layer) -> Result<()> {
+        match self {
+            Layers::Memory(layers) => layers.push(layer),
+            Layers::Disk { dir, len } => {
+                *len += 1;
+                let file = File::create(Self::file(dir, *len))
+                    .map_err(|e| Failure::LayerStorage(e.to_string()))?;
+                bincode::serialize_into(BufWriter::new(file), &layer)
+                    .map_err(|e| Failure::LayerStorage(e.to_string()))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the layer of time slot $t$. Layers kept in memory are borrowed.
+    pub fn get(&self, t: i32) -> Result<Cow<'_, Layer>> {
+        match self {
+            Layers::Memory(layers) => {
+                Ok(Cow::Borrowed(&layers[t as usize - 1]))
+            }
+            Layers::Disk { dir, .. } => {
+                let file = File::open(Self::file(dir, t))
+                    .map_err(|e| Failure::LayerStorage(e.to_string()))?;
+                bincode::deserialize_from(BufReader::new(file))
+                    .map(Cow::Owned)
+                    .map_err(|e| Failure::LayerStorage(e.to_string()))
+            }
+        }
+    }
+
+    /// Layers from which a cached search is continued. Layers spilled to disk are linked (or copied) to a new unique sibling directory
+    /// so that several continuations of the same cache do not overwrite the layers of each other.
+    pub fn continued(self) -> Result<Self> {
+        match self {
+            Layers::Memory(layers) => Ok(Layers::Memory(layers)),
+            Layers::Disk { dir, len } => {
+                let parent = dir
+                    .path()
+                    .parent()
+                    .unwrap_or_else(|| std::path::Path::new("."));
+                let new_dir = SpillDir::new(parent)?;
+                for t in 1..=len {
+                    let (from, to) =
+                        (Self::file(&dir, t), Self::file(&new_dir, t));
+                    fs::hard_link(&from, &to)
+                        .or_else(|_| fs::copy(&from, &to).map(|_| ()))
+                        .map_err(|e| Failure::LayerStorage(e.to_string()))?;
+                }
+                Ok(Layers::Disk { dir: new_dir, len })
+            }
+        }
+    }
+
+    fn file(dir: &SpillDir, t: i32) -> PathBuf {
+        dir.path().join(format!("layer_{}.bin", t))
     }
 }
 
@@ -4,8 +4,10 @@ pub mod multi_dimensional;
 pub mod uni_dimensional;
 
 mod graph_search;
+mod monitor;
 
 pub use graph_search::{Cache, CachedPath};
+pub use monitor::{CancellationToken, Monitor, Progress, ProgressCallback};
 
 use crate::algorithms::Options;
 use crate::model::{ModelOutputFailure, ModelOutputSuccess};
@@ -13,6 +15,7 @@ use crate::problem::Problem;
 use crate::result::Result;
 use crate::schedule::Schedule;
 use pyo3::prelude::*;
+use serde_derive::{Deserialize, Serialize};
 
 /// Implementation of an offline algorithm.
 ///
@@ -75,6 +78,18 @@ pub struct OfflineOptions {
     /// Compute the $L$-constrained offline optimum (`convex_optimization` only).
     #[pyo3(get, set)]
     pub l: Option<f64>,
+    /// Upper bound on the cumulative carbon emissions over the time horizon (`convex_optimization`, `static_fractional`, and `static_integral` only).
+    #[pyo3(get, set)]
+    pub carbon_budget: Option<f64>,
+    /// Wall-clock time budget in seconds (finite and non-negative).
+    /// Anytime algorithms (`static_integral` and `convex_optimization`) return the best schedule found so far once the budget is exhausted, all other algorithms fail.
+    #[pyo3(get, set)]
+    pub time_budget: Option<f64>,
+    /// Callback which is notified of the progress of the algorithm.
+    pub progress: Option<ProgressCallback>,
+    /// Token to cancel the algorithm. Cancelled algorithms behave as if their time budget was exhausted.
+    #[pyo3(get, set)]
+    pub cancellation: Option<CancellationToken>,
 }
 impl Default for OfflineOptions {
     fn default() -> Self {
@@ -82,6 +97,10 @@ impl Default for OfflineOptions {
             inverted: false,
             alpha: 1.,
             l: None,
+            carbon_budget: None,
+            time_budget: None,
+            progress: None,
+            cancellation: None,
         }
     }
 }
@@ -106,12 +125,65 @@ impl OfflineOptions {
             ..Self::default()
         }
     }
+
+    pub fn carbon_constrained(carbon_budget: f64) -> Self {
+        Self {
+            carbon_budget: Some(carbon_budget),
+            ..Self::default()
+        }
+    }
+
+    pub fn time_limited(time_budget: f64) -> Self {
+        Self {
+            time_budget: Some(time_budget),
+            ..Self::default()
+        }
+    }
+
+    pub fn cancellable(cancellation: CancellationToken) -> Self {
+        Self {
+            cancellation: Some(cancellation),
+            ..Self::default()
+        }
+    }
+
+    pub fn new(inverted: bool, alpha: f64, l: Option<f64>) -> Self {
+        OfflineOptions {
+            inverted,
+            alpha,
+            l,
+            ..Self::default()
+        }
+    }
 }
 #[pymethods]
 impl OfflineOptions {
+    /// `progress` is called with the number of completed steps, the total number of steps, and the cost of the best schedule found so far (or `None`).
     #[new]
-    pub fn new(inverted: bool, alpha: f64, l: Option<f64>) -> Self {
-        OfflineOptions { inverted, alpha, l }
+    #[args(
+        time_budget = "None",
+        progress = "None",
+        cancellation = "None",
+        carbon_budget = "None"
+    )]
+    fn constructor(
+        inverted: bool,
+        alpha: f64,
+        l: Option<f64>,
+        time_budget: Option<f64>,
+        progress: Option<PyObject>,
+        cancellation: Option<CancellationToken>,
+        carbon_budget: Option<f64>,
+    ) -> Self {
+        OfflineOptions {
+            inverted,
+            alpha,
+            l,
+            carbon_budget,
+            time_budget,
+            progress: progress.map(ProgressCallback::from_py),
+            cancellation,
+        }
     }
 }
 
@@ -119,15 +191,67 @@ impl OfflineOptions {
 pub trait OfflineResult<T> {
     /// Resulting schedule.
     fn xs(self) -> Schedule<T>;
+
+    /// Certified optimality gap of the resulting schedule (if supported by the algorithm).
+    fn certificate(&self) -> Option<Certificate>;
 }
 
 /// Result of an offline algorithm which only returns the obtained schedule.
 pub struct PureOfflineResult<T> {
     /// Schedule.
     pub xs: Schedule<T>,
+    /// Certified optimality gap.
+    pub certificate: Option<Certificate>,
 }
 impl<T> OfflineResult<T> for PureOfflineResult<T> {
     fn xs(self) -> Schedule<T> {
         self.xs
     }
+
+    fn certificate(&self) -> Option<Certificate> {
+        self.certificate
+    }
+}
+
+/// Cost of a schedule alongside a proven lower bound on the optimal cost.
+#[pyclass]
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Certificate {
+    /// Cost of the obtained schedule.
+    #[pyo3(get)]
+    pub cost: f64,
+    /// Lower bound on the optimal cost.
+    #[pyo3(get)]
+    pub lower_bound: f64,
+}
+impl Certificate {
+    /// Certificate of an optimal schedule.
+    pub fn optimal(cost: f64) -> Self {
+        Certificate {
+            cost,
+            lower_bound: cost,
+        }
+    }
+
+    /// Absolute optimality gap. The gap is $0$ if the cost matches the lower bound (even if both are infinite, i.e. the problem is infeasible)
+    /// and $\infty$ if only the cost is infinite.
+    pub fn gap(&self) -> f64 {
+        if self.cost == self.lower_bound {
+            0.
+        } else {
+            self.cost - self.lower_bound
+        }
+    }
+
+    /// Upper bound on the approximation ratio of the obtained schedule. The ratio is $1$ if the cost matches the lower bound (even if both are $0$ or infinite)
+    /// and $\infty$ if the cost exceeds a lower bound of $0$ or only the cost is infinite.
+    pub fn ratio(&self) -> f64 {
+        if self.cost == self.lower_bound {
+            1.
+        } else if self.lower_bound <= 0. {
+            f64::INFINITY
+        } else {
+            self.cost / self.lower_bound
+        }
+    }
 }
@@ -0,0 +1,134 @@
+use crate::algorithms::offline::OfflineOptions;
+use crate::result::{Failure, Result};
+use pyo3::prelude::*;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Token which can be used to cancel a running offline algorithm (e.g. from another thread).
+#[pyclass]
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+#[pymethods]
+impl CancellationToken {
+    #[new]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests the cancellation of all algorithms using this token.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    /// Whether the cancellation was requested.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// Progress of a running offline algorithm.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Progress {
+    /// Number of completed steps, e.g. time slots (layers) in case of graph searches.
+    pub step: i32,
+    /// Total number of steps (an upper bound if the algorithm may terminate early).
+    pub steps: i32,
+    /// Cost of the best (partial) schedule found so far, if known.
+    pub cost: Option<f64>,
+}
+
+/// Callback receiving the progress of a running offline algorithm.
+#[derive(Clone)]
+pub struct ProgressCallback(Arc<dyn Fn(Progress) + Send + Sync>);
+impl ProgressCallback {
+    pub fn new(f: impl Fn(Progress) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(f))
+    }
+
+    /// Wraps a Python callable receiving `step`, `steps`, and `cost`.
+    pub fn from_py(callback: PyObject) -> Self {
+        Self::new(move |Progress { step, steps, cost }| {
+            Python::with_gil(|py| {
+                if let Err(error) = callback.call1(py, (step, steps, cost)) {
+                    error.print(py);
+                }
+            })
+        })
+    }
+}
+impl fmt::Debug for ProgressCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ProgressCallback")
+    }
+}
+
+/// Keeps track of the time budget, the cancellation, and the progress of a running offline algorithm.
+#[derive(Clone, Debug)]
+pub struct Monitor {
+    deadline: Option<Instant>,
+    time_budget: Option<f64>,
+    progress: Option<ProgressCallback>,
+    cancellation: Option<CancellationToken>,
+}
+impl Monitor {
+    /// Starts monitoring an algorithm with the given options.
+    /// Time budgets exceeding the representable instants are treated as unlimited.
+    pub fn start(options: &OfflineOptions) -> Result<Self> {
+        options.verify()?;
+
+        Ok(Monitor {
+            deadline: options.time_budget.and_then(|time_budget| {
+                Instant::now()
+                    .checked_add(Duration::try_from_secs_f64(time_budget).ok()?)
+            }),
+            time_budget: options.time_budget,
+            progress: options.progress.clone(),
+            cancellation: options.cancellation.clone(),
+        })
+    }
+
+    /// Whether the algorithm was cancelled or exceeded its time budget.
+    pub fn interrupted(&self) -> bool {
+        self.cancelled() || self.exceeded()
+    }
+
+    /// Fails if the algorithm was cancelled or exceeded its time budget.
+    pub fn check(&self) -> Result<()> {
+        if self.cancelled() {
+            Err(Failure::Cancelled)
+        } else if self.exceeded() {
+            Err(Failure::TimeBudgetExceeded(self.time_budget.unwrap()))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Remaining time budget in seconds.
+    pub fn remaining(&self) -> Option<f64> {
+        self.deadline.map(|deadline| {
+            deadline
+                .saturating_duration_since(Instant::now())
+                .as_secs_f64()
+        })
+    }
+
+    /// Reports the progress of the algorithm.
+    pub fn report(&self, step: i32, steps: i32, cost: Option<f64>) {
+        if let Some(ProgressCallback(f)) = &self.progress {
+            f(Progress { step, steps, cost })
+        }
+    }
+
+    fn cancelled(&self) -> bool {
+        self.cancellation
+            .as_ref()
+            .is_some_and(|cancellation| cancellation.is_cancelled())
+    }
+
+    fn exceeded(&self) -> bool {
+        self.deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+}
@@ -1,4 +1,3 @@
-use super::graph_search::Vertice;
 use crate::algorithms::offline::graph_search::{Cache, CachedPath};
 use crate::algorithms::offline::multi_dimensional::{
     graph_search::graph_search, Values,
@@ -10,20 +9,26 @@ use crate::result::Result;
 use log::debug;
 use pyo3::prelude::*;
 use rayon::slice::ParallelSliceMut;
+use std::path::PathBuf;
 
 #[pyclass(name = "ApproxGraphSearchOptions")]
 #[derive(Clone)]
 pub struct Options {
-    pub cache: Option<Cache<Vertice>>,
+    pub cache: Option<Cache>,
     /// $\gamma > 1$. Default is $1.1$.
     #[pyo3(get, set)]
     pub gamma: f64,
+    /// Directory in which the back-pointers of all layers are written to a unique subdirectory of the run instead of being kept in memory.
+    /// The subdirectory is removed once the result (including its cache) is dropped.
+    #[pyo3(get, set)]
+    pub spill_dir: Option<PathBuf>,
 }
 impl Default for Options {
     fn default() -> Self {
         Options {
             cache: None,
             gamma: 1.1,
+            spill_dir: None,
         }
     }
 }
@@ -34,28 +39,52 @@ impl Options {
             ..Options::default()
         }
     }
+
+    /// Chooses $\gamma$ such that the obtained schedule is guaranteed to be a $(1 + \epsilon)$-approximation. $\epsilon$ must be positive.
+    pub fn with_accuracy(epsilon: f64) -> Self {
+        Options::new(1. + epsilon / 2.)
+    }
 }
 #[pymethods]
 impl Options {
     #[new]
     fn constructor(gamma: f64) -> Self {
-        Options { cache: None, gamma }
+        Options::new(gamma)
+    }
+
+    #[staticmethod]
+    #[pyo3(name = "with_accuracy")]
+    fn with_accuracy_py(epsilon: f64) -> Self {
+        Options::with_accuracy(epsilon)
     }
 }
 
 /// Graph-Based Polynomial-Time Approximation Scheme
+///
+/// The obtained schedule is a $(2\gamma - 1)$-approximation which is used to bound the optimal cost from below.
 pub fn approx_graph_search<C, D>(
     p: IntegralSimplifiedSmoothedConvexOptimization<'_, C, D>,
-    Options { cache, gamma }: Options,
+    options: Options,
     offline_options: OfflineOptions,
-) -> Result<CachedPath<Cache<Vertice>>>
+) -> Result<CachedPath<Cache>>
 where
     C: ModelOutputSuccess,
     D: ModelOutputFailure,
 {
+    options.verify()?;
+    let Options {
+        cache,
+        gamma,
+        spill_dir,
+    } = options;
+
     let values = cache_bound_indices(build_values, &p.bounds, gamma);
     debug!("starting with `{}` values", values.values.len());
-    graph_search(p, values, cache, offline_options)
+    let result = graph_search(p, values, cache, spill_dir, offline_options)?;
+    Ok(CachedPath {
+        lower_bound: result.path.cost / (2. * gamma - 1.),
+        ..result
+    })
 }
 
 /// Computes all values allowed by the approximation algorithm.
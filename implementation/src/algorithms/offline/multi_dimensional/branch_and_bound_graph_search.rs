@@ -0,0 +1,321 @@
+use crate::algorithms::offline::graph_search::Path;
+use crate::algorithms::offline::multi_dimensional::{
+    approx_graph_search::{
+        approx_graph_search, Options as ApproxGraphSearchOptions,
+    },
+    graph_search::{pruned_graph_search, verify_integral, Pruning},
+    Values,
+};
+use crate::algorithms::offline::{
+    Certificate, Monitor, OfflineOptions, OfflineResult,
+};
+use crate::config::Config;
+use crate::model::{ModelOutputFailure, ModelOutputSuccess};
+use crate::numerics::convex_optimization::{find_minimizer, WrappedObjective};
+use crate::problem::{
+    FractionalSimplifiedSmoothedConvexOptimization,
+    IntegralSimplifiedSmoothedConvexOptimization, Problem,
+};
+use crate::result::Result;
+use crate::schedule::IntegralSchedule;
+use log::debug;
+use pyo3::prelude::*;
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+#[pyclass(name = "BranchAndBoundGraphSearchOptions")]
+#[derive(Clone, Default)]
+pub struct Options {
+    /// Upper bound on the optimal cost, e.g. the cost of a feasible schedule.
+    /// By default, the cost of the schedule obtained by the graph-based approximation algorithm with $\gamma = 2$ is used.
+    #[pyo3(get, set)]
+    pub upper_bound: Option<f64>,
+    /// `hitting_cost_bounds[t-1][k][j]` is a lower bound on the hitting cost in time slot $t$ of all configs whose $k$-th dimension is $j$.
+    /// By default, hitting costs are only bounded by $0$.
+    #[pyo3(get, set)]
+    pub hitting_cost_bounds: Option<Vec<Vec<Vec<f64>>>>,
+}
+#[pymethods]
+impl Options {
+    #[new]
+    fn constructor(
+        upper_bound: Option<f64>,
+        hitting_cost_bounds: Option<Vec<Vec<Vec<f64>>>>,
+    ) -> Self {
+        Options {
+            upper_bound,
+            hitting_cost_bounds,
+        }
+    }
+}
+
+/// Shortest path alongside the number of expanded and pruned vertices.
+#[derive(Clone, Debug)]
+pub struct BranchAndBoundPath {
+    pub path: Path,
+    /// Lower bound on the optimal cost.
+    /// Equals the cost of the path unless vertices were pruned using `hitting_cost_bounds` which are not guaranteed to be admissible.
+    pub lower_bound: f64,
+    /// Number of vertices whose hitting cost was evaluated.
+    pub expanded: u64,
+    /// Number of reachable vertices which were discarded as they cannot be on a shortest path.
+    pub pruned: u64,
+    /// Number of layers which were not pruned as transition costs require considering all pairs of vertices of consecutive layers.
+    pub dense_layers: u64,
+}
+impl OfflineResult<i32> for BranchAndBoundPath {
+    fn xs(self) -> IntegralSchedule {
+        self.path.xs
+    }
+
+    fn certificate(&self) -> Option<Certificate> {
+        Some(Certificate {
+            cost: self.path.cost,
+            lower_bound: self.lower_bound,
+        })
+    }
+}
+
+/// Graph-Based Optimal Algorithm using Branch-and-Bound
+///
+/// Discards all vertices whose cost together with an admissible lower bound on the remaining cost exceeds an upper bound on the optimal cost.
+/// The remaining cost is bounded from below by the optimal solutions of the uni-dimensional problems obtained by bounding the hitting costs by `hitting_cost_bounds` in each dimension.
+///
+/// The obtained path is certified to be optimal if no vertice was pruned or only the default (trivially admissible) hitting cost bounds are used.
+/// Otherwise, the optimal cost is only bounded from below by the minimal cost of reaching a pruned vertice.
+/// Problems with transition costs (e.g. due to boot delays) are solved without pruning, see `BranchAndBoundPath::dense_layers`.
+pub fn branch_and_bound_graph_search<C, D>(
+    p: IntegralSimplifiedSmoothedConvexOptimization<'_, C, D>,
+    options: Options,
+    offline_options: OfflineOptions,
+) -> Result<BranchAndBoundPath>
+where
+    C: ModelOutputSuccess,
+    D: ModelOutputFailure,
+{
+    options.verify(p.t_end, &p.bounds)?;
+    verify_integral(&p)?;
+    let Options {
+        upper_bound,
+        hitting_cost_bounds,
+    } = options;
+
+    let monitor = Monitor::start(&offline_options)?;
+    let upper_bound = match upper_bound {
+        Some(upper_bound) => upper_bound,
+        None => {
+            approx_graph_search(
+                p.clone(),
+                ApproxGraphSearchOptions::new(2.),
+                offline_options.clone(),
+            )?
+            .path
+            .cost
+        }
+    };
+    let admissible = hitting_cost_bounds.is_none();
+    let hitting_cost = hitting_cost_bounds.unwrap_or_else(|| {
+        (1..=p.t_end)
+            .map(|_| {
+                p.bounds.iter().map(|&m| vec![0.; m as usize + 1]).collect()
+            })
+            .collect()
+    });
+    let remaining_cost = (0..p.d as usize)
+        .map(|k| {
+            remaining_cost_bounds(
+                &p,
+                &hitting_cost,
+                k,
+                offline_options.alpha,
+                offline_options.inverted,
+            )
+        })
+        .collect::<Vec<_>>();
+    let pruning = Pruning {
+        upper_bound,
+        hitting_cost,
+        // reorder to index by time slot first
+        remaining_cost: (0..=p.t_end as usize)
+            .map(|t| remaining_cost.iter().map(|vs| vs[t].clone()).collect())
+            .collect(),
+        admissible,
+    };
+
+    let max_bound = p.bounds.iter().max().unwrap();
+    let values = Values {
+        values: (0..=*max_bound).collect(),
+        bound_indices: p.bounds.iter().map(|&m| m as usize).collect(),
+    };
+    debug!("starting with upper bound `{}`", upper_bound);
+    let (result, statistics) = pruned_graph_search(
+        p,
+        values,
+        None,
+        None,
+        Some(&pruning),
+        // the time budget is shared with the computation of the upper bound
+        OfflineOptions {
+            time_budget: monitor.remaining(),
+            ..offline_options
+        },
+    )?;
+    debug!(
+        "expanded `{}` vertices and pruned `{}` vertices ({} dense layers)",
+        statistics.expanded, statistics.pruned, statistics.dense_layers
+    );
+    Ok(BranchAndBoundPath {
+        path: result.path,
+        lower_bound: result.lower_bound,
+        expanded: statistics.expanded,
+        pruned: statistics.pruned,
+        dense_layers: statistics.dense_layers,
+    })
+}
+
+/// Computes the optimal remaining cost after each time slot $t$ (starting from each value $j$) of the uni-dimensional problem in dimension $k$ using backward induction.
+fn remaining_cost_bounds<C, D>(
+    p: &IntegralSimplifiedSmoothedConvexOptimization<'_, C, D>,
+    hitting_cost: &[Vec<Vec<f64>>],
+    k: usize,
+    alpha: f64,
+    inverted: bool,
+) -> Vec<Vec<f64>>
+where
+    C: ModelOutputSuccess,
+    D: ModelOutputFailure,
+{
+    let m = p.bounds[k] as usize;
+    let beta = alpha * p.switching_cost[k];
+
+    // inverted movement costs also pay for powering down after the final time slot
+    let mut remaining_costs = vec![(0..=m)
+        .map(|j| if inverted { beta * j as f64 } else { 0. })
+        .collect::<Vec<_>>()];
+    for t in (1..=p.t_end as usize).rev() {
+        let next = remaining_costs.last().unwrap();
+        let costs: Vec<f64> = (0..=m)
+            .map(|j| hitting_cost[t - 1][k][j] + next[j])
+            .collect();
+        remaining_costs.push(cheapest_transitions(&costs, beta, inverted));
+    }
+    remaining_costs.reverse();
+    remaining_costs
+}
+
+/// Computes $\min_{j'} c(j, j') + costs_{j'}$ for all $j$ in linear time
+/// where $c(j, j') = \beta (j' - j)^+$, or $c(j, j') = \beta (j - j')^+$ if movement costs are inverted.
+fn cheapest_transitions(costs: &[f64], beta: f64, inverted: bool) -> Vec<f64> {
+    let n = costs.len();
+    let (free, paid): (Vec<f64>, Vec<f64>) = if inverted {
+        // moving up is free, moving down is paid
+        (
+            suffix_minima(costs),
+            prefix_minima(
+                &(0..n)
+                    .map(|j| costs[j] - beta * j as f64)
+                    .collect::<Vec<_>>(),
+            ),
+        )
+    } else {
+        // moving down is free, moving up is paid
+        (
+            prefix_minima(costs),
+            suffix_minima(
+                &(0..n)
+                    .map(|j| costs[j] + beta * j as f64)
+                    .collect::<Vec<_>>(),
+            ),
+        )
+    };
+    (0..n)
+        .map(|j| {
+            let paid = if inverted {
+                paid[j] + beta * j as f64
+            } else {
+                paid[j] - beta * j as f64
+            };
+            free[j].min(paid)
+        })
+        .collect()
+}
+
+fn prefix_minima(xs: &[f64]) -> Vec<f64> {
+    xs.iter()
+        .scan(f64::INFINITY, |min, &x| {
+            *min = min.min(x);
+            Some(*min)
+        })
+        .collect()
+}
+
+fn suffix_minima(xs: &[f64]) -> Vec<f64> {
+    let mut result: Vec<f64> = xs
+        .iter()
+        .rev()
+        .scan(f64::INFINITY, |min, &x| {
+            *min = min.min(x);
+            Some(*min)
+        })
+        .collect();
+    result.reverse();
+    result
+}
+
+/// Computes lower bounds on the hitting costs (see `Options::hitting_cost_bounds`) from the fractional relaxation of a problem.
+/// The relaxation is expected to coincide with the integral problem on integral configs.
+///
+/// Note that the bounds are only admissible if the numerical minimization of the (convex) hitting costs converges.
+pub fn relaxed_hitting_cost_bounds<C, D>(
+    p: &FractionalSimplifiedSmoothedConvexOptimization<'_, C, D>,
+) -> Vec<Vec<Vec<f64>>>
+where
+    C: ModelOutputSuccess,
+    D: ModelOutputFailure,
+{
+    (1..=p.t_end)
+        .map(|t| {
+            (0..p.d as usize)
+                .map(|k| {
+                    (0..=p.bounds[k].floor() as i32)
+                        .into_par_iter()
+                        .map(|j| minimize_with_fixed_dimension(p, t, k, j))
+                        .collect()
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Minimal hitting cost in time slot $t$ of all configs whose $k$-th dimension is $j$.
+fn minimize_with_fixed_dimension<C, D>(
+    p: &FractionalSimplifiedSmoothedConvexOptimization<'_, C, D>,
+    t: i32,
+    k: usize,
+    j: i32,
+) -> f64
+where
+    C: ModelOutputSuccess,
+    D: ModelOutputFailure,
+{
+    let build_config = move |raw_x: &[f64]| {
+        let mut x = raw_x.to_vec();
+        x.insert(k, j as f64);
+        Config::new(x)
+    };
+    if p.d == 1 {
+        return p.hit_cost(t, build_config(&[])).cost.raw();
+    }
+
+    let bounds = p
+        .bounds
+        .iter()
+        .enumerate()
+        .filter(|&(l, _)| l != k)
+        .map(|(_, &m)| (0., m))
+        .collect();
+    let objective = WrappedObjective::new(p.clone(), move |raw_x, p| {
+        p.hit_cost(t, build_config(raw_x)).cost
+    });
+    let (_, cost) = find_minimizer(objective, bounds);
+    cost.raw()
+}
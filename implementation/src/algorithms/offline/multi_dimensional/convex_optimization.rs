@@ -1,10 +1,12 @@
-use crate::algorithms::offline::{OfflineOptions, PureOfflineResult};
+use crate::algorithms::offline::{Monitor, OfflineOptions, PureOfflineResult};
 use crate::config::Config;
 use crate::model::{ModelOutputFailure, ModelOutputSuccess};
-use crate::numerics::convex_optimization::{minimize, WrappedObjective};
+use crate::numerics::convex_optimization::{
+    max_evaluations, minimize, WrappedObjective,
+};
 use crate::problem::{FractionalSmoothedConvexOptimization, Problem};
 use crate::result::{Failure, Result};
-use crate::schedule::Schedule;
+use crate::schedule::{FractionalSchedule, Schedule};
 use crate::utils::assert;
 use noisy_float::prelude::*;
 
@@ -12,66 +14,341 @@ use noisy_float::prelude::*;
 struct ObjectiveData<'a, C, D> {
     p: FractionalSmoothedConvexOptimization<'a, C, D>,
     alpha: f64,
+    epigraphs: Vec<Epigraph>,
 }
 
 #[derive(Clone)]
 struct ConstraintData<'a, C, D> {
     p: FractionalSmoothedConvexOptimization<'a, C, D>,
-    l: f64,
+    /// Upper bound of the constrained quantity.
+    bound: f64,
+}
+
+#[derive(Clone)]
+struct SupervisedData<'a, C> {
+    objective: WrappedObjective<'a, C>,
+    monitor: Monitor,
+    evaluations: i32,
+    picked_cost: f64,
+}
+
+/// Variable bounding the peak of a peak charge during a billing window from above.
+#[derive(Clone)]
+struct Epigraph {
+    /// Key of the quantity.
+    key: String,
+    /// Price of a unit of the peak.
+    price: f64,
+    /// Time slots of the billing window.
+    ts: Vec<i32>,
+    /// Peak which has already been paid before the billing window.
+    running_peak: f64,
+    /// Upper bound of the quantity.
+    bound: f64,
+}
+
+impl Epigraph {
+    /// Peak of the quantity during the billing window.
+    fn peak<C, D>(
+        &self,
+        p: &FractionalSmoothedConvexOptimization<'_, C, D>,
+        xs: &FractionalSchedule,
+    ) -> N64
+    where
+        C: ModelOutputSuccess,
+        D: ModelOutputFailure,
+    {
+        self.ts
+            .iter()
+            .map(|&t| {
+                let x = xs[t as usize - 1].clone();
+                n64(p.hit_cost(t, x).output.peak(&self.key))
+            })
+            .max()
+            .unwrap()
+    }
 }
 
 /// Convex Optimization
+///
+/// Once the time budget is exhausted (or the algorithm is cancelled), the best schedule found so far is returned.
+/// The carbon budget is enforced as a constraint on the emissions reported by the model.
+/// The peak of each billing window of a peak charge is bounded from above by an epigraph variable whose price is paid instead.
+/// Schedules are projected onto the configurations which may follow their predecessors (see `Problem::project`) before they are evaluated.
+/// As the solver provides no dual bound, the result carries no certificate.
 pub fn co<C, D>(
     p: FractionalSmoothedConvexOptimization<'_, C, D>,
     _: (),
-    OfflineOptions { inverted, alpha, l }: OfflineOptions,
+    offline_options: OfflineOptions,
 ) -> Result<PureOfflineResult<f64>>
 where
     C: ModelOutputSuccess,
     D: ModelOutputFailure,
 {
+    let monitor = Monitor::start(&offline_options)?;
+    let OfflineOptions {
+        inverted,
+        alpha,
+        l,
+        carbon_budget,
+        ..
+    } = offline_options;
     assert(!inverted, Failure::UnsupportedInvertedCost)?;
 
-    let d = p.d;
-    let t_end = p.t_end;
+    // peak charges are paid through the epigraph variables which follow the schedule in the raw encoding
+    let epigraphs = build_epigraphs(&p);
+    let mut p = p;
+    p.hitting_cost = p.hitting_cost.with_peak_charges(vec![]);
+
+    let bounds: Vec<_> = build_bounds(&p)
+        .into_iter()
+        .chain(epigraphs.iter().map(|epigraph| {
+            (
+                epigraph.running_peak,
+                epigraph.bound.max(epigraph.running_peak),
+            )
+        }))
+        .collect();
+    // starting from the upper bounds, the solver of constrained problems tends to get stuck, so it is
+    // warm-started from the optimum without peak charges when peaks are bounded by epigraph variables
+    let init = if epigraphs.is_empty() {
+        None
+    } else {
+        Some(build_warm_start(&p, alpha, &epigraphs, monitor.clone()))
+    };
+    let objective = supervise(
+        build_projected_objective(&p, alpha, epigraphs.clone()),
+        monitor,
+        max_evaluations(bounds.len()).min(i32::MAX as u32) as i32,
+    );
+    let mut constraints = vec![];
+
+    // l-constrained movement
+    if let Some(l) = l {
+        constraints.push(WrappedObjective::new(
+            ConstraintData {
+                p: p.clone(),
+                bound: l,
+            },
+            |raw_xs, data| {
+                let xs = project(&data.p, raw_xs);
+                data.p.total_movement(&xs, false).unwrap() - n64(data.bound)
+            },
+        ));
+    }
+
+    // carbon budget
+    if let Some(carbon_budget) = carbon_budget {
+        constraints.push(WrappedObjective::new(
+            ConstraintData {
+                p: p.clone(),
+                bound: carbon_budget,
+            },
+            |raw_xs, data| {
+                let xs = project(&data.p, raw_xs);
+                let emissions =
+                    data.p.objective_function(&xs).unwrap().output.emissions();
+                n64(emissions) - n64(data.bound)
+            },
+        ));
+    }
+
+    // peaks of billing windows
+    let offset = (p.d * p.t_end) as usize;
+    for (i, epigraph) in epigraphs.into_iter().enumerate() {
+        constraints.push(WrappedObjective::new(
+            ConstraintData {
+                p: p.clone(),
+                bound: 0.,
+            },
+            move |raw_xs, data| {
+                let xs = project(&data.p, raw_xs);
+                epigraph.peak(&data.p, &xs)
+                    - n64(raw_xs[offset + i])
+                    - n64(data.bound)
+            },
+        ));
+    }
+
+    let (raw_xs, _) = minimize(objective, bounds, init, constraints);
+    let xs = project(&p, &raw_xs);
+    Ok(PureOfflineResult {
+        xs,
+        certificate: None,
+    })
+}
+
+/// Epigraph variables of the billing windows of all peak charges during time slots $1, \dots, t_end$.
+fn build_epigraphs<C, D>(
+    p: &FractionalSmoothedConvexOptimization<'_, C, D>,
+) -> Vec<Epigraph>
+where
+    C: ModelOutputSuccess,
+    D: ModelOutputFailure,
+{
+    p.peak_charges()
+        .iter()
+        .flat_map(|peak_charge| {
+            let mut epigraphs: Vec<Epigraph> = vec![];
+            for t in 1..=p.t_end {
+                let window = peak_charge.billing_window(t);
+                if t > 1 && window == peak_charge.billing_window(t - 1) {
+                    epigraphs.last_mut().unwrap().ts.push(t);
+                } else {
+                    epigraphs.push(Epigraph {
+                        key: peak_charge.key.clone(),
+                        price: peak_charge.price,
+                        ts: vec![t],
+                        running_peak: if t == 1 {
+                            peak_charge.running_peak()
+                        } else {
+                            0.
+                        },
+                        bound: peak_charge.bound,
+                    });
+                }
+            }
+            epigraphs
+        })
+        .collect()
+}
+
+/// Optimum without peak charges followed by the peaks it draws during each billing window.
+fn build_warm_start<C, D>(
+    p: &FractionalSmoothedConvexOptimization<'_, C, D>,
+    alpha: f64,
+    epigraphs: &[Epigraph],
+    monitor: Monitor,
+) -> Vec<f64>
+where
+    C: ModelOutputSuccess,
+    D: ModelOutputFailure,
+{
+    let bounds = build_bounds(p);
+    let objective = supervise(
+        build_projected_objective(p, alpha, vec![]),
+        monitor,
+        max_evaluations(bounds.len()).min(i32::MAX as u32) as i32,
+    );
+    let (raw_xs, _) =
+        minimize(objective, bounds, None, Vec::<WrappedObjective<()>>::new());
+    let xs = project(p, &raw_xs);
+    let peaks: Vec<_> = epigraphs
+        .iter()
+        .map(|epigraph| {
+            epigraph
+                .peak(p, &xs)
+                .raw()
+                .min(epigraph.bound)
+                .max(epigraph.running_peak)
+        })
+        .collect();
+    raw_xs.into_iter().chain(peaks).collect()
+}
 
+/// Bounds of the decision space over all time slots.
+fn build_bounds<C, D>(
+    p: &FractionalSmoothedConvexOptimization<'_, C, D>,
+) -> Vec<(f64, f64)>
+where
+    C: ModelOutputSuccess,
+    D: ModelOutputFailure,
+{
     let (lower, upper): (Vec<_>, Vec<_>) = p.bounds.iter().cloned().unzip();
     let extended_lower = Schedule::build_raw(p.t_end, &Config::new(lower));
     let extended_upper = Schedule::build_raw(p.t_end, &Config::new(upper));
-    let bounds = extended_lower
+    extended_lower
         .into_iter()
         .zip(extended_upper.into_iter())
-        .collect();
+        .collect()
+}
 
-    let objective = WrappedObjective::new(
+fn build_projected_objective<'a, C, D>(
+    p: &FractionalSmoothedConvexOptimization<'a, C, D>,
+    alpha: f64,
+    epigraphs: Vec<Epigraph>,
+) -> WrappedObjective<'a, ObjectiveData<'a, C, D>>
+where
+    C: ModelOutputSuccess,
+    D: ModelOutputFailure,
+{
+    WrappedObjective::new(
         ObjectiveData {
             p: p.clone(),
             alpha,
+            epigraphs,
         },
         |raw_xs, data| {
-            let xs = Schedule::from_raw(data.p.d, data.p.t_end, raw_xs);
+            let xs = project(&data.p, raw_xs);
+            let offset = (data.p.d * data.p.t_end) as usize;
+            let peak_cost: N64 = data
+                .epigraphs
+                .iter()
+                .enumerate()
+                .map(|(i, epigraph)| {
+                    n64(epigraph.price)
+                        * (n64(raw_xs[offset + i]) - n64(epigraph.running_peak))
+                })
+                .sum();
             data.p
                 .alpha_unfair_objective_function(&xs, data.alpha)
                 .unwrap()
                 .cost
+                + peak_cost
         },
-    );
+    )
+}
 
-    // l-constrained movement
-    let constraints = match l {
-        Some(l) => {
-            vec![WrappedObjective::new(
-                ConstraintData { p, l },
-                |raw_xs, data| {
-                    let xs = Schedule::from_raw(data.p.d, data.p.t_end, raw_xs);
-                    data.p.total_movement(&xs, false).unwrap() - n64(data.l)
-                },
-            )]
-        }
-        None => vec![],
-    };
+/// Builds the schedule encoded by `raw_xs` (ignoring the trailing epigraph variables) and projects it onto the configurations which may follow their predecessors.
+fn project<C, D>(
+    p: &FractionalSmoothedConvexOptimization<'_, C, D>,
+    raw_xs: &[f64],
+) -> Schedule<f64>
+where
+    C: ModelOutputSuccess,
+    D: ModelOutputFailure,
+{
+    let xs =
+        Schedule::from_raw(p.d, p.t_end, &raw_xs[..(p.d * p.t_end) as usize]);
+    if p.hitting_cost.has_projection() {
+        p.project_schedule(&xs, &p._default_config())
+    } else {
+        xs
+    }
+}
 
-    let (raw_xs, _) = minimize(objective, bounds, None, constraints);
-    let xs = Schedule::from_raw(d, t_end, &raw_xs);
-    Ok(PureOfflineResult { xs })
+/// Makes all evaluations of the objective infinite once the algorithm is interrupted so that the solver terminates with the best schedule found so far.
+/// Reports the progress whenever the best schedule improves.
+fn supervise<'a, C>(
+    objective: WrappedObjective<'a, C>,
+    monitor: Monitor,
+    max_evaluations: i32,
+) -> WrappedObjective<'a, SupervisedData<'a, C>>
+where
+    C: 'a,
+{
+    WrappedObjective::new(
+        SupervisedData {
+            objective,
+            monitor,
+            evaluations: 0,
+            picked_cost: f64::INFINITY,
+        },
+        move |raw_xs, data| {
+            if data.monitor.interrupted() {
+                return n64(f64::INFINITY);
+            }
+            let cost = (data.objective.f)(raw_xs, &mut data.objective.data);
+            data.evaluations += 1;
+            if cost.raw() < data.picked_cost {
+                data.picked_cost = cost.raw();
+                data.monitor.report(
+                    data.evaluations,
+                    max_evaluations,
+                    Some(data.picked_cost),
+                );
+            }
+            cost
+        },
+    )
 }
@@ -1,404 +1,591 @@
-use log::debug;
+use log::{debug, warn};
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 
 use crate::algorithms::offline::graph_search::{
-    read_cache, Cache, CachedPath, Path, Paths,
+    BackPointer, Cache, CachedPath, Layer, Layers, Path,
 };
 use crate::algorithms::offline::multi_dimensional::Values;
-use crate::algorithms::offline::OfflineOptions;
-use crate::config::{Config, IntegralConfig};
+use crate::algorithms::offline::{Monitor, OfflineOptions};
+use crate::config::IntegralConfig;
 use crate::model::{ModelOutputFailure, ModelOutputSuccess};
-use crate::problem::{
-    scalar_movement, IntegralSimplifiedSmoothedConvexOptimization, Problem,
-};
+use crate::problem::{IntegralSimplifiedSmoothedConvexOptimization, Problem};
 use crate::result::{Failure, Result};
 use crate::schedule::IntegralSchedule;
 use crate::utils::assert;
-use serde_derive::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::path::PathBuf;
 
-/// Tracks the value as well as the index in which the value appears in the respective dimension.
-#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
-struct InternalConfig {
-    config: IntegralConfig,
-    indices: Config<usize>,
-}
+/// Vertices of a layer are identified by the index of their config.
+/// Configs are enumerated in mixed radix where the $k$-th digit is the index of the value of dimension $k$ (starting with the first dimension as least significant digit).
+/// Hence, all vertices of a layer are in $[\prod_{k=1}^d (|values_k|)]$ and neighboring configs w.r.t. dimension $k$ are at distance $stride_k$.
+impl Values {
+    /// Number of configs (i.e. vertices of a single phase of a layer).
+    fn len(&self) -> usize {
+        self.bound_indices.iter().map(|&i| i + 1).product()
+    }
 
-/// Vertice in the graph denoting time $t$ and the value $x$ at time $t$.
-/// The boolean flag indicates whether the vertice belongs to the powering up (`true`) or powering down (`false`) phase.
-/// The algorithm only keeps the most recent two layers in memory.
-#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
-pub struct Vertice {
-    config: InternalConfig,
-    powering_up: bool,
+    /// Distance of neighboring configs in each dimension.
+    fn strides(&self) -> Vec<usize> {
+        self.bound_indices
+            .iter()
+            .scan(1, |stride, &i| {
+                let result = *stride;
+                *stride *= i + 1;
+                Some(result)
+            })
+            .collect()
+    }
+
+    /// Index of the value of each dimension of the config with the given index.
+    fn indices(&self, id: usize) -> Vec<usize> {
+        self.bound_indices
+            .iter()
+            .scan(id, |rest, &i| {
+                let result = *rest % (i + 1);
+                *rest /= i + 1;
+                Some(result)
+            })
+            .collect()
+    }
+
+    /// Config with the given index.
+    fn config(&self, id: usize) -> IntegralConfig {
+        IntegralConfig::new(
+            self.indices(id)
+                .into_iter()
+                .map(|i| self.values[i])
+                .collect(),
+        )
+    }
 }
 
-/// Edge consisting of source and weight.
-#[derive(Clone, Debug)]
-struct Edge {
-    from: Vertice,
+/// Minimal cost of reaching a vertice alongside its back-pointer.
+#[derive(Clone, Copy, Debug)]
+struct Label {
     cost: f64,
+    /// Vertice of the current layer at which the hitting cost was paid. Only meaningful in the powering down phase.
+    x: usize,
+    /// Vertice of the previous layer the shortest path originates from.
+    from: usize,
+}
+
+/// Bounds used to prune vertices which cannot be on a shortest path.
+pub struct Pruning {
+    /// Upper bound on the cost of a shortest path.
+    pub upper_bound: f64,
+    /// `hitting_cost[t-1][k][i]` is a lower bound on the hitting cost in time slot $t$ of all configs whose $k$-th dimension has the $i$-th value.
+    pub hitting_cost: Vec<Vec<Vec<f64>>>,
+    /// `remaining_cost[t][k][i]` is a lower bound on the cost after time slot $t$ of all paths starting from a config whose $k$-th dimension has the $i$-th value.
+    pub remaining_cost: Vec<Vec<Vec<f64>>>,
+    /// Whether the bounds are known to be admissible. Otherwise, the obtained path is only certified to cost at most as much as reaching any pruned vertice.
+    pub admissible: bool,
+}
+impl Pruning {
+    /// Relative tolerance when comparing with the upper bound to guard against rounding errors.
+    const TOLERANCE: f64 = 1e-9;
+
+    fn lower_bound(bounds: &[Vec<f64>], indices: &[usize]) -> f64 {
+        indices
+            .iter()
+            .enumerate()
+            .map(|(k, &i)| bounds[k][i])
+            .fold(0., f64::max)
+    }
+
+    /// Whether a path with the given cost can be discarded.
+    fn exceeds(&self, cost: f64) -> bool {
+        cost > self.upper_bound
+            + Self::TOLERANCE * self.upper_bound.abs().max(1.)
+    }
+}
+
+/// Statistics of a graph search.
+#[derive(Clone, Copy, Debug)]
+pub struct Statistics {
+    /// Number of vertices whose hitting cost was evaluated.
+    pub expanded: u64,
+    /// Number of reachable vertices whose hitting cost was not evaluated as the bounds show that they cannot be on a shortest path.
+    pub pruned: u64,
+    /// Number of layers which were handled densely due to transition costs. Vertices of dense layers are never pruned.
+    pub dense_layers: u64,
+    /// Minimal cost of reaching a vertice which was discarded by the bounds, $\infty$ if no vertice was discarded.
+    pub min_pruned_cost: f64,
+}
+impl Default for Statistics {
+    fn default() -> Self {
+        Statistics {
+            expanded: 0,
+            pruned: 0,
+            dense_layers: 0,
+            min_pruned_cost: f64::INFINITY,
+        }
+    }
 }
 
 /// Graph-Based Integral Algorithm
+///
+/// The algorithm only keeps the minimal costs of the most recent layer in memory.
+/// For all other layers, only back-pointers are stored from which the schedule is reconstructed in the end.
+/// If `spill_dir` is given, the back-pointers are written to a unique subdirectory of it instead of being kept in memory.
+/// A given cache must stem from the same values; its spilled layers are linked to a new subdirectory so that the cache can be continued several times.
 pub fn graph_search<C, D>(
     p: IntegralSimplifiedSmoothedConvexOptimization<'_, C, D>,
     values: Values,
-    cache: Option<Cache<Vertice>>,
-    OfflineOptions { inverted, alpha, l }: OfflineOptions,
-) -> Result<CachedPath<Cache<Vertice>>>
+    cache: Option<Cache>,
+    spill_dir: Option<PathBuf>,
+    offline_options: OfflineOptions,
+) -> Result<CachedPath<Cache>>
+where
+    C: ModelOutputSuccess,
+    D: ModelOutputFailure,
+{
+    let (result, _) = pruned_graph_search(
+        p,
+        values,
+        cache,
+        spill_dir,
+        None,
+        offline_options,
+    )?;
+    Ok(result)
+}
+
+/// Graph-Based Integral Algorithm discarding all vertices which cannot be on a shortest path w.r.t. `pruning`.
+pub fn pruned_graph_search<C, D>(
+    p: IntegralSimplifiedSmoothedConvexOptimization<'_, C, D>,
+    values: Values,
+    cache: Option<Cache>,
+    spill_dir: Option<PathBuf>,
+    pruning: Option<&Pruning>,
+    offline_options: OfflineOptions,
+) -> Result<(CachedPath<Cache>, Statistics)>
 where
     C: ModelOutputSuccess,
     D: ModelOutputFailure,
 {
+    let monitor = Monitor::start(&offline_options)?;
+    let OfflineOptions {
+        inverted,
+        alpha,
+        l,
+        carbon_budget,
+        ..
+    } = offline_options;
     assert(l.is_none(), Failure::UnsupportedLConstrainedMovement)?;
+    assert(carbon_budget.is_none(), Failure::UnsupportedCarbonBudget)?;
+    verify_integral(&p)?;
 
-    let (t_init, mut paths) = read_cache(cache, || (1, HashMap::new()));
+    let (t_init, mut costs, mut layers) = match cache {
+        Some(cache) => {
+            cache.verify(p.t_end, values.len())?;
+            let Cache { t, costs, layers } = cache;
+            (t + 1, costs, layers.continued()?)
+        }
+        None => {
+            // before the first time slot, only the initial vertice (all dimensions are $0$) is reachable
+            let mut costs = vec![f64::INFINITY; values.len()];
+            costs[0] = 0.;
+            (1, costs, Layers::new(spill_dir)?)
+        }
+    };
 
     debug!("from time slot `{}` to time slot `{}`", t_init, p.t_end);
 
+    // transition costs depend on the configurations of consecutive time slots which are not known when powering up or down dimension by dimension
+    let dense = p.hitting_cost.has_transition();
+    if dense && pruning.is_some() {
+        warn!("transition costs require dense layers which are not pruned");
+    }
+
+    let mut statistics = Statistics::default();
     for t in t_init..=p.t_end {
-        handle_layer(&p, alpha, inverted, t, true, &values, &mut paths, None)?;
-        handle_layer(&p, alpha, inverted, t, false, &values, &mut paths, None)?;
+        monitor.check()?;
+        let (new_costs, layer) = if dense {
+            handle_dense_layer(
+                &p,
+                alpha,
+                inverted,
+                t,
+                &values,
+                &costs,
+                &mut statistics,
+            )
+        } else {
+            handle_layer(
+                &p,
+                alpha,
+                inverted,
+                t,
+                &values,
+                &costs,
+                pruning,
+                &mut statistics,
+            )
+        };
+        layers.push(layer)?;
+        costs = new_costs;
+        monitor.report(
+            t,
+            p.t_end,
+            Some(costs.iter().cloned().fold(f64::INFINITY, f64::min)),
+        );
     }
 
-    Ok(CachedPath {
-        path: paths[&Vertice {
-            config: build_base_config(p.d, &p.bounds, &values, true),
-            powering_up: false,
-        }]
-            .clone(),
-        cache: Cache { t: p.t_end, paths },
-    })
+    // the final vertice is the powering down vertice where all dimensions are $0$
+    let (id, cost) = if dense {
+        final_vertice(&p, alpha, inverted, &values, &costs)
+    } else {
+        (0, costs[0])
+    };
+    if let Some(pruning) = pruning {
+        assert(
+            cost.is_finite(),
+            Failure::UpperBoundBelowOptimum(pruning.upper_bound),
+        )?;
+    }
+    // any path through a discarded vertice costs at least as much as reaching it as hitting costs are non-negative
+    let lower_bound = match pruning {
+        Some(pruning) if !pruning.admissible => {
+            cost.min(statistics.min_pruned_cost)
+        }
+        _ => cost,
+    };
+    let xs = reconstruct(&values, &layers, p.t_end, id)?;
+    Ok((
+        CachedPath {
+            path: Path { xs, cost },
+            lower_bound,
+            cache: Cache {
+                t: p.t_end,
+                costs,
+                layers,
+            },
+        },
+        statistics,
+    ))
 }
 
-struct HandleLayerState {
-    k: i32,
-    configs: Vec<InternalConfig>,
+/// Verifies that no dimension of the problem models a continuous quantity (e.g. stored energy or deferred load),
+/// as restricting such dimensions to integral values distorts the problem.
+pub fn verify_integral<C, D>(
+    p: &IntegralSimplifiedSmoothedConvexOptimization<'_, C, D>,
+) -> Result<()> {
+    let continuous_dimensions = p.hitting_cost.continuous_dimensions();
+    assert(
+        continuous_dimensions.is_empty(),
+        Failure::UnsupportedContinuousDimensions(
+            continuous_dimensions.to_vec(),
+        ),
+    )
 }
 
+/// Computes the minimal costs of all powering down vertices of time slot $t$ given the minimal costs of the previous layer.
 #[allow(clippy::too_many_arguments)]
 fn handle_layer<C, D>(
     p: &IntegralSimplifiedSmoothedConvexOptimization<C, D>,
     alpha: f64,
     inverted: bool,
     t: i32,
-    powering_up: bool,
     values: &Values,
-    paths: &mut Paths<Vertice>,
-    state_: Option<HandleLayerState>,
-) -> Result<()>
+    prev_costs: &[f64],
+    pruning: Option<&Pruning>,
+    statistics: &mut Statistics,
+) -> (Vec<f64>, Layer)
 where
     C: ModelOutputSuccess,
     D: ModelOutputFailure,
 {
-    let base_k = 1;
-    let mut state = state_.unwrap_or_else(|| HandleLayerState {
-        k: base_k,
-        configs: vec![build_base_config(p.d, &p.bounds, values, powering_up)],
-    });
+    let n = values.len();
+    let strides = values.strides();
+
+    let movement_cost = |k: usize, i: usize| {
+        alpha
+            * p.switching_cost[k]
+            * (values.values[i + 1] - values.values[i]) as f64
+    };
 
-    if state.k < 1 || state.k > p.d {
-        return Ok(());
+    // powering up phase; predecessors have smaller indices
+    let mut up: Vec<Label> = Vec::with_capacity(n);
+    for id in 0..n {
+        let indices = values.indices(id);
+        let mut candidates = (0..p.d as usize)
+            .filter(|&k| indices[k] > 0)
+            .map(|k| {
+                let predecessor = up[id - strides[k]];
+                let cost = if inverted {
+                    0.
+                } else {
+                    movement_cost(k, indices[k] - 1)
+                };
+                (
+                    cost,
+                    Label {
+                        cost: predecessor.cost + cost,
+                        ..predecessor
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
+        // inaction edge from the powering down phase of the previous layer
+        candidates.push((
+            0.,
+            Label {
+                cost: prev_costs[id],
+                x: id,
+                from: id,
+            },
+        ));
+        up.push(pick(candidates));
     }
-    let mut added_configs = vec![];
 
-    let dir = if powering_up {
-        Direction::Next
-    } else {
-        Direction::Previous
-    };
-    for base_config in state.configs.iter() {
-        let mut config = if state.k == base_k {
-            base_config.clone()
-        } else {
-            build_config(dir, values, base_config, state.k).unwrap()
-        };
-        if state.k != base_k {
-            added_configs.push(config.clone());
+    // hitting costs are independent of each other; they are only evaluated for vertices which may be on a shortest path
+    let hitting_costs: Vec<Option<f64>> = (0..n)
+        .into_par_iter()
+        .map(|id| {
+            let cost = up[id].cost;
+            let expand = cost.is_finite()
+                && match pruning {
+                    None => true,
+                    Some(pruning) => {
+                        let indices = values.indices(id);
+                        !pruning.exceeds(
+                            cost + Pruning::lower_bound(
+                                &pruning.hitting_cost[t as usize - 1],
+                                &indices,
+                            ) + Pruning::lower_bound(
+                                &pruning.remaining_cost[t as usize],
+                                &indices,
+                            ),
+                        )
+                    }
+                };
+            if expand {
+                Some(p.hit_cost(t, values.config(id)).cost.raw())
+            } else {
+                None
+            }
+        })
+        .collect();
+    for (label, hitting_cost) in up.iter().zip(&hitting_costs) {
+        if hitting_cost.is_some() {
+            statistics.expanded += 1;
+        } else if label.cost.is_finite() {
+            statistics.pruned += 1;
+            statistics.min_pruned_cost =
+                statistics.min_pruned_cost.min(label.cost);
         }
-        loop {
-            handle_config(
-                p,
-                alpha,
-                inverted,
-                t,
-                state.k,
-                (powering_up, &config),
-                values,
-                paths,
-            );
-            config = match build_config(dir, values, &config, state.k) {
-                None => break,
-                Some(config) => {
-                    added_configs.push(config.clone());
-                    config
-                }
-            };
+    }
+
+    // powering down phase; predecessors have larger indices
+    let mut down = up.clone();
+    for id in (0..n).rev() {
+        let indices = values.indices(id);
+        let mut candidates = (0..p.d as usize)
+            .filter(|&k| indices[k] < values.bound_indices[k])
+            .map(|k| {
+                let predecessor = down[id + strides[k]];
+                let cost = if inverted {
+                    movement_cost(k, indices[k])
+                } else {
+                    0.
+                };
+                (
+                    cost,
+                    Label {
+                        cost: predecessor.cost + cost,
+                        ..predecessor
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
+        // inaction edge from the powering up phase paying the hitting cost
+        if let Some(hitting_cost) = hitting_costs[id] {
+            candidates.push((
+                hitting_cost,
+                Label {
+                    cost: up[id].cost + hitting_cost,
+                    x: id,
+                    from: up[id].from,
+                },
+            ));
+        }
+        down[id] = pick(candidates);
+        if let Some(pruning) = pruning {
+            if pruning.exceeds(
+                down[id].cost
+                    + Pruning::lower_bound(
+                        &pruning.remaining_cost[t as usize],
+                        &indices,
+                    ),
+            ) {
+                statistics.min_pruned_cost =
+                    statistics.min_pruned_cost.min(down[id].cost);
+                down[id].cost = f64::INFINITY;
+            }
         }
     }
 
-    state.configs.append(&mut added_configs);
-    state.k += 1;
-    handle_layer(
-        p,
-        alpha,
-        inverted,
-        t,
-        powering_up,
-        values,
-        paths,
-        Some(state),
-    )
+    let costs = down.iter().map(|label| label.cost).collect();
+    let layer = down
+        .into_iter()
+        .map(|label| BackPointer {
+            x: label.x as u32,
+            from: label.from as u32,
+        })
+        .collect();
+    (costs, layer)
 }
 
-/// updates paths up to vertex representing some config; then returns next config
-#[allow(clippy::too_many_arguments)]
-fn handle_config<C, D>(
+/// Computes the minimal costs of all vertices of time slot $t$ given the minimal costs of the previous layer
+/// by considering the edges between all pairs of vertices of consecutive layers which also pay the transition cost.
+/// The cost of a vertice is the minimal cost of a schedule ending in its config, i.e. it includes the hitting cost of time slot $t$.
+/// Vertices are not pruned as the bounds do not account for transition costs.
+fn handle_dense_layer<C, D>(
     p: &IntegralSimplifiedSmoothedConvexOptimization<C, D>,
     alpha: f64,
     inverted: bool,
     t: i32,
-    k: i32,
-    (powering_up, config): (bool, &InternalConfig),
     values: &Values,
-    paths: &mut Paths<Vertice>,
-) where
+    prev_costs: &[f64],
+    statistics: &mut Statistics,
+) -> (Vec<f64>, Layer)
+where
     C: ModelOutputSuccess,
     D: ModelOutputFailure,
 {
-    // find all immediate predecessors
-    let predecessors = find_immediate_predecessors(
-        p,
-        alpha,
-        inverted,
-        t,
-        powering_up,
-        k,
-        config,
-        values,
-    );
-
-    // determine shortest path
-    let opt_predecessor = find_optimal_predecessor(predecessors, paths);
-
-    // update paths
-    update_paths(
-        paths,
-        opt_predecessor,
-        &Vertice {
-            config: config.clone(),
-            powering_up,
-        },
-    );
-}
-
-fn build_base_config(
-    d: i32,
-    bounds: &Vec<i32>,
-    values: &Values,
-    bottom: bool,
-) -> InternalConfig {
-    if bottom {
-        InternalConfig {
-            config: IntegralConfig::repeat(0, d),
-            indices: Config::repeat(0, d),
-        }
-    } else {
-        InternalConfig {
-            config: IntegralConfig::new(bounds.clone()),
-            indices: Config::new(values.bound_indices.clone()),
-        }
-    }
-}
-
-#[derive(Clone, Copy, Debug)]
-enum Direction {
-    Next,
-    Previous,
-}
+    let n = values.len();
+    let predecessors: Vec<_> = (0..n)
+        .filter(|&from| prev_costs[from].is_finite())
+        .map(|from| (from, values.config(from)))
+        .collect();
 
-/// selects the next config w.r.t. some dimension based on a given config in a given direction
-fn build_config(
-    dir: Direction,
-    values: &Values,
-    current_config: &InternalConfig,
-    k_: i32,
-) -> Option<InternalConfig> {
-    let k = k_ as usize - 1;
-    let mut config = current_config.clone();
-    let i = match dir {
-        Direction::Next => {
-            if current_config.indices[k] == values.bound_indices[k] {
-                return None;
-            } else {
-                current_config.indices[k] + 1
-            }
-        }
-        Direction::Previous => {
-            if current_config.indices[k] == 0 {
-                return None;
+    let labels: Vec<Label> = (0..n)
+        .into_par_iter()
+        .map(|id| {
+            let x = values.config(id);
+            let predecessor = pick(
+                predecessors
+                    .iter()
+                    .map(|(from, prev_x)| {
+                        let cost = alpha
+                            * p.movement(prev_x.clone(), x.clone(), inverted)
+                                .raw()
+                            + p.transition_cost(t, prev_x.clone(), x.clone())
+                                .cost
+                                .raw();
+                        (
+                            cost,
+                            Label {
+                                cost: prev_costs[*from] + cost,
+                                x: id,
+                                from: *from,
+                            },
+                        )
+                    })
+                    .filter(|(cost, _)| cost.is_finite())
+                    .collect(),
+            );
+            if predecessor.cost.is_finite() {
+                Label {
+                    cost: predecessor.cost + p.hit_cost(t, x).cost.raw(),
+                    ..predecessor
+                }
             } else {
-                current_config.indices[k] - 1
+                predecessor
             }
-        }
-    };
+        })
+        .collect();
+    statistics.expanded +=
+        labels.iter().filter(|label| label.cost.is_finite()).count() as u64;
+    statistics.dense_layers += 1;
 
-    config.config[k] = values.values[i];
-    config.indices[k] = i;
-    Some(config)
+    let costs = labels.iter().map(|label| label.cost).collect();
+    let layer = labels
+        .into_iter()
+        .map(|label| BackPointer {
+            x: label.x as u32,
+            from: label.from as u32,
+        })
+        .collect();
+    (costs, layer)
 }
 
-#[allow(clippy::too_many_arguments)]
-fn find_immediate_predecessors<C, D>(
+/// Picks the vertice of the final layer of a dense graph from which powering down is cheapest. Returns its index and the cost of the schedule.
+fn final_vertice<C, D>(
     p: &IntegralSimplifiedSmoothedConvexOptimization<C, D>,
     alpha: f64,
     inverted: bool,
-    t: i32,
-    powering_up: bool,
-    k: i32,
-    config: &InternalConfig,
-    all_values: &Values,
-) -> Vec<Edge>
+    values: &Values,
+    costs: &[f64],
+) -> (usize, f64)
 where
     C: ModelOutputSuccess,
     D: ModelOutputFailure,
 {
-    let mut predecessors: Vec<Edge> = (1..=k)
-        .into_par_iter()
-        .filter_map(|l| {
-            let prev_config = build_config(
-                if powering_up {
-                    Direction::Previous
+    let zero = values.config(0);
+    let label = pick(
+        (0..values.len())
+            .filter(|&id| costs[id].is_finite())
+            .map(|id| {
+                let cost = if inverted {
+                    alpha
+                        * p.movement(values.config(id), zero.clone(), inverted)
+                            .raw()
                 } else {
-                    Direction::Next
-                },
-                all_values,
-                config,
-                l,
-            );
-            match prev_config {
-                None => None,
-                Some(prev_config) => Some(Edge {
-                    from: Vertice {
-                        config: prev_config.clone(),
-                        powering_up,
+                    0.
+                };
+                (
+                    cost,
+                    Label {
+                        cost: costs[id] + cost,
+                        x: id,
+                        from: id,
                     },
-                    cost: if powering_up && !inverted
-                        || !powering_up && inverted
-                    {
-                        alpha
-                            * p.switching_cost[l as usize - 1]
-                            * scalar_movement(
-                                config.config[l as usize - 1],
-                                prev_config.config[l as usize - 1],
-                                inverted,
-                            ) as f64
-                    } else {
-                        0.
-                    },
-                }),
-            }
-        })
-        .collect();
-
-    if t > 1 || !powering_up {
-        let inaction = Edge {
-            from: Vertice {
-                config: config.clone(),
-                powering_up: !powering_up,
-            },
-            cost: if powering_up {
-                0.
-            } else {
-                p.hit_cost(t, config.config.clone()).cost.raw()
-            },
-        };
-        predecessors.push(inaction);
-    }
-
-    if !(t == 1 && powering_up && config.config == Config::repeat(0, p.d)) {
-        assert!(
-            !predecessors.is_empty(),
-            "Only the initial vertice should not have a predecessor."
-        );
-    }
-
-    predecessors
+                )
+            })
+            .collect(),
+    );
+    (label.x, label.cost)
 }
 
-fn find_optimal_predecessor(
-    predecessors: Vec<Edge>,
-    paths: &mut Paths<Vertice>,
-) -> Option<(Edge, Path)> {
+/// Picks the candidate with minimal cost. Candidates are given as pairs of edge cost and resulting label.
+/// Takes the smallest possible action if costs are equal. Without candidates, the vertice is unreachable.
+fn pick(candidates: Vec<(f64, Label)>) -> Label {
     assert!(
-        predecessors.is_empty()
-            || predecessors
-                .iter()
-                .any(|predecessor| predecessor.cost.is_finite()),
+        candidates.is_empty()
+            || candidates.iter().any(|(cost, _)| cost.is_finite()),
         "Problem is infeasible. Did not find a predecessor with a finite cost."
     );
-    predecessors
-        .into_par_iter()
-        .fold(
-            || None,
-            |picked: Option<(Edge, Path)>, predecessor| {
-                let path = &paths[&predecessor.from];
-                let new_cost = path.cost + predecessor.cost;
-
-                // take smallest possible action if costs are equal
-                let picked_cost = picked.clone().map_or_else(
-                    || f64::INFINITY,
-                    |(picked_predecessor, path)| {
-                        path.cost + picked_predecessor.cost
-                    },
-                );
-                if new_cost < picked_cost {
-                    Some((predecessor, path.clone()))
-                } else {
-                    picked
-                }
-            },
-        )
-        .reduce(
-            || None,
-            |a, b| match a {
-                Some(a) => Some(match b {
-                    Some(b) => {
-                        if a.0.cost + a.1.cost <= b.0.cost + b.1.cost {
-                            a
-                        } else {
-                            b
-                        }
-                    }
-                    None => a,
-                }),
-                None => b,
-            },
-        )
-}
-
-fn update_paths(
-    paths: &mut Paths<Vertice>,
-    predecessor: Option<(Edge, Path)>,
-    to: &Vertice,
-) {
-    let path = match predecessor {
-        None => Path {
-            xs: IntegralSchedule::empty(),
-            cost: 0.,
-        },
-        Some((predecessor, path)) => {
-            let xs = if predecessor.from.powering_up && !to.powering_up {
-                path.xs.extend(to.config.config.clone())
+    candidates
+        .into_iter()
+        .map(|(_, label)| label)
+        .reduce(|picked, label| {
+            if label.cost < picked.cost {
+                label
             } else {
-                path.xs.clone()
-            };
-            Path {
-                xs,
-                cost: path.cost + predecessor.cost,
+                picked
             }
-        }
-    };
-    paths.insert(to.clone(), path);
+        })
+        .unwrap_or(Label {
+            cost: f64::INFINITY,
+            x: 0,
+            from: 0,
+        })
+}
+
+/// Follows the back-pointers from the vertice with index `id` in the final layer.
+fn reconstruct(
+    values: &Values,
+    layers: &Layers,
+    t_end: i32,
+    id: usize,
+) -> Result<IntegralSchedule> {
+    let mut id = id;
+    let mut xs = Vec::with_capacity(t_end as usize);
+    for t in (1..=t_end).rev() {
+        let BackPointer { x, from } = layers.get(t)?[id];
+        xs.push(values.config(x as usize));
+        id = from as usize;
+    }
+    xs.reverse();
+    Ok(IntegralSchedule::new(xs))
 }
@@ -1,4 +1,3 @@
-use super::graph_search::Vertice;
 use crate::algorithms::offline::graph_search::{Cache, CachedPath};
 use crate::algorithms::offline::multi_dimensional::{
     graph_search::graph_search, Values,
@@ -9,11 +8,16 @@ use crate::problem::IntegralSimplifiedSmoothedConvexOptimization;
 use crate::result::Result;
 use log::debug;
 use pyo3::prelude::*;
+use std::path::PathBuf;
 
 #[pyclass(name = "OptimalGraphSearchOptions")]
 #[derive(Clone, Default)]
 pub struct Options {
-    pub cache: Option<Cache<Vertice>>,
+    pub cache: Option<Cache>,
+    /// Directory in which the back-pointers of all layers are written to a unique subdirectory of the run instead of being kept in memory.
+    /// The subdirectory is removed once the result (including its cache) is dropped.
+    #[pyo3(get, set)]
+    pub spill_dir: Option<PathBuf>,
 }
 #[pymethods]
 impl Options {
@@ -26,9 +30,9 @@ impl Options {
 /// Graph-Based Optimal Algorithm
 pub fn optimal_graph_search<C, D>(
     p: IntegralSimplifiedSmoothedConvexOptimization<'_, C, D>,
-    Options { cache }: Options,
+    Options { cache, spill_dir }: Options,
     offline_options: OfflineOptions,
-) -> Result<CachedPath<Cache<Vertice>>>
+) -> Result<CachedPath<Cache>>
 where
     C: ModelOutputSuccess,
     D: ModelOutputFailure,
@@ -39,5 +43,5 @@ where
         bound_indices: p.bounds.iter().map(|&m| m as usize).collect(),
     };
     debug!("starting with `{}` values", max_bound);
-    graph_search(p, values, cache, offline_options)
+    graph_search(p, values, cache, spill_dir, offline_options)
 }
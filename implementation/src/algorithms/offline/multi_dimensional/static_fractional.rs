@@ -1,11 +1,12 @@
 use crate::algorithms::offline::{OfflineOptions, PureOfflineResult};
 use crate::config::Config;
 use crate::model::{ModelOutputFailure, ModelOutputSuccess};
-use crate::numerics::convex_optimization::{find_minimizer, WrappedObjective};
+use crate::numerics::convex_optimization::{minimize, WrappedObjective};
 use crate::problem::{FractionalSmoothedConvexOptimization, Problem};
 use crate::result::{Failure, Result};
 use crate::schedule::Schedule;
 use crate::utils::assert;
+use noisy_float::prelude::*;
 
 #[derive(Clone)]
 struct ObjectiveData<'a, C, D> {
@@ -13,11 +14,25 @@ struct ObjectiveData<'a, C, D> {
     alpha: f64,
 }
 
+#[derive(Clone)]
+struct ConstraintData<'a, C, D> {
+    p: FractionalSmoothedConvexOptimization<'a, C, D>,
+    carbon_budget: f64,
+}
+
 /// Algorithm computing the static fractional optimum.
+///
+/// The carbon budget is enforced as a constraint on the emissions reported by the model.
 pub fn static_fractional<C, D>(
     p: FractionalSmoothedConvexOptimization<'_, C, D>,
     _: (),
-    OfflineOptions { inverted, alpha, l }: OfflineOptions,
+    OfflineOptions {
+        inverted,
+        alpha,
+        l,
+        carbon_budget,
+        ..
+    }: OfflineOptions,
 ) -> Result<PureOfflineResult<f64>>
 where
     C: ModelOutputSuccess,
@@ -32,18 +47,43 @@ where
     let t_end = p.t_end;
 
     let bounds = p.bounds.clone();
-    let objective =
-        WrappedObjective::new(ObjectiveData { p, alpha }, |raw_x, data| {
+    let objective = WrappedObjective::new(
+        ObjectiveData {
+            p: p.clone(),
+            alpha,
+        },
+        |raw_x, data| {
             let x = Config::new(raw_x.to_vec());
             let xs = Schedule::repeat(x, data.p.t_end);
             data.p
                 .alpha_unfair_objective_function(&xs, data.alpha)
                 .unwrap()
                 .cost
-        });
+        },
+    );
+
+    let constraints = match carbon_budget {
+        Some(carbon_budget) => vec![WrappedObjective::new(
+            ConstraintData {
+                p: p.clone(),
+                carbon_budget,
+            },
+            |raw_x, data| {
+                let x = Config::new(raw_x.to_vec());
+                let xs = Schedule::repeat(x, data.p.t_end);
+                let emissions =
+                    data.p.objective_function(&xs).unwrap().output.emissions();
+                n64(emissions) - n64(data.carbon_budget)
+            },
+        )],
+        None => vec![],
+    };
 
-    let (raw_x, _) = find_minimizer(objective, bounds);
+    let (raw_x, _) = minimize(objective, bounds, None, constraints);
     let x = Config::new(raw_x.to_vec());
     let xs = Schedule::repeat(x, t_end);
-    Ok(PureOfflineResult { xs })
+    Ok(PureOfflineResult {
+        xs,
+        certificate: None,
+    })
 }
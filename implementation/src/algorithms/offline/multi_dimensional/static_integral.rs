@@ -1,7 +1,8 @@
 use log::debug;
 
-use crate::algorithms::offline::{OfflineOptions, PureOfflineResult};
+use crate::algorithms::offline::{Monitor, OfflineOptions, PureOfflineResult};
 use crate::config::IntegralConfig;
+use crate::cost::Cost;
 use crate::model::{ModelOutputFailure, ModelOutputSuccess};
 use crate::problem::{IntegralSmoothedConvexOptimization, Problem};
 use crate::result::{Failure, Result};
@@ -11,25 +12,66 @@ use crate::utils::assert;
 /// Algorithm computing the static integral optimum.
 ///
 /// Warning: do not use in practice, this algorithm is naive and has an exponential runtime.
+///
+/// Once the time budget is exhausted (or the algorithm is cancelled), the best config found so far is returned.
+/// Configs whose emissions exceed the carbon budget are considered to have infinite cost.
 pub fn static_integral<C, D>(
     p: IntegralSmoothedConvexOptimization<'_, C, D>,
     _: (),
-    OfflineOptions { inverted, alpha, l }: OfflineOptions,
+    offline_options: OfflineOptions,
 ) -> Result<PureOfflineResult<i32>>
 where
     C: ModelOutputSuccess,
     D: ModelOutputFailure,
 {
+    let monitor = Monitor::start(&offline_options)?;
+    let OfflineOptions {
+        inverted,
+        alpha,
+        l,
+        carbon_budget,
+        ..
+    } = offline_options;
     assert(!inverted, Failure::UnsupportedInvertedCost)?;
     assert(
         l.is_none() || l == Some(0.),
         Failure::UnsupportedLConstrainedMovement,
     )?;
 
-    let (config, _) =
-        check_configs(&p, alpha, 0, IntegralConfig::repeat(0, p.d))?;
+    let mut search = Search {
+        monitor,
+        checked: 0,
+        configs: p.bounds.iter().fold(1, |configs, &(lower, upper)| {
+            configs.saturating_mul(upper - lower + 1)
+        }),
+        picked_cost: f64::INFINITY,
+        carbon_budget,
+    };
+    let (config, _) = check_configs(
+        &p,
+        alpha,
+        0,
+        IntegralConfig::repeat(0, p.d),
+        &mut search,
+    )?;
     let xs = IntegralSchedule::new(vec![config; p.t_end as usize]);
-    Ok(PureOfflineResult { xs })
+    Ok(PureOfflineResult {
+        xs,
+        certificate: None,
+    })
+}
+
+/// State of the exhaustive search.
+struct Search {
+    monitor: Monitor,
+    /// Number of checked configs.
+    checked: i32,
+    /// Total number of configs.
+    configs: i32,
+    /// Minimal cost of all checked configs.
+    picked_cost: f64,
+    /// Upper bound on the cumulative carbon emissions.
+    carbon_budget: Option<f64>,
 }
 
 fn check_configs<C, D>(
@@ -37,6 +79,7 @@ fn check_configs<C, D>(
     alpha: f64,
     k: usize,
     mut base_config: IntegralConfig,
+    search: &mut Search,
 ) -> Result<(IntegralConfig, f64)>
 where
     C: ModelOutputSuccess,
@@ -47,9 +90,14 @@ where
         let mut picked_config = base_config.clone();
         let mut picked_cost = f64::INFINITY;
         for j in p.bounds[k].0..=p.bounds[k].1 {
+            // at least one config is checked in each dimension so that a schedule is returned when interrupted
+            if j > p.bounds[k].0 && search.monitor.interrupted() {
+                debug!("Interrupted while checking dimension {}.", k + 1);
+                break;
+            }
             base_config[k] = j;
             let (config, cost) =
-                check_configs(p, alpha, k + 1, base_config.clone())?;
+                check_configs(p, alpha, k + 1, base_config.clone(), search)?;
             if cost < picked_cost {
                 picked_config = config;
                 picked_cost = cost;
@@ -59,15 +107,26 @@ where
         }
         Ok((picked_config, picked_cost))
     } else {
-        let cost = p
-            .objective_function(&IntegralSchedule::new(vec![
+        let Cost { cost, output } =
+            p.objective_function(&IntegralSchedule::new(vec![
                 base_config
                     .clone();
                 p.t_end as usize
-            ]))?
-            .cost
-            .raw();
+            ]))?;
+        let cost = match search.carbon_budget {
+            Some(carbon_budget) if output.emissions() > carbon_budget => {
+                f64::INFINITY
+            }
+            _ => cost.raw(),
+        };
         debug!("Config {:?} has associated cost {:?}.", base_config, cost);
+        search.checked += 1;
+        search.picked_cost = search.picked_cost.min(cost);
+        search.monitor.report(
+            search.checked,
+            search.configs,
+            Some(search.picked_cost),
+        );
         Ok((base_config, cost))
     }
 }
@@ -0,0 +1,76 @@
+use crate::algorithms::offline::{Certificate, OfflineOptions, OfflineResult};
+use crate::algorithms::stochastic::two_stage;
+pub use crate::algorithms::stochastic::{ScenarioSchedules, MAX_SCENARIOS};
+use crate::config::Config;
+use crate::model::{ModelOutputFailure, ModelOutputSuccess};
+use crate::problem::FractionalSimplifiedSmoothedConvexOptimization;
+use crate::result::{Failure, Result};
+use crate::schedule::FractionalSchedule;
+use crate::utils::assert;
+use pyo3::prelude::*;
+
+#[pyclass(name = "StochasticConvexOptimizationOptions")]
+#[derive(Clone)]
+pub struct Options {
+    /// Number of initial time slots which are decided once for all scenarios. Default is $1$.
+    #[pyo3(get, set)]
+    pub here_and_now: i32,
+}
+impl Default for Options {
+    fn default() -> Self {
+        Options { here_and_now: 1 }
+    }
+}
+#[pymethods]
+impl Options {
+    #[new]
+    fn constructor(here_and_now: i32) -> Self {
+        Options { here_and_now }
+    }
+}
+
+impl OfflineResult<f64> for ScenarioSchedules {
+    /// The recourse decisions are averaged over all scenarios.
+    fn xs(self) -> FractionalSchedule {
+        self.expected_schedule()
+    }
+
+    /// The program is solved numerically without a dual bound, hence, no certificate is provided.
+    fn certificate(&self) -> Option<Certificate> {
+        None
+    }
+}
+
+/// Scenario-Based Stochastic Convex Optimization
+///
+/// Solves a two-stage stochastic program where the $s$-th scenario pays the hitting costs of the $s$-th sample of the predicted hitting costs.
+/// The first `here_and_now` time slots are decided once for all scenarios, all remaining time slots are decided separately for each scenario.
+/// Supports at most `MAX_SCENARIOS` scenarios.
+pub fn stochastic_co<C, D>(
+    p: FractionalSimplifiedSmoothedConvexOptimization<'_, C, D>,
+    Options { here_and_now }: Options,
+    OfflineOptions {
+        inverted,
+        alpha,
+        l,
+        carbon_budget,
+        ..
+    }: OfflineOptions,
+) -> Result<ScenarioSchedules>
+where
+    C: ModelOutputSuccess,
+    D: ModelOutputFailure,
+{
+    assert(!inverted, Failure::UnsupportedInvertedCost)?;
+    assert(l.is_none(), Failure::UnsupportedLConstrainedMovement)?;
+    assert(carbon_budget.is_none(), Failure::UnsupportedCarbonBudget)?;
+    assert(
+        0 <= here_and_now && here_and_now <= p.t_end,
+        Failure::Invalid(format!(
+            "The number of here-and-now time slots must be in [0, {}] (was {}).",
+            p.t_end, here_and_now
+        )),
+    )?;
+
+    two_stage(&p, &Config::repeat(0., p.d), here_and_now, alpha)
+}
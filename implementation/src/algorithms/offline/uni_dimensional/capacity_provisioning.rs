@@ -1,8 +1,10 @@
 use crate::algorithms::capacity_provisioning::{Bounded, BoundsMemory};
-use crate::algorithms::offline::{OfflineOptions, OfflineResult};
+use crate::algorithms::offline::{
+    Certificate, Monitor, OfflineOptions, OfflineResult,
+};
 use crate::config::Config;
 use crate::model::{ModelOutputFailure, ModelOutputSuccess};
-use crate::problem::FractionalSimplifiedSmoothedConvexOptimization;
+use crate::problem::{FractionalSimplifiedSmoothedConvexOptimization, Problem};
 use crate::result::{Failure, Result};
 use crate::schedule::{FractionalSchedule, Schedule};
 use crate::utils::{assert, project};
@@ -13,25 +15,40 @@ pub struct WithBounds {
     pub xs: FractionalSchedule,
     /// Lower and upper bounds.
     pub bounds: Vec<BoundsMemory<f64>>,
+    /// Cost of the (optimal) schedule.
+    pub cost: f64,
 }
 impl OfflineResult<f64> for WithBounds {
     fn xs(self) -> FractionalSchedule {
         self.xs
     }
+
+    fn certificate(&self) -> Option<Certificate> {
+        Some(Certificate::optimal(self.cost))
+    }
 }
 
 /// Backward-Recurrent Capacity Provisioning
 pub fn brcp<C, D>(
     p: FractionalSimplifiedSmoothedConvexOptimization<'_, C, D>,
     _: (),
-    OfflineOptions { inverted, alpha, l }: OfflineOptions,
+    offline_options: OfflineOptions,
 ) -> Result<WithBounds>
 where
     C: ModelOutputSuccess,
     D: ModelOutputFailure,
 {
+    let monitor = Monitor::start(&offline_options)?;
+    let OfflineOptions {
+        inverted,
+        alpha,
+        l,
+        carbon_budget,
+        ..
+    } = offline_options;
     assert(!inverted, Failure::UnsupportedInvertedCost)?;
     assert(l.is_none(), Failure::UnsupportedLConstrainedMovement)?;
+    assert(carbon_budget.is_none(), Failure::UnsupportedCarbonBudget)?;
     assert(p.d == 1, Failure::UnsupportedProblemDimension(p.d))?;
 
     let mut xs = Schedule::empty();
@@ -40,12 +57,15 @@ where
     let mut x = 0.;
     let mut bound;
     for t in (1..=p.t_end).rev() {
+        monitor.check()?;
         (x, bound) = next(&p, alpha, t, x)?;
         xs.shift(Config::single(x));
         bounds.insert(0, bound);
+        monitor.report(p.t_end - t + 1, p.t_end, None);
     }
 
-    Ok(WithBounds { xs, bounds })
+    let cost = p.alpha_unfair_objective_function(&xs, alpha)?.cost.raw();
+    Ok(WithBounds { xs, bounds, cost })
 }
 
 fn next<C, D>(
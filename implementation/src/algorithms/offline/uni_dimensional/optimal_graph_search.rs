@@ -1,5 +1,5 @@
 use crate::algorithms::offline::graph_search::{Path, Paths};
-use crate::algorithms::offline::OfflineOptions;
+use crate::algorithms::offline::{Monitor, OfflineOptions};
 use crate::config::{Config, IntegralConfig};
 use crate::cost::{Cost, CostFn, SingleCostFn};
 use crate::model::{ModelOutputFailure, ModelOutputSuccess};
@@ -43,13 +43,22 @@ impl Options {
 pub fn optimal_graph_search<'a, C, D>(
     mut p: IntegralSimplifiedSmoothedConvexOptimization<'a, C, D>,
     Options { x_start }: Options,
-    OfflineOptions { inverted, alpha, l }: OfflineOptions,
+    offline_options: OfflineOptions,
 ) -> Result<Path>
 where
     C: ModelOutputSuccess + 'a,
     D: ModelOutputFailure + 'a,
 {
+    let monitor = Monitor::start(&offline_options)?;
+    let OfflineOptions {
+        inverted,
+        alpha,
+        l,
+        carbon_budget,
+        ..
+    } = offline_options;
     assert(l.is_none(), Failure::UnsupportedLConstrainedMovement)?;
+    assert(carbon_budget.is_none(), Failure::UnsupportedCarbonBudget)?;
     assert(p.d == 1, Failure::UnsupportedProblemDimension(p.d))?;
 
     if !is_pow_of_2(p.bounds[0]) {
@@ -62,8 +71,14 @@ where
         0
     };
 
-    let mut path =
-        find_schedule(&p, select_initial_rows(&p), alpha, inverted, x_start);
+    let mut path = find_schedule(
+        &p,
+        select_initial_rows(&p),
+        alpha,
+        inverted,
+        x_start,
+        &monitor,
+    )?;
     for k in (0..k_init).rev() {
         path = find_schedule(
             &p,
@@ -71,7 +86,8 @@ where
             alpha,
             inverted,
             x_start,
-        );
+            &monitor,
+        )?;
     }
 
     Ok(path)
@@ -133,7 +149,8 @@ fn find_schedule<C, D>(
     alpha: f64,
     inverted: bool,
     x_start: i32,
-) -> Path
+    monitor: &Monitor,
+) -> Result<Path>
 where
     C: ModelOutputSuccess,
     D: ModelOutputFailure,
@@ -148,16 +165,26 @@ where
 
     let mut prev_rows = vec![x_start];
     for t in 1..=p.t_end {
+        monitor.check()?;
         let rows = select_rows(t);
         for &j in &rows {
             find_shortest_subpath(
                 p, &mut paths, t, &prev_rows, j, alpha, inverted,
             );
         }
+        monitor.report(
+            t,
+            p.t_end,
+            Some(
+                rows.iter()
+                    .map(|&j| paths[&Vertice(t, j)].cost)
+                    .fold(f64::INFINITY, f64::min),
+            ),
+        );
         prev_rows = rows;
     }
 
-    prev_rows.iter().fold(
+    Ok(prev_rows.iter().fold(
         Path {
             xs: Schedule::empty(),
             cost: f64::INFINITY,
@@ -174,7 +201,7 @@ where
                 result
             }
         },
-    )
+    ))
 }
 
 fn find_shortest_subpath<C, D>(
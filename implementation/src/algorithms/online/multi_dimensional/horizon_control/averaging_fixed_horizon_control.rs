@@ -1,4 +1,4 @@
-use super::next;
+use super::{next, next_stochastic, RunningPeaks};
 use crate::algorithms::online::{FractionalStep, Step};
 use crate::config::{Config, FractionalConfig};
 use crate::model::{ModelOutputFailure, ModelOutputSuccess};
@@ -16,6 +16,8 @@ use serde_derive::{Deserialize, Serialize};
 pub struct Memory {
     /// Last initial configs for each iteration $k$.
     pub prev_x: Vec<FractionalConfig>,
+    /// Running peaks of peak charges up to the last initial configs for each iteration $k$.
+    pub running_peaks: Vec<RunningPeaks>,
 }
 impl<'a, C, D>
     DefaultGivenOnlineProblem<
@@ -33,6 +35,7 @@ where
     ) -> Self {
         Self {
             prev_x: vec![Config::repeat(0., o.p.d); o.w as usize + 1],
+            running_peaks: vec![Default::default(); o.w as usize + 1],
         }
     }
 }
@@ -51,11 +54,62 @@ where
 {
     let mut x = Config::repeat(0., o.p.d);
     let mut prev_x = vec![];
+    let mut running_peaks = vec![];
     for k in 1..=o.w + 1 {
-        let (new_prev_x, new_x) =
-            next(k, o.clone(), t, memory.prev_x[k as usize - 1].clone());
+        let (new_prev_x, new_x, peaks) = next(
+            k,
+            o.clone(),
+            t,
+            memory.prev_x[k as usize - 1].clone(),
+            &memory.running_peaks[k as usize - 1].peaks,
+        );
         prev_x.push(new_prev_x);
+        running_peaks.push(RunningPeaks { peaks });
         x = x + new_x;
     }
-    Ok(Step(x / (o.w + 1) as f64, Some(Memory { prev_x })))
+    Ok(Step(
+        x / (o.w + 1) as f64,
+        Some(Memory {
+            prev_x,
+            running_peaks,
+        }),
+    ))
+}
+
+/// Stochastic Averaging Fixed Horizon Control
+///
+/// Solves two-stage stochastic programs over the horizons where future time slots are decided separately for each scenario of the predicted hitting costs.
+pub fn stochastic_afhc<C, D>(
+    o: Online<FractionalSimplifiedSmoothedConvexOptimization<C, D>>,
+    t: i32,
+    _: &FractionalSchedule,
+    memory: Memory,
+    _: (),
+) -> Result<FractionalStep<Memory>>
+where
+    C: ModelOutputSuccess,
+    D: ModelOutputFailure,
+{
+    let mut x = Config::repeat(0., o.p.d);
+    let mut prev_x = vec![];
+    let mut running_peaks = vec![];
+    for k in 1..=o.w + 1 {
+        let (new_prev_x, new_x, peaks) = next_stochastic(
+            k,
+            o.clone(),
+            t,
+            memory.prev_x[k as usize - 1].clone(),
+            &memory.running_peaks[k as usize - 1].peaks,
+        )?;
+        prev_x.push(new_prev_x);
+        running_peaks.push(RunningPeaks { peaks });
+        x = x + new_x;
+    }
+    Ok(Step(
+        x / (o.w + 1) as f64,
+        Some(Memory {
+            prev_x,
+            running_peaks,
+        }),
+    ))
 }
@@ -1,11 +1,15 @@
-use crate::config::{Config, FractionalConfig};
+use crate::algorithms::stochastic::{two_stage, ScenarioSchedules};
+use crate::config::FractionalConfig;
 use crate::convert::Resettable;
 use crate::model::{ModelOutputFailure, ModelOutputSuccess};
 use crate::numerics::convex_optimization::{find_minimizer, WrappedObjective};
 use crate::problem::{
     FractionalSimplifiedSmoothedConvexOptimization, Online, Problem,
 };
+use crate::result::Result;
 use crate::schedule::Schedule;
+use pyo3::prelude::*;
+use serde_derive::{Deserialize, Serialize};
 
 pub mod averaging_fixed_horizon_control;
 pub mod receding_horizon_control;
@@ -15,23 +19,35 @@ struct ObjectiveData<'a, C, D> {
     t_start: i32,
     o: Online<FractionalSimplifiedSmoothedConvexOptimization<'a, C, D>>,
     prev_x: FractionalConfig,
+    running_peaks: Vec<f64>,
 }
 
-/// Returns new initial config $prev_x$ and config for time slot $t$.
+/// Running peaks of the peak charges (see `PeakCharge`) during the billing windows of the most recently decided time slot.
+#[pyclass]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct RunningPeaks {
+    /// Running peak of each peak charge. Missing running peaks are $0$.
+    pub peaks: Vec<f64>,
+}
+
+/// Returns new initial config $prev_x$, config for time slot $t$, and the running peaks including the new initial config.
+///
+/// $running_peaks$ are the running peaks up to the time slot preceding the new initial config.
 fn next<C, D>(
     k: i32,
     o: Online<FractionalSimplifiedSmoothedConvexOptimization<C, D>>,
     t: i32,
     prev_x: FractionalConfig,
-) -> (FractionalConfig, FractionalConfig)
+    running_peaks: &[f64],
+) -> (FractionalConfig, FractionalConfig, Vec<f64>)
 where
     C: ModelOutputSuccess,
     D: ModelOutputFailure,
 {
     assert!(1 <= k && k <= o.w + 1);
     let t_start = t + k - (o.w + 1);
+    let running_peaks = carry_peaks(&o.p, t_start, running_peaks);
 
-    let d = o.p.d;
     let bounds = (0..o.w + 1)
         .into_iter()
         .flat_map(|_| {
@@ -39,10 +55,18 @@ where
         })
         .collect();
     let objective = WrappedObjective::new(
-        ObjectiveData { t_start, o, prev_x },
+        ObjectiveData {
+            t_start,
+            o: o.clone(),
+            prev_x: prev_x.clone(),
+            running_peaks: running_peaks.clone(),
+        },
         |raw_xs, data| {
-            let xs = Schedule::from_raw(data.o.p.d, data.o.w + 1, raw_xs);
-            let p = data.o.p.reset(data.t_start - 1);
+            let mut p = data.o.p.reset(data.t_start - 1);
+            p.t_end = data.o.w + 1;
+            p.hitting_cost =
+                p.hitting_cost.with_running_peaks(&data.running_peaks);
+            let xs = project(&p, raw_xs, &data.prev_x);
             p.objective_function_with_default(&xs, &data.prev_x)
                 .unwrap()
                 .cost
@@ -50,11 +74,112 @@ where
     );
 
     let (raw_xs, _) = find_minimizer(objective, bounds);
-    let offset = d * (t - t_start);
+    let mut p = o.p.reset(t_start - 1);
+    p.t_end = o.w + 1;
+    let xs = project(&p, &raw_xs, &prev_x);
+    let running_peaks = update_peaks(&o.p, t_start, &xs[0], running_peaks);
     (
-        Config::new(raw_xs[0..d as usize].to_vec()),
-        Config::new(
-            raw_xs[offset as usize..offset as usize + d as usize].to_vec(),
-        ),
+        xs[0].clone(),
+        xs[(t - t_start) as usize].clone(),
+        running_peaks,
     )
 }
+
+/// Running peaks before time slot $t$ given the running peaks up to time slot $t - 1$. Running peaks are reset when a new billing window begins.
+fn carry_peaks<C, D>(
+    p: &FractionalSimplifiedSmoothedConvexOptimization<C, D>,
+    t: i32,
+    running_peaks: &[f64],
+) -> Vec<f64>
+where
+    C: ModelOutputSuccess,
+    D: ModelOutputFailure,
+{
+    p.peak_charges()
+        .iter()
+        .enumerate()
+        .map(|(c, peak_charge)| {
+            if t > 1
+                && peak_charge.billing_window(t)
+                    == peak_charge.billing_window(t - 1)
+            {
+                running_peaks.get(c).copied().unwrap_or(0.)
+            } else {
+                0.
+            }
+        })
+        .collect()
+}
+
+/// Running peaks up to time slot $t$ given the running peaks before time slot $t$ and configuration $x$ of time slot $t$.
+fn update_peaks<C, D>(
+    p: &FractionalSimplifiedSmoothedConvexOptimization<C, D>,
+    t: i32,
+    x: &FractionalConfig,
+    running_peaks: Vec<f64>,
+) -> Vec<f64>
+where
+    C: ModelOutputSuccess,
+    D: ModelOutputFailure,
+{
+    if t < 1 || running_peaks.is_empty() {
+        return running_peaks;
+    }
+    let output = p.hit_cost(t, x.clone()).output;
+    p.peak_charges()
+        .iter()
+        .zip(running_peaks)
+        .map(|(peak_charge, running_peak)| {
+            running_peak.max(output.peak(&peak_charge.key))
+        })
+        .collect()
+}
+
+/// Builds the schedule of the time window encoded by `raw_xs` and projects it onto the configurations which may follow their predecessors (starting from $prev_x$).
+fn project<C, D>(
+    p: &FractionalSimplifiedSmoothedConvexOptimization<C, D>,
+    raw_xs: &[f64],
+    prev_x: &FractionalConfig,
+) -> Schedule<f64>
+where
+    C: ModelOutputSuccess,
+    D: ModelOutputFailure,
+{
+    let xs = Schedule::from_raw(p.d, p.t_end, raw_xs);
+    if p.hitting_cost.has_projection() {
+        p.project_schedule(&xs, prev_x)
+    } else {
+        xs
+    }
+}
+
+/// Returns new initial config $prev_x$, config for time slot $t$, and the running peaks including the new initial config.
+///
+/// All time slots up to $t$ are decided once, whereas all future time slots are decided separately for each scenario of the predicted hitting costs.
+fn next_stochastic<C, D>(
+    k: i32,
+    o: Online<FractionalSimplifiedSmoothedConvexOptimization<C, D>>,
+    t: i32,
+    prev_x: FractionalConfig,
+    running_peaks: &[f64],
+) -> Result<(FractionalConfig, FractionalConfig, Vec<f64>)>
+where
+    C: ModelOutputSuccess,
+    D: ModelOutputFailure,
+{
+    assert!(1 <= k && k <= o.w + 1);
+    let t_start = t + k - (o.w + 1);
+    let running_peaks = carry_peaks(&o.p, t_start, running_peaks);
+
+    let mut p = o.p.reset(t_start - 1);
+    p.t_end = o.w + 1;
+    p.hitting_cost = p.hitting_cost.with_running_peaks(&running_peaks);
+    let ScenarioSchedules { xs, .. } =
+        two_stage(&p, &prev_x, t - t_start + 1, 1.)?;
+    let running_peaks = update_peaks(&o.p, t_start, &xs[0], running_peaks);
+    Ok((
+        xs[0].clone(),
+        xs[(t - t_start) as usize].clone(),
+        running_peaks,
+    ))
+}
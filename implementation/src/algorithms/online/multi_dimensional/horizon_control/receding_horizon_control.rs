@@ -1,4 +1,4 @@
-use super::next;
+use super::{next, next_stochastic, RunningPeaks};
 use crate::algorithms::online::{FractionalStep, Step};
 use crate::config::Config;
 use crate::model::{ModelOutputFailure, ModelOutputSuccess};
@@ -7,18 +7,40 @@ use crate::result::Result;
 use crate::schedule::FractionalSchedule;
 
 /// Receding Horizon Control
+///
+/// The running peaks of peak charges are kept in memory such that only exceeding them is charged.
 pub fn rhc<C, D>(
     o: Online<FractionalSimplifiedSmoothedConvexOptimization<C, D>>,
     t: i32,
     xs: &FractionalSchedule,
+    memory: RunningPeaks,
     _: (),
+) -> Result<FractionalStep<RunningPeaks>>
+where
+    C: ModelOutputSuccess,
+    D: ModelOutputFailure,
+{
+    let prev_x = xs.now_with_default(Config::repeat(0., o.p.d));
+    let (_, x, peaks) = next(o.w + 1, o, t, prev_x, &memory.peaks);
+    Ok(Step(x, Some(RunningPeaks { peaks })))
+}
+
+/// Stochastic Receding Horizon Control
+///
+/// Solves a two-stage stochastic program over the prediction window where future time slots are decided separately for each scenario of the predicted hitting costs.
+/// The running peaks of peak charges are kept in memory such that only exceeding them is charged.
+pub fn stochastic_rhc<C, D>(
+    o: Online<FractionalSimplifiedSmoothedConvexOptimization<C, D>>,
+    t: i32,
+    xs: &FractionalSchedule,
+    memory: RunningPeaks,
     _: (),
-) -> Result<FractionalStep<()>>
+) -> Result<FractionalStep<RunningPeaks>>
 where
     C: ModelOutputSuccess,
     D: ModelOutputFailure,
 {
     let prev_x = xs.now_with_default(Config::repeat(0., o.p.d));
-    let (_, x) = next(o.w + 1, o, t, prev_x);
-    Ok(Step(x, None))
+    let (_, x, peaks) = next_stochastic(o.w + 1, o, t, prev_x, &memory.peaks)?;
+    Ok(Step(x, Some(RunningPeaks { peaks })))
 }
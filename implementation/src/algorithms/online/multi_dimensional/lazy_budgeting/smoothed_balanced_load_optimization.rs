@@ -1,7 +1,6 @@
 use crate::algorithms::offline::multi_dimensional::optimal_graph_search::{
     optimal_graph_search, Options as OptimalGraphSearchOptions,
 };
-use crate::algorithms::offline::multi_dimensional::Vertice;
 use crate::algorithms::offline::Cache;
 use crate::algorithms::offline::OfflineAlgorithm;
 use crate::algorithms::online::{IntegralStep, Step};
@@ -250,7 +249,7 @@ struct AlgBMemory {
     /// Maps dimension to the number of added instances for some sub time slot $u$.
     init_times: Vec<Vec<i32>>,
     /// Cache of offline algorithm.
-    cache: Option<Cache<Vertice>>,
+    cache: Option<Cache>,
 }
 
 fn alg_b(
@@ -333,13 +332,16 @@ fn cumulative_idle_hitting_cost(
 }
 
 fn find_optimal_config(
-    cache: Option<Cache<Vertice>>,
+    cache: Option<Cache>,
     p: IntegralSmoothedBalancedLoadOptimization,
-) -> Result<(IntegralConfig, Cache<Vertice>)> {
+) -> Result<(IntegralConfig, Cache)> {
     let ssco_p = p.into_ssco();
     let result = optimal_graph_search.solve(
         ssco_p,
-        OptimalGraphSearchOptions { cache },
+        OptimalGraphSearchOptions {
+            cache,
+            ..Default::default()
+        },
         Default::default(),
     )?;
     Ok((result.path.xs.now(), result.cache))
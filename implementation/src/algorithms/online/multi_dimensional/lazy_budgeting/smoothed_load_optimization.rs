@@ -1,7 +1,6 @@
 use crate::algorithms::offline::multi_dimensional::optimal_graph_search::{
     optimal_graph_search, Options as OptimalGraphSearchOptions,
 };
-use crate::algorithms::offline::multi_dimensional::Vertice;
 use crate::algorithms::offline::Cache;
 use crate::algorithms::offline::OfflineAlgorithm;
 use crate::algorithms::online::{IntegralStep, Online, Step};
@@ -31,7 +30,7 @@ pub struct Memory {
     /// Factor for calculating next time horizons when using the randomized variant of the algorithm.
     pub gamma: f64,
     /// Cache of offline algorithm.
-    cache: Option<Cache<Vertice>>,
+    cache: Option<Cache>,
 }
 impl
     DefaultGivenOnlineProblem<
@@ -204,16 +203,19 @@ fn active_lanes(x: &IntegralConfig, from: i32, to: i32) -> i32 {
 }
 
 fn find_optimal_lanes(
-    cache: Option<Cache<Vertice>>,
+    cache: Option<Cache>,
     p: IntegralSmoothedLoadOptimization,
     bound: i32,
-) -> Result<(Lanes, Cache<Vertice>)> {
+) -> Result<(Lanes, Cache)> {
     let d = p.d;
     let sblo_p = p.into_sblo();
     let ssco_p = sblo_p.into_ssco();
     let result = optimal_graph_search.solve(
         ssco_p,
-        OptimalGraphSearchOptions { cache },
+        OptimalGraphSearchOptions {
+            cache,
+            ..Default::default()
+        },
         Default::default(),
     )?;
     debug!(
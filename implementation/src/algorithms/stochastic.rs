@@ -0,0 +1,204 @@
+use crate::config::{Config, FractionalConfig};
+use crate::model::{ModelOutputFailure, ModelOutputSuccess};
+use crate::numerics::convex_optimization::{find_minimizer, WrappedObjective};
+use crate::problem::{FractionalSimplifiedSmoothedConvexOptimization, Problem};
+use crate::result::{Failure, Result};
+use crate::schedule::{FractionalSchedule, Schedule};
+use crate::utils::{assert, mean};
+use noisy_float::prelude::*;
+
+/// Maximal number of scenarios of a two-stage stochastic program.
+/// As the recourse decisions of all scenarios are optimized jointly, the dimension of the program grows linearly with the number of scenarios.
+pub static MAX_SCENARIOS: usize = 32;
+
+/// Solution of a scenario-based two-stage stochastic program.
+#[derive(Clone, Debug)]
+pub struct ScenarioSchedules {
+    /// Here-and-now decisions which are shared by all scenarios.
+    pub xs: FractionalSchedule,
+    /// Recourse decisions for the remaining time slots in each scenario.
+    pub recourse: Vec<FractionalSchedule>,
+    /// Expected cost.
+    pub cost: f64,
+}
+impl ScenarioSchedules {
+    /// Here-and-now decisions followed by the recourse decisions averaged over all scenarios.
+    pub fn expected_schedule(&self) -> FractionalSchedule {
+        let mut xs = self.xs.clone();
+        let t_end = self.recourse.first().map_or(0, |ys| ys.t_end());
+        for t in 1..=t_end {
+            let d = self.recourse[0][t as usize - 1].d();
+            let x =
+                self.recourse.iter().fold(Config::repeat(0., d), |x, ys| {
+                    x + ys[t as usize - 1].clone()
+                });
+            xs.push(x / self.recourse.len() as f64);
+        }
+        xs
+    }
+}
+
+#[derive(Clone)]
+struct ObjectiveData<'a, C, D> {
+    p: FractionalSimplifiedSmoothedConvexOptimization<'a, C, D>,
+    default: FractionalConfig,
+    here_and_now: i32,
+    scenarios: usize,
+    alpha: f64,
+}
+
+/// Solves the two-stage stochastic program over time slots $1, \dots, t_end$ starting from `default`.
+///
+/// The first `here_and_now` time slots are decided once for all scenarios (paying the mean hitting cost).
+/// All remaining time slots are decided separately for each scenario, where the $s$-th scenario pays the hitting cost of the $s$-th sample.
+/// The number of scenarios is the largest number of samples returned for any of the remaining time slots.
+/// Certain time slots (with a single sample) are shared by all scenarios, all other time slots must return a sample for each scenario.
+/// Peak charges (see `PeakCharge`) are only paid for the peaks of the here-and-now time slots.
+/// Fails with `Failure::TooManyScenarios` if there are more than `MAX_SCENARIOS` scenarios, and with `Failure::InconsistentSamples` if some time slot returns neither one nor a sample for each scenario.
+pub fn two_stage<C, D>(
+    p: &FractionalSimplifiedSmoothedConvexOptimization<'_, C, D>,
+    default: &FractionalConfig,
+    here_and_now: i32,
+    alpha: f64,
+) -> Result<ScenarioSchedules>
+where
+    C: ModelOutputSuccess,
+    D: ModelOutputFailure,
+{
+    assert!(0 <= here_and_now && here_and_now <= p.t_end);
+
+    let d = p.d;
+    let samples: Vec<_> = (here_and_now + 1..=p.t_end)
+        .map(|t| (t, p.samples(t, default.clone())))
+        .collect();
+    let scenarios = samples
+        .iter()
+        .map(|&(_, samples)| samples)
+        .max()
+        .unwrap_or(1);
+    assert(
+        scenarios <= MAX_SCENARIOS,
+        Failure::TooManyScenarios {
+            scenarios,
+            max: MAX_SCENARIOS,
+        },
+    )?;
+    for (t, samples) in samples {
+        assert(
+            samples == 1 || samples == scenarios,
+            Failure::InconsistentSamples {
+                t,
+                samples,
+                scenarios,
+            },
+        )?;
+    }
+    let remaining = p.t_end - here_and_now;
+
+    let slots = here_and_now as usize + scenarios * remaining as usize;
+    let bounds = (0..slots)
+        .flat_map(|_| (0..d as usize).map(|k| (0., p.bounds[k])))
+        .collect();
+    let objective = WrappedObjective::new(
+        ObjectiveData {
+            p: p.clone(),
+            default: default.clone(),
+            here_and_now,
+            scenarios,
+            alpha,
+        },
+        |raw_xs, data| {
+            let (xs, recourse) = split(
+                data.p.d,
+                data.here_and_now,
+                data.p.t_end - data.here_and_now,
+                data.scenarios,
+                raw_xs,
+            );
+            expected_cost(data, &xs, &recourse)
+        },
+    );
+
+    let (raw_xs, cost) = find_minimizer(objective, bounds);
+    let (xs, recourse) = split(d, here_and_now, remaining, scenarios, &raw_xs);
+    Ok(ScenarioSchedules {
+        xs,
+        recourse,
+        cost: cost.raw(),
+    })
+}
+
+/// Splits the flattened decisions into here-and-now decisions and the recourse decisions of each scenario.
+fn split(
+    d: i32,
+    here_and_now: i32,
+    remaining: i32,
+    scenarios: usize,
+    raw_xs: &[f64],
+) -> (FractionalSchedule, Vec<FractionalSchedule>) {
+    let offset = (d * here_and_now) as usize;
+    let length = (d * remaining) as usize;
+    let xs = Schedule::from_raw(d, here_and_now, &raw_xs[..offset]);
+    let recourse = (0..scenarios)
+        .map(|s| {
+            let start = offset + s * length;
+            Schedule::from_raw(d, remaining, &raw_xs[start..start + length])
+        })
+        .collect();
+    (xs, recourse)
+}
+
+fn expected_cost<C, D>(
+    data: &ObjectiveData<'_, C, D>,
+    xs: &FractionalSchedule,
+    recourse: &[FractionalSchedule],
+) -> N64
+where
+    C: ModelOutputSuccess,
+    D: ModelOutputFailure,
+{
+    let ObjectiveData {
+        p,
+        default,
+        here_and_now,
+        alpha,
+        ..
+    } = data;
+
+    let shared_cost = p
+        ._objective_function_with_default(
+            xs,
+            default,
+            *alpha,
+            false,
+            *here_and_now,
+        )
+        .unwrap()
+        .cost;
+    let last_x = xs.now_with_default(default.clone());
+    let recourse_costs = recourse
+        .iter()
+        .enumerate()
+        .map(|(s, ys)| {
+            (1..=ys.t_end())
+                .map(|t| {
+                    let prev_y = if t == 1 {
+                        last_x.clone()
+                    } else {
+                        ys[t as usize - 2].clone()
+                    };
+                    let y = ys[t as usize - 1].clone();
+                    p.sample_hit_cost(here_and_now + t, y.clone(), s).cost
+                        + p.transition_cost(
+                            here_and_now + t,
+                            prev_y.clone(),
+                            y.clone(),
+                        )
+                        .cost
+                        + n64(*alpha) * p.movement(prev_y, y, false)
+                })
+                .sum::<N64>()
+        })
+        .collect();
+    shared_cost + mean(recourse_costs)
+}
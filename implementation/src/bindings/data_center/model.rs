@@ -1,18 +1,137 @@
+use crate::config::Config;
 use crate::model::data_center::{
-    model::{DataCenterModel, JobType, Location, ServerType, Source},
+    calibration::{
+        calibrate_linear_energy_consumption_model,
+        calibrate_non_linear_energy_consumption_model,
+        calibrate_processing_times,
+        calibrate_simplified_linear_energy_consumption_model, Calibration,
+        GoodnessOfFit,
+    },
+    model::{
+        Affinity, DataCenterModel, DataCenterOfflineInput, JobType, Location,
+        ServerType, Source,
+    },
     models::{
+        cooling::TemperatureDependentCoolingModel,
+        delay::{ErlangCDelayModel, FirstComeFirstServedDelayModel},
         energy_consumption::{
             LinearEnergyConsumptionModel, NonLinearEnergyConsumptionModel,
             SimplifiedLinearEnergyConsumptionModel,
         },
-        energy_cost::{LinearEnergyCostModel, QuotasEnergyCostModel},
-        revenue_loss::MinimalDetectableDelayRevenueLossModel,
+        energy_cost::{
+            CarbonAwareEnergyCostModel, DemandCharge, LinearEnergyCostModel,
+            QuotasEnergyCostModel,
+        },
+        energy_storage::Battery,
+        rental_cost::RentalCost,
+        revenue_loss::{
+            MinimalDetectableDelayRevenueLossModel, TailLatencyRevenueLossModel,
+        },
         switching_cost::SwitchingCost,
     },
-    DataCenterModelOutputSuccess,
+    simulation::{simulate, Job},
+    CostBreakdown, DataCenterModelOutputSuccess,
 };
+use crate::model::Model;
+use crate::problem::{FractionalSimplifiedSmoothedConvexOptimization, Problem};
+use crate::schedule::Schedule;
+use pyo3::exceptions::PyAssertionError;
 use pyo3::prelude::*;
 
+/// Reports the constituents of the cost of a schedule for each time slot as NumPy arrays.
+#[pyfunction]
+#[pyo3(name = "cost_report")]
+fn cost_report_py(
+    py: Python,
+    model: DataCenterModel,
+    input: DataCenterOfflineInput,
+    xs: Vec<Vec<f64>>,
+) -> PyResult<PyObject> {
+    let report = py
+        .allow_threads(|| {
+            let p: FractionalSimplifiedSmoothedConvexOptimization<_, _> =
+                model.to(input);
+            p.cost_report(&Schedule::new(
+                xs.into_iter().map(Config::new).collect(),
+            ))
+        })
+        .map_err(|failure| PyAssertionError::new_err(failure.to_string()))?;
+    report.to_numpy(py)
+}
+
+/// Replays a trace of jobs against a schedule and reports the realized and predicted behavior for each time slot as NumPy arrays.
+#[pyfunction]
+#[pyo3(name = "simulate")]
+fn simulate_py(
+    py: Python,
+    model: DataCenterModel,
+    xs: Vec<Vec<f64>>,
+    trace: Vec<Job>,
+) -> PyResult<PyObject> {
+    let report = py.allow_threads(|| {
+        simulate(
+            &model,
+            &Schedule::new(xs.into_iter().map(Config::new).collect()),
+            &trace,
+        )
+        .unwrap()
+    });
+    report.to_numpy(py)
+}
+
+/// Converts a calibration to a tuple of the calibrated model and its goodness of fit.
+fn calibration_to_py<M>(
+    calibration: crate::result::Result<Calibration<M>>,
+) -> PyResult<(M, GoodnessOfFit)> {
+    calibration
+        .map(|Calibration { model, fit }| (model, fit))
+        .map_err(|failure| PyAssertionError::new_err(failure.to_string()))
+}
+
+/// Fits the linear energy consumption model to samples of utilization and power.
+#[pyfunction]
+#[pyo3(name = "calibrate_linear_energy_consumption_model")]
+fn calibrate_linear_energy_consumption_model_py(
+    samples: Vec<(f64, f64)>,
+) -> PyResult<(LinearEnergyConsumptionModel, GoodnessOfFit)> {
+    calibration_to_py(calibrate_linear_energy_consumption_model(&samples))
+}
+
+/// Fits the simplified linear energy consumption model to samples of utilization and power.
+#[pyfunction]
+#[pyo3(name = "calibrate_simplified_linear_energy_consumption_model")]
+fn calibrate_simplified_linear_energy_consumption_model_py(
+    samples: Vec<(f64, f64)>,
+) -> PyResult<(SimplifiedLinearEnergyConsumptionModel, GoodnessOfFit)> {
+    calibration_to_py(calibrate_simplified_linear_energy_consumption_model(
+        &samples,
+    ))
+}
+
+/// Fits the non-linear energy consumption model to samples of utilization and power.
+#[pyfunction]
+#[pyo3(name = "calibrate_non_linear_energy_consumption_model")]
+fn calibrate_non_linear_energy_consumption_model_py(
+    py: Python,
+    samples: Vec<(f64, f64)>,
+) -> PyResult<(NonLinearEnergyConsumptionModel, GoodnessOfFit)> {
+    py.allow_threads(|| {
+        calibration_to_py(calibrate_non_linear_energy_consumption_model(
+            &samples,
+        ))
+    })
+}
+
+/// Fits the processing times of a job type to observed durations on server types.
+#[pyfunction]
+#[pyo3(name = "calibrate_processing_times")]
+fn calibrate_processing_times_py(
+    key: String,
+    durations: Vec<(String, f64)>,
+) -> PyResult<(JobType, GoodnessOfFit)> {
+    calibration_to_py(calibrate_processing_times(key, &durations))
+}
+
 pub fn submodule(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<DataCenterModel>()?;
 
@@ -20,6 +139,8 @@ pub fn submodule(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<ServerType>()?;
     m.add_class::<Source>()?;
     m.add_class::<JobType>()?;
+    m.add_class::<Affinity>()?;
+    m.add_class::<Job>()?;
 
     m.add_class::<LinearEnergyConsumptionModel>()?;
     m.add_class::<SimplifiedLinearEnergyConsumptionModel>()?;
@@ -27,12 +148,41 @@ pub fn submodule(_py: Python, m: &PyModule) -> PyResult<()> {
 
     m.add_class::<LinearEnergyCostModel>()?;
     m.add_class::<QuotasEnergyCostModel>()?;
+    m.add_class::<CarbonAwareEnergyCostModel>()?;
+    m.add_class::<DemandCharge>()?;
+
+    m.add_class::<Battery>()?;
+
+    m.add_class::<TemperatureDependentCoolingModel>()?;
+
+    m.add_class::<ErlangCDelayModel>()?;
+    m.add_class::<FirstComeFirstServedDelayModel>()?;
 
     m.add_class::<MinimalDetectableDelayRevenueLossModel>()?;
+    m.add_class::<TailLatencyRevenueLossModel>()?;
 
     m.add_class::<SwitchingCost>()?;
+    m.add_class::<RentalCost>()?;
 
     m.add_class::<DataCenterModelOutputSuccess>()?;
+    m.add_class::<CostBreakdown>()?;
+    m.add_class::<GoodnessOfFit>()?;
+
+    m.add_function(wrap_pyfunction!(cost_report_py, m)?)?;
+    m.add_function(wrap_pyfunction!(simulate_py, m)?)?;
+    m.add_function(wrap_pyfunction!(
+        calibrate_linear_energy_consumption_model_py,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(
+        calibrate_simplified_linear_energy_consumption_model_py,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(
+        calibrate_non_linear_energy_consumption_model_py,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(calibrate_processing_times_py, m)?)?;
 
     Ok(())
 }
@@ -4,13 +4,19 @@ use crate::{
             approx_graph_search::{
                 approx_graph_search, Options as ApproxGraphSearchOptions,
             },
+            branch_and_bound_graph_search::{
+                branch_and_bound_graph_search,
+                Options as BranchAndBoundGraphSearchOptions,
+            },
             convex_optimization::co,
             optimal_graph_search::{
                 optimal_graph_search, Options as OptimalGraphSearchOptions,
             },
             static_fractional::static_fractional,
             static_integral::static_integral,
-            Vertice,
+            stochastic_convex_optimization::{
+                stochastic_co, Options as StochasticConvexOptimizationOptions,
+            },
         },
         uni_dimensional::{
             capacity_provisioning::brcp,
@@ -19,7 +25,8 @@ use crate::{
                 Options as OptimalGraphSearch1dOptions,
             },
         },
-        Cache, CachedPath, OfflineOptions, PureOfflineResult,
+        Cache, CachedPath, CancellationToken, Certificate, OfflineOptions,
+        PureOfflineResult,
     },
     bindings::DataCenterCost,
     cost::Cost,
@@ -37,9 +44,13 @@ use crate::{
 use log::info;
 use pyo3::prelude::*;
 
-type Response<T> = (Vec<Vec<T>>, DataCenterCost, u128);
-type SLOResponse<T> =
-    (Vec<Vec<T>>, Cost<(), DataCenterModelOutputFailure>, u128);
+type Response<T> = (Vec<Vec<T>>, DataCenterCost, Option<Certificate>, u128);
+type SLOResponse<T> = (
+    Vec<Vec<T>>,
+    Cost<(), DataCenterModelOutputFailure>,
+    Option<Certificate>,
+    u128,
+);
 
 /// Backward-Recurrent Capacity Provisioning
 #[pyfunction]
@@ -52,9 +63,9 @@ fn brcp_py(
 ) -> PyResult<Response<f64>> {
     py.allow_threads(|| {
         info!("BRCP");
-        let (xs, cost, runtime) =
+        let (xs, cost, certificate, runtime) =
             offline::solve(&model, &brcp, (), offline_options, input).unwrap();
-        Ok((xs.to_vec(), cost, runtime))
+        Ok((xs.to_vec(), cost, certificate, runtime))
     })
 }
 
@@ -70,7 +81,7 @@ fn optimal_graph_search_1d_py(
 ) -> PyResult<Response<i32>> {
     py.allow_threads(|| {
         info!("1d Graph Search");
-        let (xs, cost, runtime) = offline::solve(
+        let (xs, cost, certificate, runtime) = offline::solve(
             &model,
             &optimal_graph_search_1d,
             options,
@@ -78,7 +89,7 @@ fn optimal_graph_search_1d_py(
             input,
         )
         .unwrap();
-        Ok((xs.to_vec(), cost, runtime))
+        Ok((xs.to_vec(), cost, certificate, runtime))
     })
 }
 
@@ -94,7 +105,7 @@ fn optimal_graph_search_py(
 ) -> PyResult<Response<i32>> {
     py.allow_threads(|| {
         info!("Graph Search");
-        let (xs, cost, runtime) = offline::solve(
+        let (xs, cost, certificate, runtime) = offline::solve(
             &model,
             &optimal_graph_search,
             options,
@@ -102,7 +113,7 @@ fn optimal_graph_search_py(
             input,
         )
         .unwrap();
-        Ok((xs.to_vec(), cost, runtime))
+        Ok((xs.to_vec(), cost, certificate, runtime))
     })
 }
 
@@ -118,7 +129,7 @@ fn optimal_graph_search_slo_py(
 ) -> PyResult<SLOResponse<i32>> {
     py.allow_threads(|| {
         info!("Graph Search for SLO");
-        let (xs, cost, runtime) = offline::solve(
+        let (xs, cost, certificate, runtime) = offline::solve(
             &model,
             &optimal_graph_search_slo,
             options,
@@ -126,7 +137,7 @@ fn optimal_graph_search_slo_py(
             input,
         )
         .unwrap();
-        Ok((xs.to_vec(), cost, runtime))
+        Ok((xs.to_vec(), cost, certificate, runtime))
     })
 }
 
@@ -142,7 +153,7 @@ fn optimal_graph_search_sblo_py(
 ) -> PyResult<Response<i32>> {
     py.allow_threads(|| {
         info!("Graph Search for SBLO");
-        let (xs, cost, runtime) = offline::solve(
+        let (xs, cost, certificate, runtime) = offline::solve(
             &model,
             &optimal_graph_search_sblo,
             options,
@@ -150,7 +161,7 @@ fn optimal_graph_search_sblo_py(
             input,
         )
         .unwrap();
-        Ok((xs.to_vec(), cost, runtime))
+        Ok((xs.to_vec(), cost, certificate, runtime))
     })
 }
 
@@ -166,7 +177,7 @@ fn approx_graph_search_py(
 ) -> PyResult<Response<i32>> {
     py.allow_threads(|| {
         info!("Approximate Graph Search");
-        let (xs, cost, runtime) = offline::solve(
+        let (xs, cost, certificate, runtime) = offline::solve(
             &model,
             &approx_graph_search,
             options,
@@ -174,7 +185,7 @@ fn approx_graph_search_py(
             input,
         )
         .unwrap();
-        Ok((xs.to_vec(), cost, runtime))
+        Ok((xs.to_vec(), cost, certificate, runtime))
     })
 }
 
@@ -190,7 +201,7 @@ fn approx_graph_search_slo_py(
 ) -> PyResult<SLOResponse<i32>> {
     py.allow_threads(|| {
         info!("Approximate Graph Search for SLO");
-        let (xs, cost, runtime) = offline::solve(
+        let (xs, cost, certificate, runtime) = offline::solve(
             &model,
             &approx_graph_search_slo,
             options,
@@ -198,7 +209,7 @@ fn approx_graph_search_slo_py(
             input,
         )
         .unwrap();
-        Ok((xs.to_vec(), cost, runtime))
+        Ok((xs.to_vec(), cost, certificate, runtime))
     })
 }
 
@@ -214,7 +225,7 @@ fn approx_graph_search_sblo_py(
 ) -> PyResult<Response<i32>> {
     py.allow_threads(|| {
         info!("Approximate Graph Search for SBLO");
-        let (xs, cost, runtime) = offline::solve(
+        let (xs, cost, certificate, runtime) = offline::solve(
             &model,
             &approx_graph_search_sblo,
             options,
@@ -222,7 +233,31 @@ fn approx_graph_search_sblo_py(
             input,
         )
         .unwrap();
-        Ok((xs.to_vec(), cost, runtime))
+        Ok((xs.to_vec(), cost, certificate, runtime))
+    })
+}
+
+/// Graph-Based Optimal Algorithm using Branch-and-Bound
+#[pyfunction]
+#[pyo3(name = "branch_and_bound_graph_search")]
+fn branch_and_bound_graph_search_py(
+    py: Python,
+    model: DataCenterModel,
+    input: DataCenterOfflineInput,
+    options: BranchAndBoundGraphSearchOptions,
+    offline_options: OfflineOptions,
+) -> PyResult<Response<i32>> {
+    py.allow_threads(|| {
+        info!("Branch-and-Bound Graph Search");
+        let (xs, cost, certificate, runtime) = offline::solve(
+            &model,
+            &branch_and_bound_graph_search,
+            options,
+            offline_options,
+            input,
+        )
+        .unwrap();
+        Ok((xs.to_vec(), cost, certificate, runtime))
     })
 }
 
@@ -237,9 +272,33 @@ fn convex_optimization_py(
 ) -> PyResult<Response<f64>> {
     py.allow_threads(|| {
         info!("Convex Program");
-        let (xs, cost, runtime) =
+        let (xs, cost, certificate, runtime) =
             offline::solve(&model, &co, (), offline_options, input).unwrap();
-        Ok((xs.to_vec(), cost, runtime))
+        Ok((xs.to_vec(), cost, certificate, runtime))
+    })
+}
+
+/// Scenario-Based Stochastic Convex Optimization
+#[pyfunction]
+#[pyo3(name = "stochastic_convex_optimization")]
+fn stochastic_convex_optimization_py(
+    py: Python,
+    model: DataCenterModel,
+    input: DataCenterOfflineInput,
+    options: StochasticConvexOptimizationOptions,
+    offline_options: OfflineOptions,
+) -> PyResult<Response<f64>> {
+    py.allow_threads(|| {
+        info!("Stochastic Convex Program");
+        let (xs, cost, certificate, runtime) = offline::solve(
+            &model,
+            &stochastic_co,
+            options,
+            offline_options,
+            input,
+        )
+        .unwrap();
+        Ok((xs.to_vec(), cost, certificate, runtime))
     })
 }
 
@@ -254,7 +313,7 @@ fn static_fractional_py(
 ) -> PyResult<Response<f64>> {
     py.allow_threads(|| {
         info!("Static Integral");
-        let (xs, cost, runtime) = offline::solve(
+        let (xs, cost, certificate, runtime) = offline::solve(
             &model,
             &static_fractional,
             (),
@@ -262,7 +321,7 @@ fn static_fractional_py(
             input,
         )
         .unwrap();
-        Ok((xs.to_vec(), cost, runtime))
+        Ok((xs.to_vec(), cost, certificate, runtime))
     })
 }
 
@@ -277,7 +336,7 @@ fn static_integral_py(
 ) -> PyResult<Response<i32>> {
     py.allow_threads(|| {
         info!("Static Integral");
-        let (xs, cost, runtime) = offline::solve(
+        let (xs, cost, certificate, runtime) = offline::solve(
             &model,
             &static_integral,
             (),
@@ -285,7 +344,7 @@ fn static_integral_py(
             input,
         )
         .unwrap();
-        Ok((xs.to_vec(), cost, runtime))
+        Ok((xs.to_vec(), cost, certificate, runtime))
     })
 }
 
@@ -300,7 +359,7 @@ fn static_integral_slo_py(
 ) -> PyResult<SLOResponse<i32>> {
     py.allow_threads(|| {
         info!("Static Integral for SLO");
-        let (xs, cost, runtime) = offline::solve(
+        let (xs, cost, certificate, runtime) = offline::solve(
             &model,
             &static_integral_slo,
             (),
@@ -308,7 +367,7 @@ fn static_integral_slo_py(
             input,
         )
         .unwrap();
-        Ok((xs.to_vec(), cost, runtime))
+        Ok((xs.to_vec(), cost, certificate, runtime))
     })
 }
 
@@ -323,7 +382,7 @@ fn static_integral_sblo_py(
 ) -> PyResult<Response<i32>> {
     py.allow_threads(|| {
         info!("Static Integral for SBLO");
-        let (xs, cost, runtime) = offline::solve(
+        let (xs, cost, certificate, runtime) = offline::solve(
             &model,
             &static_integral_sblo,
             (),
@@ -331,12 +390,14 @@ fn static_integral_sblo_py(
             input,
         )
         .unwrap();
-        Ok((xs.to_vec(), cost, runtime))
+        Ok((xs.to_vec(), cost, certificate, runtime))
     })
 }
 
 pub fn submodule(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<OfflineOptions>()?;
+    m.add_class::<CancellationToken>()?;
+    m.add_class::<Certificate>()?;
 
     m.add_function(wrap_pyfunction!(brcp_py, m)?)?;
 
@@ -353,8 +414,14 @@ pub fn submodule(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(approx_graph_search_sblo_py, m)?)?;
     m.add_class::<ApproxGraphSearchOptions>()?;
 
+    m.add_function(wrap_pyfunction!(branch_and_bound_graph_search_py, m)?)?;
+    m.add_class::<BranchAndBoundGraphSearchOptions>()?;
+
     m.add_function(wrap_pyfunction!(convex_optimization_py, m)?)?;
 
+    m.add_function(wrap_pyfunction!(stochastic_convex_optimization_py, m)?)?;
+    m.add_class::<StochasticConvexOptimizationOptions>()?;
+
     m.add_function(wrap_pyfunction!(static_fractional_py, m)?)?;
 
     m.add_function(wrap_pyfunction!(static_integral_py, m)?)?;
@@ -368,7 +435,7 @@ fn optimal_graph_search_slo(
     p: IntegralSmoothedLoadOptimization,
     options: OptimalGraphSearchOptions,
     offline_options: OfflineOptions,
-) -> Result<CachedPath<Cache<Vertice>>> {
+) -> Result<CachedPath<Cache>> {
     optimal_graph_search_sblo(p.into_sblo(), options, offline_options)
 }
 
@@ -376,7 +443,7 @@ fn approx_graph_search_slo(
     p: IntegralSmoothedLoadOptimization,
     options: ApproxGraphSearchOptions,
     offline_options: OfflineOptions,
-) -> Result<CachedPath<Cache<Vertice>>> {
+) -> Result<CachedPath<Cache>> {
     approx_graph_search_sblo(p.into_sblo(), options, offline_options)
 }
 
@@ -392,7 +459,7 @@ fn optimal_graph_search_sblo(
     p: IntegralSmoothedBalancedLoadOptimization,
     options: OptimalGraphSearchOptions,
     offline_options: OfflineOptions,
-) -> Result<CachedPath<Cache<Vertice>>> {
+) -> Result<CachedPath<Cache>> {
     optimal_graph_search(p.into_ssco(), options, offline_options)
 }
 
@@ -400,7 +467,7 @@ fn approx_graph_search_sblo(
     p: IntegralSmoothedBalancedLoadOptimization,
     options: ApproxGraphSearchOptions,
     offline_options: OfflineOptions,
-) -> Result<CachedPath<Cache<Vertice>>> {
+) -> Result<CachedPath<Cache>> {
     approx_graph_search(p.into_ssco(), options, offline_options)
 }
 
@@ -1,5 +1,5 @@
 use crate::{algorithms::online::multi_dimensional::horizon_control::averaging_fixed_horizon_control::{
-        afhc, Memory,
+        afhc, stochastic_afhc, Memory,
     }, bindings::data_center::online::{DataCenterFractionalSimplifiedSmoothedConvexOptimization, Response, StepResponse}, model::data_center::{
         model::{
             DataCenterModel, DataCenterOfflineInput, DataCenterOnlineInput,
@@ -38,6 +38,36 @@ fn start(
     })
 }
 
+/// Starts backend of the stochastic variant in a new thread.
+#[pyfunction]
+#[allow(clippy::type_complexity)]
+fn start_stochastic(
+    py: Python,
+    addr: String,
+    model: DataCenterModel,
+    input: DataCenterOfflineInput,
+    w: i32,
+) -> PyResult<Response<f64, Memory>> {
+    py.allow_threads(|| {
+        let OfflineResponse {
+            xs: (xs, cost),
+            int_xs: (int_xs, int_cost),
+            m,
+            runtime,
+        } = online::start(
+            addr.parse().unwrap(),
+            model,
+            &stochastic_afhc,
+            (),
+            w,
+            input,
+            None,
+        )
+        .unwrap();
+        Ok(((xs.to_vec(), cost), (int_xs.to_vec(), int_cost), m, runtime))
+    })
+}
+
 /// Executes next iteration of the algorithm.
 #[pyfunction]
 fn next(
@@ -63,6 +93,7 @@ fn next(
 /// Lazy Capacity Provisioning
 pub fn submodule(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(start, m)?)?;
+    m.add_function(wrap_pyfunction!(start_stochastic, m)?)?;
     m.add_function(wrap_pyfunction!(next, m)?)?;
 
     Ok(())
@@ -1,11 +1,19 @@
-use crate::{algorithms::online::multi_dimensional::horizon_control::receding_horizon_control::{
-    rhc,
-}, bindings::data_center::online::{DataCenterFractionalSmoothedConvexOptimization, Response, StepResponse}, model::data_center::{
-    model::{
-        DataCenterModel, DataCenterOfflineInput, DataCenterOnlineInput,
+use crate::{
+    algorithms::online::multi_dimensional::horizon_control::{
+        receding_horizon_control::{rhc, stochastic_rhc},
+        RunningPeaks,
     },
-    DataCenterModelOutputFailure, DataCenterModelOutputSuccess,
-}, streaming::online::{self, OfflineResponse}};
+    bindings::data_center::online::{
+        DataCenterFractionalSmoothedConvexOptimization, Response, StepResponse,
+    },
+    model::data_center::{
+        model::{
+            DataCenterModel, DataCenterOfflineInput, DataCenterOnlineInput,
+        },
+        DataCenterModelOutputFailure, DataCenterModelOutputSuccess,
+    },
+    streaming::online::{self, OfflineResponse},
+};
 use pyo3::{exceptions::PyAssertionError, prelude::*};
 
 /// Starts backend in a new thread.
@@ -17,7 +25,7 @@ fn start(
     model: DataCenterModel,
     input: DataCenterOfflineInput,
     w: i32,
-) -> PyResult<Response<f64, ()>> {
+) -> PyResult<Response<f64, RunningPeaks>> {
     py.allow_threads(|| {
         let OfflineResponse {
             xs: (xs, cost),
@@ -38,19 +46,49 @@ fn start(
     })
 }
 
+/// Starts backend of the stochastic variant in a new thread.
+#[pyfunction]
+#[allow(clippy::type_complexity)]
+fn start_stochastic(
+    py: Python,
+    addr: String,
+    model: DataCenterModel,
+    input: DataCenterOfflineInput,
+    w: i32,
+) -> PyResult<Response<f64, RunningPeaks>> {
+    py.allow_threads(|| {
+        let OfflineResponse {
+            xs: (xs, cost),
+            int_xs: (int_xs, int_cost),
+            m,
+            runtime,
+        } = online::start(
+            addr.parse().unwrap(),
+            model,
+            &stochastic_rhc,
+            (),
+            w,
+            input,
+            None,
+        )
+        .unwrap();
+        Ok(((xs.to_vec(), cost), (int_xs.to_vec(), int_cost), m, runtime))
+    })
+}
+
 /// Executes next iteration of the algorithm.
 #[pyfunction]
 fn next(
     py: Python,
     addr: String,
     input: DataCenterOnlineInput,
-) -> PyResult<StepResponse<f64, ()>> {
+) -> PyResult<StepResponse<f64, RunningPeaks>> {
     py.allow_threads(|| {
         let ((x, cost), (int_x, int_cost), m, runtime) =
             online::next::<
                 f64,
                 DataCenterFractionalSmoothedConvexOptimization,
-                (),
+                RunningPeaks,
                 DataCenterOnlineInput,
                 DataCenterModelOutputSuccess,
                 DataCenterModelOutputFailure,
@@ -63,6 +101,7 @@ fn next(
 /// Lazy Capacity Provisioning
 pub fn submodule(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(start, m)?)?;
+    m.add_function(wrap_pyfunction!(start_stochastic, m)?)?;
     m.add_function(wrap_pyfunction!(next, m)?)?;
 
     Ok(())
@@ -70,6 +70,8 @@ where
     D: ModelOutputFailure + 'a,
 {
     fn into_i(self) -> CostFn<'a, IntegralConfig, C, D> {
+        let peak_charges = self.peak_charges().to_vec();
+        let continuous_dimensions = self.continuous_dimensions().to_vec();
         CostFn::new(
             1,
             SingleCostFn::certain(move |t, x: IntegralConfig| {
@@ -77,6 +79,8 @@ where
                 f.call_certain(t, x.to())
             }),
         )
+        .with_peak_charges(peak_charges)
+        .with_continuous_dimensions(continuous_dimensions)
     }
 }
 
@@ -95,6 +99,8 @@ where
     D: ModelOutputFailure + 'a,
 {
     fn into_f(self) -> CostFn<'a, FractionalConfig, C, D> {
+        let peak_charges = self.peak_charges().to_vec();
+        let continuous_dimensions = self.continuous_dimensions().to_vec();
         CostFn::new(
             1,
             SingleCostFn::certain(move |t, x: FractionalConfig| {
@@ -115,14 +121,16 @@ where
                     Cost::new(
                         (j.ceil() - j) * lower.cost
                             + (j - j.floor()) * upper.cost,
-                        ModelOutput::vertical_reduce(vec![
-                            lower.output,
-                            upper.output,
+                        ModelOutput::weighted_vertical_reduce(vec![
+                            ((j.ceil() - j).raw(), lower.output),
+                            ((j - j.floor()).raw(), upper.output),
                         ]),
                     )
                 }
             }),
         )
+        .with_peak_charges(peak_charges)
+        .with_continuous_dimensions(continuous_dimensions)
     }
 }
 
@@ -195,14 +203,19 @@ where
             .map(|&u| (NumCast::from(0).unwrap(), u))
             .collect();
         let switching_cost = manhattan_scaled(self.switching_cost.clone());
-        SmoothedConvexOptimization {
-            d: self.d,
-            t_end: self.t_end,
-            bounds,
-            switching_cost: switching_cost.clone(),
-            hitting_cost: CostFn::new(
-                1,
-                SingleCostFn::certain(move |t: i32, x: Config<T>| {
+        let d = self.d;
+        let t_end = self.t_end;
+        let transition = (self.hitting_cost.has_transition()
+            || self.hitting_cost.has_final_transition())
+        .then(|| self.clone());
+        let projection =
+            self.hitting_cost.has_projection().then(|| self.clone());
+        let peak_charges = self.hitting_cost.peak_charges().to_vec();
+        let hitting_cost = CostFn::new(
+            1,
+            SingleCostFn::certain({
+                let switching_cost = switching_cost.clone();
+                move |t: i32, x: Config<T>| {
                     if t == self.t_end {
                         let hitting_cost = self.hit_cost(t, x.clone());
                         Cost::new(
@@ -212,8 +225,35 @@ where
                     } else {
                         self.hit_cost(t, x)
                     }
-                }),
-            ),
+                }
+            }),
+        )
+        .with_peak_charges(peak_charges);
+        SmoothedConvexOptimization {
+            d,
+            t_end,
+            bounds,
+            switching_cost,
+            hitting_cost: match (transition, projection) {
+                (Some(p), Some(q)) => hitting_cost
+                    .with_transition(move |t, prev_x, x| {
+                        p.transition_cost(t, prev_x, x)
+                    })
+                    .with_projection(move |t, prev_x, x| {
+                        q.project(t, prev_x, x)
+                    }),
+                (Some(p), None) => {
+                    hitting_cost.with_transition(move |t, prev_x, x| {
+                        p.transition_cost(t, prev_x, x)
+                    })
+                }
+                (None, Some(q)) => {
+                    hitting_cost.with_projection(move |t, prev_x, x| {
+                        q.project(t, prev_x, x)
+                    })
+                }
+                (None, None) => hitting_cost,
+            },
         }
     }
 }
@@ -424,7 +464,7 @@ where
     D: ModelOutputFailure,
 {
     fn reset(&'a self, t_start: i32) -> CostFn<'a, T, C, D> {
-        CostFn::new(
+        let f = CostFn::new(
             1,
             SingleCostFn::predictive(move |t, j| {
                 let shifted_t = shift_time(t, t_start + 1);
@@ -435,6 +475,50 @@ where
                 }
             }),
         )
+        .with_risk_measure(self.risk_measure())
+        .with_peak_charges(
+            self.peak_charges()
+                .iter()
+                .map(|peak_charge| peak_charge.reset(t_start))
+                .collect(),
+        )
+        .with_continuous_dimensions(self.continuous_dimensions().to_vec());
+        let f = if self.has_transition() {
+            f.with_transition(move |t, prev_j, j| {
+                let shifted_t = shift_time(t, t_start + 1);
+                if shifted_t >= 1 {
+                    self.call_transition(shifted_t, prev_j, j)
+                } else {
+                    Default::default()
+                }
+            })
+        } else {
+            f
+        };
+        let f = if self.has_final_transition() {
+            f.with_final_transition(move |t, j| {
+                let shifted_t = shift_time(t, t_start + 1);
+                if shifted_t >= 1 {
+                    self.call_final_transition(shifted_t, j)
+                } else {
+                    Default::default()
+                }
+            })
+        } else {
+            f
+        };
+        if self.has_projection() {
+            f.with_projection(move |t, prev_j, j| {
+                let shifted_t = shift_time(t, t_start + 1);
+                if shifted_t >= 1 {
+                    self.call_projection(shifted_t, prev_j, j)
+                } else {
+                    j
+                }
+            })
+        } else {
+            f
+        }
     }
 }
 
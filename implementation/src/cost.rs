@@ -4,13 +4,15 @@ use crate::config::Config;
 use crate::model::{ModelOutput, ModelOutputFailure, ModelOutputSuccess};
 use crate::utils::mean;
 use crate::value::Value;
+use cached::{Cached, SizedCache};
 use noisy_float::prelude::*;
 use num::NumCast;
-use pyo3::{IntoPy, PyObject, Python};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::BTreeMap;
 use std::iter::Sum;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 /// Result of cost computation.
 #[derive(Clone, Debug, PartialEq)]
@@ -38,13 +40,28 @@ where
 
     /// Aggregates a vector of costs.
     pub fn mean(costs: Vec<Self>) -> Self {
-        let (raw_costs, outputs) = costs
+        Self::aggregate(costs, RiskMeasure::Mean)
+    }
+
+    /// Aggregates a vector of costs (samples) using the given risk measure.
+    /// The output is merged from the samples which determine the aggregated cost.
+    pub fn aggregate(costs: Vec<Self>, risk_measure: RiskMeasure) -> Self {
+        assert!(!costs.is_empty());
+        let raw_costs: Vec<N64> = costs.iter().map(|c| c.cost).collect();
+        let (cost, samples) = risk_measure.apply(&raw_costs);
+        let mut outputs: Vec<_> = costs
             .into_iter()
-            .map(|Self { cost, output }| (cost, output))
-            .unzip();
+            .map(|Self { output, .. }| Some(output))
+            .collect();
+        let output = ModelOutput::vertical_reduce(
+            samples
+                .iter()
+                .map(|&s| outputs[s].take().unwrap())
+                .collect(),
+        );
         Self {
-            cost: mean(raw_costs),
-            output: ModelOutput::vertical_reduce(outputs),
+            cost,
+            output: output.select_samples(&samples),
         }
     }
 
@@ -114,6 +131,80 @@ where
     }
 }
 
+/// Risk measure used to reduce the samples of an uncertain cost to a single cost.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum RiskMeasure {
+    /// Expected cost.
+    #[default]
+    Mean,
+    /// Largest cost across all samples.
+    WorstCase,
+    /// Conditional value at risk at level $\alpha \in \[0,1)$, i.e. the expected cost of the worst $(1 - \alpha)$-fraction of samples.
+    ConditionalValueAtRisk(f64),
+    /// Expected cost plus $k$ times the standard deviation of the cost.
+    MeanStandardDeviation(f64),
+}
+impl RiskMeasure {
+    /// Returns the aggregated cost and the indices of the samples which determine it.
+    /// Assumes that the risk measure was verified.
+    pub fn apply(&self, costs: &[N64]) -> (N64, Vec<usize>) {
+        assert!(!costs.is_empty());
+        let n = costs.len();
+        match *self {
+            RiskMeasure::Mean => (mean(costs.to_vec()), (0..n).collect()),
+            RiskMeasure::WorstCase => {
+                let s = (0..n).max_by_key(|&s| costs[s]).unwrap();
+                (costs[s], vec![s])
+            }
+            RiskMeasure::ConditionalValueAtRisk(alpha) => {
+                let tail = (((1. - alpha) * n as f64).ceil() as usize).max(1);
+                let mut samples: Vec<usize> = (0..n).collect();
+                samples.sort_by_key(|&s| std::cmp::Reverse(costs[s]));
+                samples.truncate(tail);
+                samples.sort_unstable();
+                (mean(samples.iter().map(|&s| costs[s]).collect()), samples)
+            }
+            RiskMeasure::MeanStandardDeviation(k) => {
+                let expected = mean(costs.to_vec());
+                let cost = if expected.is_finite() {
+                    let variance = mean(
+                        costs.iter().map(|&c| (c - expected).powi(2)).collect(),
+                    );
+                    expected + n64(k) * variance.sqrt()
+                } else {
+                    expected
+                };
+                (cost, (0..n).collect())
+            }
+        }
+    }
+}
+impl<'a> FromPyObject<'a> for RiskMeasure {
+    /// Extracts `"mean"`, `"worst_case"`, `("cvar", alpha)`, or `("mean_std", k)`.
+    fn extract(ob: &'a PyAny) -> PyResult<Self> {
+        if let Ok(name) = ob.extract::<&str>() {
+            match name {
+                "mean" => return Ok(RiskMeasure::Mean),
+                "worst_case" => return Ok(RiskMeasure::WorstCase),
+                _ => {}
+            }
+        } else if let Ok((name, parameter)) = ob.extract::<(&str, f64)>() {
+            match name {
+                "cvar" => {
+                    return Ok(RiskMeasure::ConditionalValueAtRisk(parameter))
+                }
+                "mean_std" => {
+                    return Ok(RiskMeasure::MeanStandardDeviation(parameter))
+                }
+                _ => {}
+            }
+        }
+        Err(PyValueError::new_err(
+            "risk measure must be one of \"mean\", \"worst_case\", (\"cvar\", alpha), or (\"mean_std\", k)",
+        ))
+    }
+}
+
 /// A cost with associated information in case of a failure.
 pub type FailableCost<D> = Cost<(), D>;
 /// A cost without associated information.
@@ -144,16 +235,17 @@ where
         Self(Arc::new(f))
     }
 
-    /// Returns mean if cost function returns a prediction.
-    fn call_mean(&self, t_start: i32, t: i32, x: T) -> Cost<C, D> {
-        Cost::mean(self.call_predictive(t_start, t, x))
-    }
-
-    /// Computes certain cost.
-    fn call_certain(&self, t_start: i32, t: i32, x: T) -> Cost<C, D> {
-        let results = self.call_predictive(t_start, t, x);
-        assert!(results.len() == 1);
-        results.into_iter().next().unwrap()
+    /// Evaluates the cost function on the configuration which is obtained by applying $f$.
+    pub fn map_config<U>(
+        self,
+        f: impl Fn(U) -> T + Send + Sync + 'a,
+    ) -> SingleCostFn<'a, U, C, D>
+    where
+        T: 'a,
+        C: 'a,
+        D: 'a,
+    {
+        SingleCostFn::predictive(move |t, x| (self.0)(t, f(x)))
     }
 
     /// Computes uncertain cost.
@@ -179,10 +271,194 @@ where
 /// A single cost function returning costs without any associated information.
 pub type RawSingleCostFn<'a, T> = SingleCostFn<'a, T, (), ()>;
 
+/// Configurations which can be used to look up cached costs.
+pub trait CacheKey {
+    /// Key of the configuration consisting of the bit pattern of each dimension, i.e. only identical configurations share a key.
+    fn cache_key(&self) -> Vec<u64>;
+
+    /// Key of the configuration where each dimension is rounded to a multiple of $resolution$.
+    fn quantized_cache_key(&self, resolution: f64) -> Vec<u64>;
+}
+impl<'a, T> CacheKey for Config<T>
+where
+    T: Value<'a>,
+{
+    fn cache_key(&self) -> Vec<u64> {
+        self.to_vec()
+            .into_iter()
+            .map(|j| NumCast::from(j).map_or(f64::NAN, |j: f64| j).to_bits())
+            .collect()
+    }
+
+    fn quantized_cache_key(&self, resolution: f64) -> Vec<u64> {
+        self.to_vec()
+            .into_iter()
+            .map(|j| {
+                (NumCast::from(j).map_or(f64::NAN, |j: f64| j) / resolution)
+                    .round()
+                    .to_bits()
+            })
+            .collect()
+    }
+}
+
+/// Statistics of the cache of a cost function.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CacheStatistics {
+    /// Number of evaluations which were answered by the cache.
+    pub hits: u64,
+    /// Number of evaluations which were not answered by the cache.
+    pub misses: u64,
+    /// Number of cached costs.
+    pub size: usize,
+}
+impl CacheStatistics {
+    /// Fraction of evaluations which were answered by the cache.
+    pub fn hit_rate(&self) -> f64 {
+        let evaluations = self.hits + self.misses;
+        if evaluations == 0 {
+            0.
+        } else {
+            self.hits as f64 / evaluations as f64
+        }
+    }
+}
+
+/// Size-bounded and thread-safe cache of the (uncertain) costs of a cost function.
+/// Costs are keyed by the time slot, the time slot of the cost function which was evaluated, and the (quantized) configuration.
+#[derive(Clone)]
+#[allow(clippy::type_complexity)]
+struct CostCache<'a, T, C, D> {
+    key: Arc<dyn Fn(&T) -> Vec<u64> + Send + Sync + 'a>,
+    entries: Arc<Mutex<SizedCache<(i32, i32, Vec<u64>), Vec<Cost<C, D>>>>>,
+}
+
+/// Cost of moving from the configuration of the previous time slot to the configuration of time slot $t$ which is paid in addition to the hitting cost,
+/// e.g. for energy that is stored in (or drawn from) batteries.
+#[allow(clippy::type_complexity)]
+pub type TransitionFn<'a, T, C, D> =
+    Arc<dyn Fn(i32, T, T) -> Cost<C, D> + Send + Sync + 'a>;
+
+/// Cost of the configuration of the final time slot $t$ of the time horizon which is paid in addition to its transition cost,
+/// e.g. for load which is still deferred at the end of the time horizon.
+#[allow(clippy::type_complexity)]
+pub type FinalTransitionFn<'a, T, C, D> =
+    Arc<dyn Fn(i32, T) -> Cost<C, D> + Send + Sync + 'a>;
+
+/// Projects a configuration of time slot $t$ onto the configurations which may follow the configuration of the previous time slot,
+/// i.e. whose transition cost is finite. Used by algorithms which cannot handle infinite transition costs.
+#[allow(clippy::type_complexity)]
+pub type ProjectionFn<'a, T> = Arc<dyn Fn(i32, T, T) -> T + Send + Sync + 'a>;
+
+/// Charge on the peak of some quantity reported by the model (see `ModelOutputSuccess::peak`) during each billing window, e.g. a demand charge on the peak power drawn by a location.
+///
+/// Peak charges are paid by the objective function in addition to hitting, transition, and movement costs.
+/// As the peak of a billing window depends on all of its time slots, peak charges cannot be attributed to individual time slots.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PeakCharge {
+    /// Key of the quantity.
+    pub key: String,
+    /// Non-negative price of a unit of the peak.
+    pub price: f64,
+    /// Positive length of a billing window in time slots.
+    pub window: i32,
+    /// Upper bound of the quantity during all time slots.
+    pub bound: f64,
+    /// Number of time slots of the billing window of time slot $1$ which precede time slot $1$ (modulo the length of billing windows).
+    offset: i32,
+    /// Peak of the quantity during the time slots of the billing window of time slot $1$ which precede time slot $1$.
+    running_peak: f64,
+}
+impl PeakCharge {
+    /// Creates a peak charge whose billing windows start with time slots $1, w + 1, 2w + 1, \dots$
+    pub fn new(key: String, price: f64, window: i32, bound: f64) -> Self {
+        PeakCharge {
+            key,
+            price,
+            window,
+            bound,
+            offset: 0,
+            running_peak: 0.,
+        }
+    }
+
+    /// Index of the billing window containing time slot $t$.
+    pub fn billing_window(&self, t: i32) -> i32 {
+        (t + self.offset - 1).div_euclid(self.window)
+    }
+
+    /// Peak of the quantity during the time slots of the billing window of time slot $1$ which precede time slot $1$.
+    /// It has already been paid for and is only charged when it is exceeded.
+    pub fn running_peak(&self) -> f64 {
+        self.running_peak
+    }
+
+    /// Shifts the billing windows to some new initial time $t_start$ (see `Resettable`).
+    /// The running peak is only known for $t_start = 0$ and must be set anew otherwise.
+    pub fn reset(&self, t_start: i32) -> Self {
+        PeakCharge {
+            offset: (self.offset + t_start).rem_euclid(self.window),
+            running_peak: if t_start == 0 { self.running_peak } else { 0. },
+            ..self.clone()
+        }
+    }
+
+    /// Cost of the peaks of the quantity reported by $outputs$ where the $i$-th output belongs to time slot $i + 1$.
+    pub fn cost<C, D>(&self, outputs: &[&ModelOutput<C, D>]) -> N64
+    where
+        C: ModelOutputSuccess,
+        D: ModelOutputFailure,
+    {
+        let mut peaks: BTreeMap<i32, N64> = BTreeMap::new();
+        for (i, output) in outputs.iter().enumerate() {
+            let peak =
+                peaks.entry(self.billing_window(i as i32 + 1)).or_default();
+            *peak = (*peak).max(n64(output.peak(&self.key)));
+        }
+        let first_window = self.billing_window(1);
+        peaks
+            .into_iter()
+            .map(|(window, peak)| {
+                let running_peak = if window == first_window {
+                    n64(self.running_peak)
+                } else {
+                    n64(0.)
+                };
+                n64(self.price) * (peak - running_peak).max(n64(0.))
+            })
+            .sum()
+    }
+}
+
 /// Cost functions that arrived over time. Individual cost functions may have different domains.
 /// For example, in a predictive online setting, a cost function arriving at time $t$ generally has the domain $\[t, t + w\]$.
 #[derive(Clone)]
-pub struct CostFn<'a, T, C, D>(BTreeMap<i32, SingleCostFn<'a, T, C, D>>);
+pub struct CostFn<'a, T, C, D> {
+    fs: BTreeMap<i32, SingleCostFn<'a, T, C, D>>,
+    risk_measure: RiskMeasure,
+    transition: Option<TransitionFn<'a, T, C, D>>,
+    final_transition: Option<FinalTransitionFn<'a, T, C, D>>,
+    projection: Option<ProjectionFn<'a, T>>,
+    peak_charges: Vec<PeakCharge>,
+    continuous_dimensions: Vec<usize>,
+    cache: Option<CostCache<'a, T, C, D>>,
+}
+impl<'a, T, C, D> CostFn<'a, T, C, D> {
+    /// Risk measure which is used to reduce uncertain costs.
+    pub fn risk_measure(&self) -> RiskMeasure {
+        self.risk_measure
+    }
+
+    /// Charges on the peaks of the outputs of the cost function which are paid in addition to its costs.
+    pub fn peak_charges(&self) -> &[PeakCharge] {
+        &self.peak_charges
+    }
+
+    /// Dimensions which model continuous quantities (e.g. stored energy or deferred load) and hence cannot be restricted to integral values.
+    pub fn continuous_dimensions(&self) -> &[usize] {
+        &self.continuous_dimensions
+    }
+}
 impl<'a, T, C, D> CostFn<'a, T, C, D>
 where
     T: Clone,
@@ -191,38 +467,296 @@ where
 {
     /// Creates empty cost function.
     pub fn empty() -> Self {
-        CostFn(BTreeMap::new())
+        CostFn {
+            fs: BTreeMap::new(),
+            risk_measure: RiskMeasure::default(),
+            transition: None,
+            final_transition: None,
+            projection: None,
+            peak_charges: vec![],
+            continuous_dimensions: vec![],
+            cache: None,
+        }
     }
 
     /// Creates initial cost function from some time $t \geq 1$.
     pub fn new(t: i32, f: SingleCostFn<'a, T, C, D>) -> Self {
         let mut fs = BTreeMap::new();
         fs.insert(t, f);
-        CostFn(fs)
+        CostFn {
+            fs,
+            risk_measure: RiskMeasure::default(),
+            transition: None,
+            final_transition: None,
+            projection: None,
+            peak_charges: vec![],
+            continuous_dimensions: vec![],
+            cache: None,
+        }
+    }
+
+    /// Evaluates all cost functions on the configuration which is obtained by applying $f$.
+    /// Must not have a transition cost. The cache, peak charges, and continuous dimensions are retained, and the cache is keyed by the configuration which is obtained by applying $f$.
+    pub fn map_config<U>(
+        self,
+        f: impl Fn(U) -> T + Send + Sync + 'a,
+    ) -> CostFn<'a, U, C, D>
+    where
+        T: 'a,
+        U: Clone,
+        C: 'a,
+        D: 'a,
+    {
+        assert!(
+            self.transition.is_none()
+                && self.final_transition.is_none()
+                && self.projection.is_none(),
+            "Cannot map the configurations of a transition cost."
+        );
+        let f = Arc::new(f);
+        let cache = self.cache.map(|cache| {
+            let f = f.clone();
+            let key = cache.key;
+            CostCache {
+                key: Arc::new(move |x: &U| key(&f(x.clone()))),
+                entries: cache.entries,
+            }
+        });
+        CostFn {
+            fs: self
+                .fs
+                .into_iter()
+                .map(|(t, g)| {
+                    let f = f.clone();
+                    (t, g.map_config(move |x| f(x)))
+                })
+                .collect(),
+            risk_measure: self.risk_measure,
+            transition: None,
+            final_transition: None,
+            projection: None,
+            peak_charges: self.peak_charges,
+            continuous_dimensions: self.continuous_dimensions,
+            cache,
+        }
+    }
+
+    /// Uses the given risk measure to reduce uncertain costs. Default is the mean.
+    pub fn with_risk_measure(mut self, risk_measure: RiskMeasure) -> Self {
+        self.risk_measure = risk_measure;
+        self
+    }
+
+    /// Pays the given transition cost in addition to the hitting cost during each time slot.
+    pub fn with_transition(
+        mut self,
+        transition: impl Fn(i32, T, T) -> Cost<C, D> + Send + Sync + 'a,
+    ) -> Self {
+        self.transition = Some(Arc::new(transition));
+        self
+    }
+
+    /// Whether a transition cost is paid in addition to the hitting cost.
+    pub fn has_transition(&self) -> bool {
+        self.transition.is_some()
+    }
+
+    /// Computes the transition cost from configuration $prev_x$ to configuration $x$ during time slot $t$.
+    pub fn call_transition(&self, t: i32, prev_x: T, x: T) -> Cost<C, D> {
+        match &self.transition {
+            Some(transition) => transition(t, prev_x, x),
+            None => Default::default(),
+        }
+    }
+
+    /// Pays the given final transition cost in addition to the transition cost during the final time slot of the time horizon.
+    pub fn with_final_transition(
+        mut self,
+        final_transition: impl Fn(i32, T) -> Cost<C, D> + Send + Sync + 'a,
+    ) -> Self {
+        self.final_transition = Some(Arc::new(final_transition));
+        self
+    }
+
+    /// Whether a final transition cost is paid at the end of the time horizon.
+    pub fn has_final_transition(&self) -> bool {
+        self.final_transition.is_some()
+    }
+
+    /// Computes the final transition cost of configuration $x$ if time slot $t$ is the final time slot of the time horizon.
+    pub fn call_final_transition(&self, t: i32, x: T) -> Cost<C, D> {
+        match &self.final_transition {
+            Some(final_transition) => final_transition(t, x),
+            None => Default::default(),
+        }
+    }
+
+    /// Restricts the configurations which may follow the configuration of the previous time slot to those returned by the given projection.
+    pub fn with_projection(
+        mut self,
+        projection: impl Fn(i32, T, T) -> T + Send + Sync + 'a,
+    ) -> Self {
+        self.projection = Some(Arc::new(projection));
+        self
+    }
+
+    /// Whether the configurations which may follow the configuration of the previous time slot are restricted.
+    pub fn has_projection(&self) -> bool {
+        self.projection.is_some()
+    }
+
+    /// Projects configuration $x$ onto the configurations which may follow configuration $prev_x$ during time slot $t$.
+    pub fn call_projection(&self, t: i32, prev_x: T, x: T) -> T {
+        match &self.projection {
+            Some(projection) => projection(t, prev_x, x),
+            None => x,
+        }
+    }
+
+    /// Marks the given dimensions as modeling continuous quantities (see `continuous_dimensions`).
+    pub fn with_continuous_dimensions(
+        mut self,
+        continuous_dimensions: Vec<usize>,
+    ) -> Self {
+        self.continuous_dimensions = continuous_dimensions;
+        self
+    }
+
+    /// Pays the given charges on the peaks of the outputs in addition to the costs.
+    pub fn with_peak_charges(mut self, peak_charges: Vec<PeakCharge>) -> Self {
+        self.peak_charges = peak_charges;
+        self
+    }
+
+    /// Sets the running peak of the billing window of time slot $1$ of each peak charge (see `PeakCharge::running_peak`).
+    pub fn with_running_peaks(mut self, running_peaks: &[f64]) -> Self {
+        assert!(running_peaks.len() == self.peak_charges.len());
+        for (peak_charge, &running_peak) in
+            self.peak_charges.iter_mut().zip(running_peaks)
+        {
+            peak_charge.running_peak = running_peak;
+        }
+        self
+    }
+
+    /// Caches up to $capacity$ (uncertain) costs keyed by time slot and configuration.
+    ///
+    /// Only identical configurations share a key, i.e. the costs of fractional configurations are cached exactly but are only reused if a configuration is evaluated repeatedly.
+    pub fn with_cache(mut self, capacity: usize) -> Self
+    where
+        T: CacheKey + 'a,
+    {
+        self.cache = Some(CostCache {
+            key: Arc::new(T::cache_key),
+            entries: Arc::new(Mutex::new(SizedCache::with_size(capacity))),
+        });
+        self
+    }
+
+    /// Caches up to $capacity$ (uncertain) costs keyed by time slot and the configuration rounded to multiples of $resolution$.
+    ///
+    /// Configurations which are rounded to the same key share the cost of the configuration that was evaluated first,
+    /// i.e. costs of fractional configurations are approximated up to $resolution$ in each dimension. Integral configurations are cached exactly if $resolution \leq 1$.
+    pub fn with_quantized_cache(
+        mut self,
+        capacity: usize,
+        resolution: f64,
+    ) -> Self
+    where
+        T: CacheKey + 'a,
+    {
+        assert!(
+            resolution > 0.,
+            "The resolution of the cache must be positive (was {}).",
+            resolution
+        );
+        self.cache = Some(CostCache {
+            key: Arc::new(move |x: &T| x.quantized_cache_key(resolution)),
+            entries: Arc::new(Mutex::new(SizedCache::with_size(capacity))),
+        });
+        self
+    }
+
+    /// Whether costs are cached.
+    pub fn has_cache(&self) -> bool {
+        self.cache.is_some()
+    }
+
+    /// Returns statistics of the cache if costs are cached.
+    pub fn cache_statistics(&self) -> Option<CacheStatistics> {
+        self.cache.as_ref().map(|cache| {
+            let entries = cache.entries.lock().unwrap();
+            CacheStatistics {
+                hits: entries.cache_hits().unwrap_or(0),
+                misses: entries.cache_misses().unwrap_or(0),
+                size: entries.cache_size(),
+            }
+        })
     }
 
     /// Adds a new cost function which may return uncertain predictions.
     /// Must always return at least one sample (which corresponds to certainty).
     pub fn add(&mut self, t: i32, f: SingleCostFn<'a, T, C, D>) {
-        self.0.insert(t, f);
+        self.fs.insert(t, f);
     }
 
     /// Returns mean if cost function returns a prediction.
     pub fn call_mean(&self, t: i32, x: T) -> Cost<C, D> {
-        let (&t_start, f) = self.get(t);
-        f.call_mean(t_start, t, x)
+        Cost::mean(self.call_predictive(t, x))
+    }
+
+    /// Reduces a prediction using the risk measure of the cost function.
+    pub fn call_risk_adjusted(&self, t: i32, x: T) -> Cost<C, D> {
+        Cost::aggregate(self.call_predictive(t, x), self.risk_measure)
     }
 
     /// Computes certain cost.
     pub fn call_certain(&self, t: i32, x: T) -> Cost<C, D> {
-        let (&t_start, f) = self.get(t);
-        f.call_certain(t_start, t, x)
+        let results = self.call_predictive(t, x);
+        assert!(results.len() == 1);
+        results.into_iter().next().unwrap()
     }
 
     /// Computes uncertain cost.
     pub fn call_predictive(&self, t: i32, x: T) -> Vec<Cost<C, D>> {
         let (&t_start, f) = self.get(t);
-        f.call_predictive(t_start, t, x)
+        match &self.cache {
+            Some(cache) => {
+                let key = (t_start, t, (cache.key)(&x));
+                if let Some(results) =
+                    cache.entries.lock().unwrap().cache_get(&key)
+                {
+                    return results.clone();
+                }
+                // the lock is not held during evaluation such that other threads are not blocked
+                let results = f.call_predictive(t_start, t, x);
+                cache
+                    .entries
+                    .lock()
+                    .unwrap()
+                    .cache_set(key, results.clone());
+                results
+            }
+            None => f.call_predictive(t_start, t, x),
+        }
+    }
+
+    /// Computes the cost of the $s$-th sample. Certain costs are shared by all samples.
+    ///
+    /// Samples with the same index are assumed to belong to the same scenario across time slots.
+    pub fn call_sample(&self, t: i32, x: T, s: usize) -> Cost<C, D> {
+        let mut results = self.call_predictive(t, x);
+        if results.len() == 1 {
+            results.pop().unwrap()
+        } else {
+            assert!(
+                s < results.len(),
+                "Cost function returned {} samples, but sample {} was requested.",
+                results.len(),
+                s
+            );
+            results.swap_remove(s)
+        }
     }
 
     /// Returns mean if cost function returns a prediction while ensuring that the given parameter is within the decision space.
@@ -245,6 +779,26 @@ where
         }
     }
 
+    /// Reduces a prediction using the risk measure of the cost function while ensuring that the given parameter is within the decision space.
+    pub fn call_risk_adjusted_within_bounds<B>(
+        &self,
+        t: i32,
+        x: T,
+        bounds: &B,
+    ) -> Cost<C, D>
+    where
+        B: DecisionSpace<'a, T>,
+    {
+        if bounds.within(&x) {
+            self.call_risk_adjusted(t, x)
+        } else {
+            Cost::new(
+                n64(f64::INFINITY),
+                ModelOutput::Failure(D::outside_decision_space()),
+            )
+        }
+    }
+
     /// Computes certain cost while ensuring that the given parameter is within the decision space.
     pub fn call_certain_within_bounds<B>(
         &self,
@@ -265,6 +819,27 @@ where
         }
     }
 
+    /// Computes the cost of the $s$-th sample while ensuring that the given parameter is within the decision space.
+    pub fn call_sample_within_bounds<B>(
+        &self,
+        t: i32,
+        x: T,
+        s: usize,
+        bounds: &B,
+    ) -> Cost<C, D>
+    where
+        B: DecisionSpace<'a, T>,
+    {
+        if bounds.within(&x) {
+            self.call_sample(t, x, s)
+        } else {
+            Cost::new(
+                n64(f64::INFINITY),
+                ModelOutput::Failure(D::outside_decision_space()),
+            )
+        }
+    }
+
     /// Computes uncertain cost while ensuring that the given parameter is within the decision space.
     pub fn call_predictive_within_bounds<B>(
         &self,
@@ -297,7 +872,7 @@ where
             "Cost functions are only defined for `t >= 1` (got `t = {}`).",
             t
         );
-        self.0.range(1..=t).last().expect("Cost function does not have an implementation for the given time slot")
+        self.fs.range(1..=t).last().expect("Cost function does not have an implementation for the given time slot")
     }
 }
 
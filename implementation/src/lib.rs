@@ -44,6 +44,7 @@ pub mod cost;
 pub mod distance;
 pub mod model;
 pub mod problem;
+pub mod report;
 pub mod result;
 pub mod schedule;
 pub mod streaming;
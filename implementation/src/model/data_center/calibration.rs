@@ -0,0 +1,254 @@
+//! Calibration of the parameters of models from measurements.
+//!
+//! Parameters are fitted by least squares subject to constraints which preserve the convexity of the models.
+
+use super::model::JobType;
+use super::models::energy_consumption::{
+    LinearEnergyConsumptionModel, NonLinearEnergyConsumptionModel,
+    SimplifiedLinearEnergyConsumptionModel,
+};
+use crate::numerics::convex_optimization::{find_minimizer, WrappedObjective};
+use crate::result::{Failure, Result};
+use crate::utils::assert;
+use noisy_float::prelude::*;
+use pyo3::prelude::*;
+use std::collections::HashMap;
+
+/// Lower bound on the exponent $\alpha$ of the non-linear energy consumption model, which ensures $\alpha > 1$.
+pub static MIN_ALPHA: f64 = 1.001;
+/// Upper bound on the exponent $\alpha$ of the non-linear energy consumption model.
+pub static MAX_ALPHA: f64 = 10.;
+
+/// Goodness of fit of a calibrated model.
+#[pyclass]
+#[derive(Clone, Debug, PartialEq)]
+pub struct GoodnessOfFit {
+    /// Coefficient of determination $R^2$, i.e. the fraction of the variance of the measurements which is explained by the model.
+    /// `NaN` if the measurements have no variance.
+    #[pyo3(get)]
+    pub r_squared: f64,
+    /// Root mean squared error.
+    #[pyo3(get)]
+    pub rmse: f64,
+    /// Number of measurements.
+    #[pyo3(get)]
+    pub samples: usize,
+}
+impl GoodnessOfFit {
+    fn new(measured: &[f64], predicted: &[f64]) -> Self {
+        let n = measured.len() as f64;
+        let mean = measured.iter().sum::<f64>() / n;
+        let residual: f64 = measured
+            .iter()
+            .zip(predicted)
+            .map(|(y, y_hat)| (y - y_hat).powi(2))
+            .sum();
+        let total: f64 = measured.iter().map(|y| (y - mean).powi(2)).sum();
+        GoodnessOfFit {
+            r_squared: if total > 0. {
+                1. - residual / total
+            } else {
+                f64::NAN
+            },
+            rmse: (residual / n).sqrt(),
+            samples: measured.len(),
+        }
+    }
+}
+
+/// Calibrated model with its goodness of fit.
+#[derive(Clone)]
+pub struct Calibration<M> {
+    pub model: M,
+    pub fit: GoodnessOfFit,
+}
+
+/// Fits the linear energy consumption model to samples of the power consumed by a server in a unit of time at some utilization, i.e. $(s, p)$.
+/// The power consumed when idling is non-negative and does not exceed the power consumed at full load.
+pub fn calibrate_linear_energy_consumption_model(
+    samples: &[(f64, f64)],
+) -> Result<Calibration<LinearEnergyConsumptionModel>> {
+    verify_power_samples(samples, 2)?;
+    let (ss, ps): (Vec<_>, Vec<_>) = samples.iter().copied().unzip();
+    let (phi_min, slope) = non_negative_least_squares(&ss, &ps);
+    Ok(Calibration {
+        model: LinearEnergyConsumptionModel {
+            phi_min,
+            phi_max: phi_min + slope,
+        },
+        fit: GoodnessOfFit::new(
+            &ps,
+            &ss.iter().map(|s| phi_min + slope * s).collect::<Vec<_>>(),
+        ),
+    })
+}
+
+/// Fits the simplified linear energy consumption model to samples of the power consumed by a server in a unit of time at some utilization, i.e. $(s, p)$.
+/// The power consumed at full load is non-negative.
+pub fn calibrate_simplified_linear_energy_consumption_model(
+    samples: &[(f64, f64)],
+) -> Result<Calibration<SimplifiedLinearEnergyConsumptionModel>> {
+    verify_power_samples(samples, 1)?;
+    let (ss, ps): (Vec<_>, Vec<_>) = samples.iter().copied().unzip();
+    let ws: Vec<_> = ss.iter().map(|s| (1. + s) / 2.).collect();
+    let phi_max = (ws.iter().zip(&ps).map(|(w, p)| w * p).sum::<f64>()
+        / ws.iter().map(|w| w * w).sum::<f64>())
+    .max(0.);
+    Ok(Calibration {
+        model: SimplifiedLinearEnergyConsumptionModel { phi_max },
+        fit: GoodnessOfFit::new(
+            &ps,
+            &ws.iter().map(|w| phi_max * w).collect::<Vec<_>>(),
+        ),
+    })
+}
+
+/// Fits the non-linear energy consumption model to samples of the power consumed by a server in a unit of time at some utilization, i.e. $(s, p)$.
+/// The power consumed when idling is non-negative, `MIN_ALPHA` $\leq \alpha \leq$ `MAX_ALPHA` (i.e. $\alpha > 1$), and $\beta > 0$.
+/// Fails if the fitted dynamic power vanishes, i.e. the power does not increase with the utilization, in which case $\beta$ would be infinite.
+///
+/// For a fixed $\alpha$, the remaining parameters are fitted by non-negative least squares. $\alpha$ is chosen to minimize the squared error.
+pub fn calibrate_non_linear_energy_consumption_model(
+    samples: &[(f64, f64)],
+) -> Result<Calibration<NonLinearEnergyConsumptionModel>> {
+    verify_power_samples(samples, 3)?;
+    let (ss, ps): (Vec<_>, Vec<_>) = samples.iter().copied().unzip();
+    let fit = |alpha: f64| {
+        let xs: Vec<_> = ss.iter().map(|s| s.powf(alpha)).collect();
+        let (phi_min, c) = non_negative_least_squares(&xs, &ps);
+        let predicted: Vec<_> = xs.iter().map(|x| phi_min + c * x).collect();
+        (phi_min, c, predicted)
+    };
+    let objective = WrappedObjective::new((), |alpha: &[f64], _| {
+        let (_, _, predicted) = fit(alpha[0]);
+        n64(ps
+            .iter()
+            .zip(&predicted)
+            .map(|(p, p_hat)| (p - p_hat).powi(2))
+            .sum())
+    });
+    let (argmin, _) = find_minimizer(objective, vec![(MIN_ALPHA, MAX_ALPHA)]);
+    let alpha = argmin[0].clamp(MIN_ALPHA, MAX_ALPHA);
+    let (phi_min, c, predicted) = fit(alpha);
+    assert(
+        c > 0.,
+        Failure::Invalid(
+            "dynamic power must increase with the utilization".to_string(),
+        ),
+    )?;
+    Ok(Calibration {
+        model: NonLinearEnergyConsumptionModel {
+            phi_min,
+            alpha,
+            beta: 1. / c,
+        },
+        fit: GoodnessOfFit::new(&ps, &predicted),
+    })
+}
+
+/// Fits the processing times of a job type to observed durations of jobs which were processed in isolation (i.e. at full utilization) on some server type, i.e. $(k, \eta)$.
+/// The processing time on each server type is the mean of its observed durations. The job type can only be processed by the observed server types.
+pub fn calibrate_processing_times(
+    key: String,
+    durations: &[(String, f64)],
+) -> Result<Calibration<JobType>> {
+    assert(
+        !durations.is_empty(),
+        Failure::Invalid("at least one duration must be observed".to_string()),
+    )?;
+    for (server_type, duration) in durations {
+        assert(
+            duration.is_finite() && *duration > 0.,
+            Failure::Invalid(format!(
+                "observed duration on server type `{}` must be positive (was {})",
+                server_type, duration
+            )),
+        )?;
+    }
+
+    let mut totals: HashMap<String, (f64, usize)> = HashMap::new();
+    for (server_type, duration) in durations {
+        let total = totals.entry(server_type.clone()).or_insert((0., 0));
+        total.0 += duration;
+        total.1 += 1;
+    }
+    let processing_times: HashMap<_, _> = totals
+        .into_iter()
+        .map(|(server_type, (total, n))| (server_type, total / n as f64))
+        .collect();
+    let (measured, predicted): (Vec<_>, Vec<_>) = durations
+        .iter()
+        .map(|(server_type, duration)| {
+            (*duration, processing_times[server_type])
+        })
+        .unzip();
+    Ok(Calibration {
+        model: JobType::from_cached(key, processing_times),
+        fit: GoodnessOfFit::new(&measured, &predicted),
+    })
+}
+
+/// Checks that there are at least $n$ samples with utilizations in $\[0,1\]$ and non-negative power.
+fn verify_power_samples(samples: &[(f64, f64)], n: usize) -> Result<()> {
+    assert(
+        samples.len() >= n,
+        Failure::Invalid(format!(
+            "at least {} samples are required (was {})",
+            n,
+            samples.len()
+        )),
+    )?;
+    for &(s, p) in samples {
+        assert(
+            (0. ..=1.).contains(&s),
+            Failure::Invalid(format!(
+                "utilization must be in [0, 1] (was {})",
+                s
+            )),
+        )?;
+        assert(
+            p.is_finite() && p >= 0.,
+            Failure::Invalid(format!("power must be non-negative (was {})", p)),
+        )?;
+    }
+    Ok(())
+}
+
+/// Least squares fit of $y = a + b x$ subject to $a, b \geq 0$. Returns $(a, b)$.
+///
+/// The optimum is the unconstrained optimum if it is feasible and otherwise the optimum of one of the faces of the feasible region.
+fn non_negative_least_squares(xs: &[f64], ys: &[f64]) -> (f64, f64) {
+    let n = xs.len() as f64;
+    let mean_x = xs.iter().sum::<f64>() / n;
+    let mean_y = ys.iter().sum::<f64>() / n;
+    let sxx: f64 = xs.iter().map(|x| (x - mean_x).powi(2)).sum();
+    let sxy: f64 = xs
+        .iter()
+        .zip(ys)
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum();
+    let xx: f64 = xs.iter().map(|x| x * x).sum();
+    let xy: f64 = xs.iter().zip(ys).map(|(x, y)| x * y).sum();
+
+    let mut candidates = vec![(mean_y.max(0.), 0.)];
+    if xx > 0. {
+        candidates.push((0., (xy / xx).max(0.)));
+    }
+    if sxx > 0. {
+        let b = sxy / sxx;
+        let a = mean_y - b * mean_x;
+        if a >= 0. && b >= 0. {
+            candidates.push((a, b));
+        }
+    }
+    let error = |&(a, b): &(f64, f64)| -> f64 {
+        xs.iter()
+            .zip(ys)
+            .map(|(x, y)| (y - a - b * x).powi(2))
+            .sum()
+    };
+    candidates
+        .into_iter()
+        .min_by(|u, v| error(u).total_cmp(&error(v)))
+        .unwrap()
+}
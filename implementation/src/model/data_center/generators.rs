@@ -0,0 +1,309 @@
+//! Seeded generators of synthetic loads and energy prices.
+//!
+//! Generated series are reproducible given their seed and allow stress-testing algorithms on controlled scenarios.
+
+use super::loads::LoadProfile;
+use super::models::energy_cost::LinearEnergyCostModel;
+use crate::result::{Failure, Result};
+use crate::utils::assert;
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg64;
+use std::f64::consts::PI;
+use std::sync::Arc;
+
+/// Maximum rate for which Poisson arrivals are sampled exactly. Larger rates are approximated by a normal distribution.
+static MAX_EXACT_POISSON_RATE: f64 = 30.;
+
+/// Pattern of the load of a single job type. Time slots are indexed from $0$ and loads are truncated to be non-negative.
+#[derive(Clone, Debug, PartialEq)]
+pub enum LoadPattern {
+    /// Diurnal sinusoid $mean + amplitude \sin(2 \pi (t + phase) / period)$ with additive Gaussian noise.
+    Diurnal {
+        mean: f64,
+        amplitude: f64,
+        /// Length of a day in time slots.
+        period: f64,
+        /// Shift in time slots.
+        phase: f64,
+        /// Standard deviation of the noise.
+        noise: f64,
+    },
+    /// Bursty arrivals of a Markov-modulated Poisson process.
+    /// During each time slot, the number of arrivals is Poisson distributed with the rate of the current state.
+    /// The process starts in the first state and changes its state according to a Markov chain after each time slot.
+    Mmpp {
+        /// Expected number of arrivals during a time slot for each state.
+        rates: Vec<f64>,
+        /// Probabilities of transitioning from one state (row) to another (column).
+        transitions: Vec<Vec<f64>>,
+    },
+    /// Constant load which is interrupted by flash crowds.
+    /// During each time slot, a flash crowd starts with some probability and adds $magnitude \cdot base$ to the load, which then decays geometrically.
+    FlashCrowd {
+        base: f64,
+        /// Probability that a flash crowd starts during a time slot.
+        probability: f64,
+        /// Peak of a flash crowd relative to the base load.
+        magnitude: f64,
+        /// Fraction of the additional load of a flash crowd which persists to the next time slot, in $[0,1)$.
+        decay: f64,
+        /// Standard deviation of the noise.
+        noise: f64,
+    },
+    /// Linear trend $level + trend \cdot t$ plus a sinusoid for each season with additive Gaussian noise.
+    TrendSeasonal {
+        level: f64,
+        /// Change of the load per time slot.
+        trend: f64,
+        /// Amplitude and period (in time slots) of each season.
+        seasons: Vec<(f64, f64)>,
+        /// Standard deviation of the noise.
+        noise: f64,
+    },
+}
+impl LoadPattern {
+    /// Samples the load during the first $t_end$ time slots.
+    fn sample(&self, rng: &mut Pcg64, t_end: i32) -> Vec<f64> {
+        let loads: Vec<f64> = match self {
+            LoadPattern::Diurnal {
+                mean,
+                amplitude,
+                period,
+                phase,
+                noise,
+            } => (0..t_end)
+                .map(|t| {
+                    mean + amplitude
+                        * (2. * PI * (t as f64 + phase) / period).sin()
+                        + noise * normal(rng)
+                })
+                .collect(),
+            LoadPattern::Mmpp { rates, transitions } => {
+                let mut state = 0;
+                (0..t_end)
+                    .map(|_| {
+                        let load = poisson(rng, rates[state]);
+                        state = categorical(rng, &transitions[state]);
+                        load
+                    })
+                    .collect()
+            }
+            LoadPattern::FlashCrowd {
+                base,
+                probability,
+                magnitude,
+                decay,
+                noise,
+            } => {
+                let mut surge = 0.;
+                (0..t_end)
+                    .map(|_| {
+                        surge *= decay;
+                        if rng.gen_bool(*probability) {
+                            surge += magnitude * base;
+                        }
+                        base + surge + noise * normal(rng)
+                    })
+                    .collect()
+            }
+            LoadPattern::TrendSeasonal {
+                level,
+                trend,
+                seasons,
+                noise,
+            } => (0..t_end)
+                .map(|t| {
+                    let t = t as f64;
+                    level
+                        + trend * t
+                        + seasons
+                            .iter()
+                            .map(|(amplitude, period)| {
+                                amplitude * (2. * PI * t / period).sin()
+                            })
+                            .sum::<f64>()
+                        + noise * normal(rng)
+                })
+                .collect(),
+        };
+        loads.into_iter().map(|load| load.max(0.)).collect()
+    }
+}
+
+/// Generator of load profiles with a pattern for each job type.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LoadGenerator {
+    /// Pattern of each job type (or more generally, of each entry of a load profile).
+    pub patterns: Vec<LoadPattern>,
+    pub seed: u64,
+}
+impl LoadGenerator {
+    pub fn new(patterns: Vec<LoadPattern>, seed: u64) -> Self {
+        LoadGenerator { patterns, seed }
+    }
+
+    /// Generates the load profiles of the first $t_end$ time slots.
+    /// The loads of each job type are sampled independently, i.e. adding a job type does not affect the loads of the others.
+    pub fn generate(&self, t_end: i32) -> Result<Vec<LoadProfile>> {
+        assert(
+            t_end >= 0,
+            Failure::Invalid(format!(
+                "time horizon must be non-negative, is {}",
+                t_end
+            )),
+        )?;
+        for pattern in &self.patterns {
+            pattern.verify()?;
+        }
+
+        let loads: Vec<_> = self
+            .patterns
+            .iter()
+            .enumerate()
+            .map(|(i, pattern)| {
+                let mut rng =
+                    Pcg64::seed_from_u64(self.seed.wrapping_add(i as u64));
+                pattern.sample(&mut rng, t_end)
+            })
+            .collect();
+        Ok((0..t_end as usize)
+            .map(|t| LoadProfile::raw(loads.iter().map(|l| l[t]).collect()))
+            .collect())
+    }
+}
+
+/// Generator of energy prices which match the given sample statistics and are correlated with the total load.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PriceGenerator {
+    pub mean: f64,
+    /// Standard deviation of the prices.
+    pub std_dev: f64,
+    /// Correlation of the prices with the total load, in $[-1,1]$.
+    pub correlation: f64,
+    pub seed: u64,
+}
+impl PriceGenerator {
+    pub fn new(mean: f64, std_dev: f64, correlation: f64, seed: u64) -> Self {
+        PriceGenerator {
+            mean,
+            std_dev,
+            correlation,
+            seed,
+        }
+    }
+
+    /// Generates a price for each time slot of the given loads.
+    ///
+    /// The sample mean, standard deviation, and correlation with the total load of the prices match exactly.
+    /// If the total load is constant, the prices are uncorrelated. Prices are truncated to be non-negative,
+    /// which only affects the statistics if the standard deviation is large relative to the mean.
+    pub fn generate(&self, loads: &[LoadProfile]) -> Result<Vec<f64>> {
+        self.verify()?;
+        let n = loads.len();
+        if n == 0 {
+            return Ok(vec![]);
+        }
+
+        let totals: Vec<_> =
+            loads.iter().map(|load| load.total().raw()).collect();
+        let mut rng = Pcg64::seed_from_u64(self.seed);
+        let noise: Vec<_> = (0..n).map(|_| normal(&mut rng)).collect();
+
+        let z_load = standardize(&totals);
+        let correlation = if z_load.iter().any(|&z| z != 0.) {
+            self.correlation
+        } else {
+            0.
+        };
+        // remove the component of the noise which is correlated with the load
+        let projection = dot(&noise, &z_load) / n as f64;
+        let z_noise = standardize(
+            &noise
+                .iter()
+                .zip(&z_load)
+                .map(|(e, z)| e - projection * z)
+                .collect::<Vec<_>>(),
+        );
+        Ok(z_load
+            .iter()
+            .zip(&z_noise)
+            .map(|(z_load, z_noise)| {
+                let z = correlation * z_load
+                    + (1. - correlation.powi(2)).sqrt() * z_noise;
+                (self.mean + self.std_dev * z).max(0.)
+            })
+            .collect())
+    }
+}
+
+/// Linear energy cost model whose cost during time slot $t$ is the $t$-th price.
+/// Time slots beyond the generated prices retain the final price.
+pub fn into_energy_cost_model(
+    prices: Vec<f64>,
+) -> Result<LinearEnergyCostModel> {
+    assert(
+        !prices.is_empty(),
+        Failure::Invalid("at least one price must be given".to_string()),
+    )?;
+    Ok(LinearEnergyCostModel {
+        cost: Arc::new(move |t| {
+            prices[((t - 1).max(0) as usize).min(prices.len() - 1)]
+        }),
+    })
+}
+
+/// Sample of the standard normal distribution using the Box-Muller transform.
+fn normal(rng: &mut Pcg64) -> f64 {
+    let u: f64 = 1. - rng.gen::<f64>();
+    let v: f64 = rng.gen();
+    (-2. * u.ln()).sqrt() * (2. * PI * v).cos()
+}
+
+/// Sample of the Poisson distribution with the given rate.
+fn poisson(rng: &mut Pcg64, rate: f64) -> f64 {
+    if rate > MAX_EXACT_POISSON_RATE {
+        return (rate + rate.sqrt() * normal(rng)).round().max(0.);
+    }
+    let threshold = (-rate).exp();
+    let mut k = 0.;
+    let mut p: f64 = rng.gen();
+    while p > threshold {
+        k += 1.;
+        p *= rng.gen::<f64>();
+    }
+    k
+}
+
+/// Samples an index with the given probabilities.
+fn categorical(rng: &mut Pcg64, probabilities: &[f64]) -> usize {
+    let u: f64 = rng.gen();
+    let mut cum = 0.;
+    for (i, p) in probabilities.iter().enumerate() {
+        cum += p;
+        if u < cum {
+            return i;
+        }
+    }
+    probabilities.len() - 1
+}
+
+/// Shifts and scales values to zero mean and unit standard deviation. Constant values are mapped to zero.
+fn standardize(xs: &[f64]) -> Vec<f64> {
+    let n = xs.len() as f64;
+    let mean = xs.iter().sum::<f64>() / n;
+    let std_dev =
+        (xs.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n).sqrt();
+    xs.iter()
+        .map(|x| {
+            if std_dev > 0. {
+                (x - mean) / std_dev
+            } else {
+                0.
+            }
+        })
+        .collect()
+}
+
+fn dot(xs: &[f64], ys: &[f64]) -> f64 {
+    xs.iter().zip(ys).map(|(x, y)| x * y).sum()
+}
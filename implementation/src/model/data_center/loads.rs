@@ -1,15 +1,16 @@
 //! Definition of load profiles.
 
 use super::{
-    DataCenterModelOutputFailure, DataCenterModelOutputSuccess,
-    IntermediateObjective,
+    CostBreakdown, DataCenterModelOutputFailure, DataCenterModelOutputSuccess,
+    IntermediateObjective, IntermediateResult,
 };
 use crate::config::Config;
 use crate::cost::{Cost, CostFn, SingleCostFn};
-use crate::model::data_center::DataCenterObjective;
 use crate::model::ModelOutput;
-use crate::numerics::convex_optimization::{minimize, WrappedObjective};
-use crate::numerics::ApplicablePrecision;
+use crate::numerics::simplex::{
+    minimize_on_simplices, minimize_separable_on_simplices, HalfSpace, Simplex,
+};
+use crate::numerics::TOLERANCE;
 use crate::utils::{access, mean, unshift_time};
 use crate::value::Value;
 use crate::vec_wrapper::VecWrapper;
@@ -25,7 +26,8 @@ use rayon::iter::{
 use rayon::slice::Iter;
 use rayon::vec::IntoIter;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use std::cmp::{max, min, Ordering};
+use std::cmp::{max, min};
+use std::collections::BTreeMap;
 use std::iter::FromIterator;
 use std::ops::Div;
 use std::ops::Index;
@@ -245,14 +247,19 @@ impl PredictedLoadProfile {
         );
 
         // we only use a randomly chosen subset of all samples to remain efficient
-        self.to_vec()
+        let samples: Vec<Vec<N64>> = self
+            .to_vec()
             .into_iter()
             .map(|zs| {
                 assert!(zs.len() >= sample_size as usize);
                 zs.into_iter()
                     .choose_multiple(&mut rng, sample_size as usize)
             })
-            .map(LoadProfile::new)
+            .collect();
+
+        // each load profile combines the $s$-th sample of all load types
+        (0..sample_size as usize)
+            .map(|s| LoadProfile::new(samples.iter().map(|zs| zs[s]).collect()))
             .collect()
     }
 }
@@ -346,6 +353,7 @@ impl IntoParallelIterator for PredictedLoadProfile {
 pub struct LoadFractions {
     /// Stores for each dimension $k \in \[d\]$ the fractions of loads $i \in \[e\]$ that are handled.
     /// Flat representation where position $k \cdot e + i$ represents the fraction of jobs of type $i$ assigned to servers of type $k$.
+    /// The fractions of each job type sum to $1$.
     pub zs: Vec<N64>,
     /// Number of dimensions.
     pub d: i32,
@@ -355,63 +363,216 @@ pub struct LoadFractions {
 
 impl LoadFractions {
     /// Returns the load fraction for dimension $k$ and job type $i$.
-    pub fn get(&self, k: usize, i: usize, lambda: &LoadProfile) -> N64 {
-        match k.cmp(&(self.d as usize - 1)) {
-            Ordering::Less => self.zs[k * self.e as usize + i],
-            Ordering::Equal => {
-                // computes final dimension based on other dimensions
-                let total_lambda = lambda.total();
-                if total_lambda > 0. {
-                    lambda[i] / total_lambda
-                        - (0..k)
-                            .into_iter()
-                            .map(|j| self.zs[j * self.e as usize + i])
-                            .sum::<N64>()
-                } else {
-                    n64(0.)
-                }
-            }
-            Ordering::Greater => panic!("Invalid dimension."),
-        }
+    pub fn get(&self, k: usize, i: usize) -> N64 {
+        assert!(k < self.d as usize, "Invalid dimension.");
+        self.zs[k * self.e as usize + i]
     }
 
     /// Selects loads for dimension $k$.
     pub fn select_loads(&self, lambda: &LoadProfile, k: usize) -> LoadProfile {
         (0..self.e as usize)
             .into_iter()
-            .map(|i| lambda[i] * self.get(k, i, lambda))
+            .map(|i| lambda[i] * self.get(k, i))
             .collect()
     }
 }
 impl LoadFractions {
     fn new(zs_: &[f64], d: i32, e: i32) -> Self {
         LoadFractions {
-            zs: zs_.iter().map(|&z| n64(z.apply_precision())).collect(),
+            zs: zs_.iter().map(|&z| n64(z)).collect(),
             d,
             e,
         }
     }
 }
 
+/// Objective to be minimized when assigning loads to the dimensions of a configuration.
+pub trait LoadObjective<T>: Send + Sync {
+    /// Cost of configuration $x$ during time slot $t$ when load profile $\lambda$ is assigned according to load fractions $zs$.
+    fn cost(
+        &self,
+        t: i32,
+        x: &Config<T>,
+        lambda: &LoadProfile,
+        zs: &LoadFractions,
+    ) -> IntermediateObjective;
+
+    /// Cost of dimension $k$ of configuration $x$ during time slot $t$ when it is assigned the load profile $loads$,
+    /// or `None` if the objective is not separable. The objective is separable if it is (up to a constant that does not depend on the loads)
+    /// the sum of the costs of all dimensions, in which case it is minimized by water-filling. By default, objectives are not separable.
+    fn dimension_cost(
+        &self,
+        _t: i32,
+        _x: &Config<T>,
+        _k: usize,
+        _loads: &LoadProfile,
+    ) -> Option<IntermediateResult> {
+        None
+    }
+
+    /// Whether load type $i$ may be assigned to dimension $k$. By default, all assignments are allowed.
+    fn allows(&self, _k: usize, _i: usize) -> bool {
+        true
+    }
+
+    /// Capacities of the resources (e.g. memory) of the dimensions of configuration $x$ bounding the loads assigned to them.
+    /// By default, loads are only bounded by the capacities passed to `apply_loads`.
+    fn resource_capacities(&self, _x: &Config<T>) -> Vec<ResourceCapacity> {
+        vec![]
+    }
+
+    /// Attribution of the cost of configuration $x$ during time slot $t$ when load profile $\lambda$ is assigned according to load fractions $zs$.
+    /// Only evaluated for the optimal load fractions. By default, the cost is not attributed.
+    fn breakdown(
+        &self,
+        _t: i32,
+        _x: &Config<T>,
+        _lambda: &LoadProfile,
+        _zs: &LoadFractions,
+    ) -> Result<CostBreakdown, DataCenterModelOutputFailure> {
+        Ok(CostBreakdown::default())
+    }
+
+    /// Power drawn by each component subject to a peak charge (see `PeakCharge`) with configuration $x$ during time slot $t$ when load profile $\lambda$ is assigned according to load fractions $zs$.
+    /// Only evaluated for the optimal load fractions. By default, no component is subject to a peak charge.
+    fn power(
+        &self,
+        _t: i32,
+        _x: &Config<T>,
+        _lambda: &LoadProfile,
+        _zs: &LoadFractions,
+    ) -> Result<BTreeMap<String, f64>, DataCenterModelOutputFailure> {
+        Ok(BTreeMap::new())
+    }
+}
+impl<T, F> LoadObjective<T> for F
+where
+    F: Fn(
+            i32,
+            &Config<T>,
+            &LoadProfile,
+            &LoadFractions,
+        ) -> IntermediateObjective
+        + Send
+        + Sync,
+{
+    fn cost(
+        &self,
+        t: i32,
+        x: &Config<T>,
+        lambda: &LoadProfile,
+        zs: &LoadFractions,
+    ) -> IntermediateObjective {
+        self(t, x, lambda, zs)
+    }
+}
+
+/// Upper bound on the total load of some load types assigned to some dimensions, e.g. the capacity of a network link.
+#[derive(Clone, Debug)]
+pub struct LoadCapacity {
+    /// Dimensions receiving the load.
+    pub dimensions: Vec<usize>,
+    /// Load types whose load is bounded.
+    pub load_types: Vec<usize>,
+    /// Maximum total load.
+    pub capacity: N64,
+}
+
+impl LoadCapacity {
+    /// Constraint on the load fractions of configurations with $e$ job types when load profile $\lambda$ is assigned.
+    fn half_space(&self, e: i32, lambda: &LoadProfile) -> HalfSpace {
+        HalfSpace {
+            coefficients: self
+                .dimensions
+                .iter()
+                .flat_map(|&k| {
+                    self.load_types
+                        .iter()
+                        .map(move |&i| (k * e as usize + i, lambda[i].raw()))
+                })
+                .collect(),
+            bound: self.capacity.raw(),
+        }
+    }
+
+    /// Load exceeding the capacity when load profile $\lambda$ is assigned according to load fractions $zs$ (negative if the capacity is not reached).
+    fn excess(&self, lambda: &LoadProfile, zs: &LoadFractions) -> N64 {
+        self.dimensions
+            .iter()
+            .flat_map(|&k| {
+                self.load_types
+                    .iter()
+                    .map(move |&i| lambda[i] * zs.get(k, i))
+            })
+            .sum::<N64>()
+            - self.capacity
+    }
+}
+
+/// Upper bound on the resource usage of the load assigned to some dimension, e.g. the memory of its active servers.
+#[derive(Clone, Debug)]
+pub struct ResourceCapacity {
+    /// Dimension receiving the load.
+    pub dimension: usize,
+    /// Units of the resource used by a unit of load of each load type.
+    pub demands: Vec<N64>,
+    /// Available units of the resource.
+    pub capacity: N64,
+}
+
+impl ResourceCapacity {
+    /// Constraint on the load fractions of configurations with $e$ job types when load profile $\lambda$ is assigned.
+    fn half_space(&self, e: i32, lambda: &LoadProfile) -> HalfSpace {
+        HalfSpace {
+            coefficients: self
+                .demands
+                .iter()
+                .enumerate()
+                .filter(|(_, &demand)| demand > 0.)
+                .map(|(i, &demand)| {
+                    (
+                        self.dimension * e as usize + i,
+                        (lambda[i] * demand).raw(),
+                    )
+                })
+                .collect(),
+            bound: self.capacity.raw(),
+        }
+    }
+
+    /// Resource usage of all load of load profile $\lambda$, i.e. if it were assigned to the dimension entirely.
+    fn total_usage(&self, lambda: &LoadProfile) -> N64 {
+        self.demands
+            .iter()
+            .enumerate()
+            .map(|(i, &demand)| lambda[i] * demand)
+            .sum()
+    }
+
+    /// Resource usage exceeding the capacity when load profile $\lambda$ is assigned according to load fractions $zs$ (negative if the capacity is not reached).
+    fn excess(&self, lambda: &LoadProfile, zs: &LoadFractions) -> N64 {
+        self.demands
+            .iter()
+            .enumerate()
+            .map(|(i, &demand)| lambda[i] * zs.get(self.dimension, i) * demand)
+            .sum::<N64>()
+            - self.capacity
+    }
+}
+
 /// Optimally applies (certain) loads to a model to obtain a cost function.
 ///
 /// * $d$ - number of dimensions
 /// * $e$ - number of job types
 /// * $objective$ - cost function to minimize w.r.t. load assignments, returning energy cost and revenue loss
+/// * $capacities$ - upper bounds on the loads assigned to dimensions
 /// * $loads$ - vector of (certain) loads for all time slots that should be supported by the returned cost function
 /// * $t_start$ - time offset, i.e. time of first load profile
 pub fn apply_loads_over_time<'a, 'b, T>(
     d: i32,
     e: i32,
-    objective: impl Fn(
-            i32,
-            &Config<T>,
-            &LoadProfile,
-            &LoadFractions,
-        ) -> IntermediateObjective
-        + Send
-        + Sync
-        + 'b,
+    objective: impl LoadObjective<T> + 'b,
+    capacities: Vec<LoadCapacity>,
     loads: Vec<LoadProfile>,
     t_start: i32,
 ) -> CostFn<
@@ -427,7 +588,7 @@ where
         t_start,
         SingleCostFn::certain(move |t, x: Config<T>| {
             let lambda = access(&loads, unshift_time(t, t_start)).unwrap();
-            apply_loads(d, e, &objective, lambda, t, x)
+            apply_loads(d, e, &objective, &capacities, lambda, t, x)
         }),
     )
 }
@@ -437,20 +598,14 @@ where
 /// * $d$ - number of dimensions
 /// * $e$ - number of job types
 /// * $objective$ - cost function to minimize w.r.t. load assignments
+/// * $capacities$ - upper bounds on the loads assigned to dimensions
 /// * $predicted_loads$ - vector of predicted loads for all time slots that should be supported by the returned cost function
 /// * $t_start$ - time offset, i.e. time of first load samples
 pub fn apply_predicted_loads<'a, 'b, T>(
     d: i32,
     e: i32,
-    objective: impl Fn(
-            i32,
-            &Config<T>,
-            &LoadProfile,
-            &LoadFractions,
-        ) -> IntermediateObjective
-        + Send
-        + Sync
-        + 'b,
+    objective: impl LoadObjective<T> + 'b,
+    capacities: Vec<LoadCapacity>,
     predicted_loads: Vec<PredictedLoadProfile>,
     t_start: i32,
 ) -> SingleCostFn<
@@ -468,45 +623,132 @@ where
         predicted_load_profile
             .sample_load_profiles()
             .into_par_iter()
-            .map(|lambda| apply_loads(d, e, &objective, &lambda, t, x.clone()))
+            .map(|lambda| {
+                apply_loads(
+                    d,
+                    e,
+                    &objective,
+                    &capacities,
+                    &lambda,
+                    t,
+                    x.clone(),
+                )
+            })
             .collect()
     })
 }
 
-#[derive(Clone)]
-struct ObjectiveData<T> {
+/// Optimally applies loads to a model to obtain a cost function subject to a chance constraint.
+///
+/// A configuration is feasible only if it can serve the load of at least a $(1 - \epsilon)$-fraction of the predicted samples.
+/// The load of the remaining samples is dropped, i.e. their servers remain idle, and priced by $penalty$ instead of infinite cost.
+///
+/// * $d$ - number of dimensions
+/// * $e$ - number of job types
+/// * $objective$ - cost function to minimize w.r.t. load assignments
+/// * $capacities$ - upper bounds on the loads assigned to dimensions
+/// * $penalty$ - revenue loss of dropping a load profile during time slot $t$
+/// * $epsilon$ - maximum fraction of samples that may be dropped, in $\[0,1)$ (see `DataCenterModel::verify`)
+/// * $predicted_loads$ - vector of predicted loads for all time slots that should be supported by the returned cost function
+/// * $t_start$ - time offset, i.e. time of first load samples
+#[allow(clippy::too_many_arguments)]
+pub fn apply_chance_constrained_loads<'a, 'b, T>(
     d: i32,
     e: i32,
-    lambda: LoadProfile,
-    t: i32,
-    x: Config<T>,
-}
+    objective: impl LoadObjective<T> + 'b,
+    capacities: Vec<LoadCapacity>,
+    penalty: impl Fn(i32, &LoadProfile) -> N64 + Send + Sync + 'b,
+    epsilon: f64,
+    predicted_loads: Vec<PredictedLoadProfile>,
+    t_start: i32,
+) -> SingleCostFn<
+    'b,
+    Config<T>,
+    DataCenterModelOutputSuccess,
+    DataCenterModelOutputFailure,
+>
+where
+    T: Value<'a>,
+{
+    SingleCostFn::predictive(move |t, x: Config<T>| {
+        let predicted_load_profile =
+            access(&predicted_loads, unshift_time(t, t_start)).unwrap();
+        let lambdas = predicted_load_profile.sample_load_profiles();
+        let costs: Vec<_> = lambdas
+            .par_iter()
+            .map(|lambda| {
+                apply_loads(d, e, &objective, &capacities, lambda, t, x.clone())
+            })
+            .collect();
+
+        let dropped =
+            costs.iter().filter(|cost| cost.cost.is_infinite()).count();
+        if dropped as f64 > epsilon * costs.len() as f64 {
+            return costs
+                .iter()
+                .map(|_| {
+                    Cost::new(
+                        n64(f64::INFINITY),
+                        ModelOutput::Failure(
+                            DataCenterModelOutputFailure::DemandExceedingSupply,
+                        ),
+                    )
+                })
+                .collect();
+        }
 
-#[derive(Clone)]
-struct ConstraintData {
-    d: i32,
-    e: i32,
-    i: usize,
-    lambda: LoadProfile,
+        let idle = LoadProfile::new(vec![n64(0.); e as usize]);
+        costs
+            .into_iter()
+            .zip(lambdas.iter())
+            .map(|(cost, lambda)| {
+                if cost.cost.is_finite() {
+                    return cost;
+                }
+                let penalty = penalty(t, lambda);
+                if penalty.is_infinite() {
+                    return cost;
+                }
+                let Cost { cost, output } = apply_loads(
+                    d,
+                    e,
+                    &objective,
+                    &capacities,
+                    &idle,
+                    t,
+                    x.clone(),
+                );
+                let output = match output {
+                    ModelOutput::Success(mut output) => {
+                        output.revenue_loss += penalty.raw();
+                        ModelOutput::Success(output)
+                    }
+                    output => output,
+                };
+                Cost::new(cost + penalty, output)
+            })
+            .collect()
+    })
 }
 
 /// Calculates cost based on a model for an optimal distribution of loads.
 ///
+/// If the objective is separable and the loads are unbounded, the loads are distributed by water-filling, where the marginal costs are equalized by Newton steps.
+/// Several job types are water-filled one at a time while the loads of all other job types are fixed until their assignments no longer change.
+/// Otherwise, the loads are distributed by projected gradient descent subject to the capacities passed and the resource capacities of the objective.
+///
 /// * $d$ - number of dimensions
 /// * $e$ - number of job types
 /// * $objective$ - cost function to minimize w.r.t. load assignments
+/// * $capacities$ - upper bounds on the loads assigned to dimensions
 /// * $\lambda$ - load profile
 /// * $t$ - time slot
 /// * $x$ - configuration
 pub fn apply_loads<'a, T>(
     d: i32,
     e: i32,
-    objective: &impl Fn(
-        i32,
-        &Config<T>,
-        &LoadProfile,
-        &LoadFractions,
-    ) -> IntermediateObjective,
+    objective: &impl LoadObjective<T>,
+    capacities: &[LoadCapacity],
     lambda: &LoadProfile,
     t: i32,
     x: Config<T>,
@@ -516,89 +758,153 @@ where
 {
     assert!(e == lambda.e());
 
-    // we store for each dimension $k \in \[d\]$ the fractions of loads $i \in \[e\]$ that are handled
-    // the final dimensions are completely determined by all preceding dimensions
-    let solver_d = (d * e) as usize - e as usize;
-    let bounds = vec![(0., 1.); solver_d];
-    let solver_objective = WrappedObjective::new(
-        ObjectiveData {
-            d,
-            e,
-            lambda: lambda.clone(),
-            t,
-            x: x.clone(),
-        },
-        |zs_, data| {
-            let zs = LoadFractions::new(zs_, data.d, data.e);
-            let DataCenterObjective {
-                energy_cost,
-                revenue_loss,
-            } = objective(data.t, &data.x, &data.lambda, &zs)
-                .unwrap_or_else(DataCenterObjective::failure);
-            energy_cost + revenue_loss
-        },
-    );
-
-    // assigns each dimension a fraction of each load type
-    // note: the chosen assignment ensures that any server type with $0$ active servers
-    // is also assigned an initial load fraction of $0$
-    let number_of_non_zero_entries =
-        x.iter().filter(|&&j| j > NumCast::from(0).unwrap()).count();
-    let init = if number_of_non_zero_entries == 0 {
-        vec![1. / (solver_d as f64 + e as f64); solver_d]
+    // trailing dimensions (e.g. the states of charge of batteries) do not process any load
+    let x = if x.d() > d {
+        Config::new(x.to_vec()[..d as usize].to_vec())
     } else {
-        let value = 1. / (number_of_non_zero_entries as f64 * e as f64);
-        let vec_x = x.to_vec();
-        let (_, butlast_x) = vec_x.split_last().unwrap();
-        butlast_x
-            .iter()
-            .flat_map(|&j| {
-                if j == NumCast::from(0).unwrap() {
-                    vec![0.; e as usize]
-                } else {
-                    vec![value; e as usize]
-                }
-            })
-            .collect()
+        x
     };
-
-    // ensure that the fractions across all solver dimensions of each load type do not exceed $1$
-    let constraints = (0..e as usize)
-        .map(|i| {
-            WrappedObjective::new(
-                ConstraintData {
-                    d,
-                    e,
-                    i,
-                    lambda: lambda.clone(),
-                },
-                |zs_, data| {
-                    let zs = LoadFractions::new(zs_, data.d, data.e);
-                    -zs.get(d as usize - 1, data.i, &data.lambda)
-                },
-            )
-        })
+    let active: Vec<_> = (0..d as usize)
+        .filter(|&k| x[k] > NumCast::from(0).unwrap())
         .collect();
+    let evaluate = |zs_: &[f64]| {
+        objective.cost(t, &x, lambda, &LoadFractions::new(zs_, d, e))
+    };
 
-    // minimize cost across all possible server to load matchings
-    let (zs_, cost) =
-        minimize(solver_objective, bounds, Some(init), constraints);
+    // each job type with a positive load is distributed across all allowed dimensions with active servers
+    // proportionally to their number of active servers, unless there are no such servers
+    // the fractions of all other dimensions remain zero
+    let mut init = vec![0.; (d * e) as usize];
+    let mut simplices = vec![];
+    for i in (0..e as usize).filter(|&i| lambda[i] > 0.) {
+        let allowed: Vec<_> = active
+            .iter()
+            .copied()
+            .filter(|&k| objective.allows(k, i))
+            .collect();
+        if allowed.is_empty() {
+            match (0..d as usize).rev().find(|&k| objective.allows(k, i)) {
+                Some(k) => init[k * e as usize + i] = 1.,
+                None => {
+                    return Cost::new(
+                        n64(f64::INFINITY),
+                        ModelOutput::Failure(
+                            DataCenterModelOutputFailure::DemandExceedingSupply,
+                        ),
+                    )
+                }
+            }
+            continue;
+        }
+        let total_servers: f64 = allowed
+            .iter()
+            .map(|&k| -> f64 { NumCast::from(x[k]).unwrap() })
+            .sum();
+        for &k in &allowed {
+            let servers: f64 = NumCast::from(x[k]).unwrap();
+            init[k * e as usize + i] = servers / total_servers;
+        }
+        simplices.push(Simplex {
+            coordinates: allowed.iter().map(|&k| k * e as usize + i).collect(),
+            total: 1.,
+        });
+    }
+
+    let resource_capacities = objective.resource_capacities(&x);
+    // objectives are separable if they return the cost of a dimension, which is probed with an idle dimension
+    let zero = LoadProfile::new(vec![n64(0.); e as usize]);
+    let zs_ = if capacities.is_empty()
+        && resource_capacities.is_empty()
+        && !simplices.is_empty()
+        && objective.dimension_cost(t, &x, active[0], &zero).is_some()
+    {
+        let x = &x;
+        let dimension_cost = |k: usize, zs_: &[f64]| {
+            let loads = LoadProfile::new(
+                (0..e as usize)
+                    .map(|i| lambda[i] * n64(zs_[k * e as usize + i]))
+                    .collect(),
+            );
+            match objective.dimension_cost(t, x, k, &loads) {
+                Some(Ok(cost)) => cost.raw(),
+                _ => f64::INFINITY,
+            }
+        };
+        let groups: Vec<_> =
+            (0..(d * e) as usize).map(|j| j / e as usize).collect();
+        minimize_separable_on_simplices(
+            dimension_cost,
+            &groups,
+            &simplices,
+            &init,
+        )
+    } else {
+        let half_spaces: Vec<_> = capacities
+            .iter()
+            .map(|capacity| capacity.half_space(e, lambda))
+            .chain(
+                resource_capacities
+                    .iter()
+                    .map(|capacity| capacity.half_space(e, lambda)),
+            )
+            .collect();
+        let (zs_, _) = minimize_on_simplices(
+            |zs_| evaluate(zs_).map_or(f64::INFINITY, |o| o.cost().raw()),
+            &simplices,
+            &half_spaces,
+            &init,
+        );
+        zs_
+    };
 
     let zs = LoadFractions::new(&zs_, d, e);
-    let output = objective(t, &x, lambda, &zs)
-        .map(
-            |DataCenterObjective {
-                 energy_cost,
-                 revenue_loss,
-             }| {
-                assert!(cost == energy_cost + revenue_loss);
-                ModelOutput::Success(DataCenterModelOutputSuccess::new(
-                    energy_cost.raw(),
-                    revenue_loss.raw(),
+    let tolerance = n64(TOLERANCE) * lambda.total();
+    if capacities
+        .iter()
+        .any(|capacity| capacity.excess(lambda, &zs) > tolerance)
+    {
+        return Cost::new(
+            n64(f64::INFINITY),
+            ModelOutput::Failure(
+                DataCenterModelOutputFailure::LinkCapacityExceeded,
+            ),
+        );
+    }
+    if resource_capacities.iter().any(|capacity| {
+        capacity.excess(lambda, &zs)
+            > n64(TOLERANCE) * capacity.total_usage(lambda)
+    }) {
+        return Cost::new(
+            n64(f64::INFINITY),
+            ModelOutput::Failure(
+                DataCenterModelOutputFailure::ResourceCapacityExceeded,
+            ),
+        );
+    }
+    match evaluate(&zs_).and_then(|cost| {
+        Ok((
+            cost,
+            objective.breakdown(t, &x, lambda, &zs)?,
+            objective.power(t, &x, lambda, &zs)?,
+        ))
+    }) {
+        Ok((objective, breakdown, peak_power)) => Cost::new(
+            objective.cost(),
+            ModelOutput::Success(DataCenterModelOutputSuccess {
+                transfer_cost: objective.transfer_cost.raw(),
+                rental_cost: objective.rental_cost.raw(),
+                emissions: objective.emissions.raw(),
+                breakdown,
+                peak_power,
+                ..DataCenterModelOutputSuccess::new(
+                    objective.energy_cost.raw(),
+                    objective.revenue_loss.raw(),
                     zs_,
-                ))
-            },
-        )
-        .unwrap_or_else(ModelOutput::Failure);
-    Cost::new(cost, output)
+                )
+            }),
+        ),
+        Err(failure) => {
+            Cost::new(n64(f64::INFINITY), ModelOutput::Failure(failure))
+        }
+    }
 }
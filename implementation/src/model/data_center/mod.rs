@@ -1,6 +1,6 @@
 //! Utilities to model the cost of data centers.
 
-use std::{iter::Sum, ops::Add};
+use std::{collections::BTreeMap, iter::Sum, ops::Add};
 
 use super::{ModelOutput, ModelOutputFailure, ModelOutputSuccess};
 use noisy_float::prelude::*;
@@ -8,9 +8,12 @@ use pyo3::prelude::*;
 use serde_derive::{Deserialize, Serialize};
 use thiserror::Error;
 
+pub mod calibration;
+pub mod generators;
 pub mod loads;
 pub mod model;
 pub mod models;
+pub mod simulation;
 
 /// Model result.
 #[pyclass]
@@ -22,32 +25,103 @@ pub struct DataCenterModelOutputSuccess {
     /// Revenue loss of model.
     #[pyo3(get)]
     pub revenue_loss: f64,
+    /// Cost of transferring jobs from their sources to the locations processing them.
+    #[pyo3(get)]
+    pub transfer_cost: f64,
+    /// Cost of renting servers.
+    #[pyo3(get)]
+    pub rental_cost: f64,
+    /// Carbon emissions of model.
+    #[pyo3(get)]
+    pub emissions: f64,
     /// All possible assignments of fractions of loads to server types for each time slot.
     #[pyo3(get)]
     pub assignments: Vec<Vec<Vec<f64>>>,
+    /// Samples of the predicted loads which determined the reported cost for each time slot.
+    #[pyo3(get)]
+    pub samples: Vec<Vec<usize>>,
+    /// Attribution of the energy cost and revenue loss to locations and server types.
+    #[pyo3(get)]
+    pub breakdown: CostBreakdown,
+    /// Peak power drawn by each location with a demand charge (including its cooling overhead).
+    #[pyo3(get)]
+    pub peak_power: BTreeMap<String, f64>,
 }
 impl ModelOutputSuccess for DataCenterModelOutputSuccess {
     fn horizontal_merge(mut self, output: Self) -> Self {
         self.assignments.extend(output.assignments.into_iter());
+        self.samples.extend(output.samples);
         Self {
             energy_cost: self.energy_cost + output.energy_cost,
             revenue_loss: self.revenue_loss + output.revenue_loss,
+            transfer_cost: self.transfer_cost + output.transfer_cost,
+            rental_cost: self.rental_cost + output.rental_cost,
+            emissions: self.emissions + output.emissions,
             assignments: self.assignments,
+            samples: self.samples,
+            breakdown: self.breakdown.merge(output.breakdown, |a, b| a + b),
+            peak_power: merge(self.peak_power, output.peak_power, f64::max),
         }
     }
 
-    /// Computes the mean energy cost and revenue loss.
+    /// Sums the energy cost, revenue loss, transfer cost, rental cost, emissions, attributions, and peak power.
     fn vertical_merge(mut self, output: Self) -> Self {
         assert!(self.assignments.len() == 1);
         assert!(output.assignments.len() == 1);
         self.assignments[0]
             .extend(output.assignments.into_iter().next().unwrap().into_iter());
+        self.samples[0].extend(output.samples.into_iter().next().unwrap());
         Self {
-            energy_cost: (self.energy_cost + output.energy_cost) / 2.,
-            revenue_loss: (self.revenue_loss + output.revenue_loss) / 2.,
+            energy_cost: self.energy_cost + output.energy_cost,
+            revenue_loss: self.revenue_loss + output.revenue_loss,
+            transfer_cost: self.transfer_cost + output.transfer_cost,
+            rental_cost: self.rental_cost + output.rental_cost,
+            emissions: self.emissions + output.emissions,
             assignments: self.assignments,
+            samples: self.samples,
+            breakdown: self.breakdown.merge(output.breakdown, |a, b| a + b),
+            peak_power: merge(self.peak_power, output.peak_power, |a, b| a + b),
         }
     }
+
+    fn vertical_scale(self, factor: f64) -> Self {
+        Self {
+            energy_cost: factor * self.energy_cost,
+            revenue_loss: factor * self.revenue_loss,
+            transfer_cost: factor * self.transfer_cost,
+            rental_cost: factor * self.rental_cost,
+            emissions: factor * self.emissions,
+            breakdown: self.breakdown.scale(factor),
+            peak_power: scale(self.peak_power, factor),
+            ..self
+        }
+    }
+
+    fn select_samples(mut self, samples: &[usize]) -> Self {
+        assert!(self.samples.len() == 1);
+        self.samples[0] = samples.to_vec();
+        self
+    }
+
+    fn emissions(&self) -> f64 {
+        self.emissions
+    }
+
+    fn peak(&self, key: &str) -> f64 {
+        self.peak_power.get(key).copied().unwrap_or(0.)
+    }
+
+    fn report(&self) -> Vec<(String, f64)> {
+        let mut report = vec![
+            ("energy_cost".to_string(), self.energy_cost),
+            ("revenue_loss".to_string(), self.revenue_loss),
+            ("transfer_cost".to_string(), self.transfer_cost),
+            ("rental_cost".to_string(), self.rental_cost),
+            ("emissions".to_string(), self.emissions),
+        ];
+        report.extend(self.breakdown.report());
+        report
+    }
 }
 impl DataCenterModelOutputSuccess {
     pub fn new(
@@ -58,14 +132,165 @@ impl DataCenterModelOutputSuccess {
         Self {
             energy_cost,
             revenue_loss,
+            transfer_cost: 0.,
+            rental_cost: 0.,
+            emissions: 0.,
             assignments: vec![vec![assignment]],
+            samples: vec![vec![0]],
+            breakdown: CostBreakdown::default(),
+            peak_power: BTreeMap::new(),
         }
     }
 }
 
+/// Attribution of the energy cost and revenue loss of processing loads to locations and server types.
+/// The energy cost of a location is attributed to its server types proportionally to their energy consumption.
+/// Costs which are paid when moving between configurations (e.g. for booting servers, batteries, or deferred jobs) are not attributed.
+#[pyclass]
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct CostBreakdown {
+    /// Energy cost of each location.
+    #[pyo3(get)]
+    pub energy_cost_by_location: BTreeMap<String, f64>,
+    /// Energy cost of each server type across all locations.
+    #[pyo3(get)]
+    pub energy_cost_by_server_type: BTreeMap<String, f64>,
+    /// Revenue loss of each location.
+    #[pyo3(get)]
+    pub revenue_loss_by_location: BTreeMap<String, f64>,
+    /// Revenue loss of each server type across all locations.
+    #[pyo3(get)]
+    pub revenue_loss_by_server_type: BTreeMap<String, f64>,
+}
+impl CostBreakdown {
+    /// Attributes energy cost and revenue loss to some server type at some location.
+    pub fn attribute(
+        &mut self,
+        location: &str,
+        server_type: &str,
+        energy_cost: f64,
+        revenue_loss: f64,
+    ) {
+        *self
+            .energy_cost_by_location
+            .entry(location.to_string())
+            .or_default() += energy_cost;
+        *self
+            .energy_cost_by_server_type
+            .entry(server_type.to_string())
+            .or_default() += energy_cost;
+        *self
+            .revenue_loss_by_location
+            .entry(location.to_string())
+            .or_default() += revenue_loss;
+        *self
+            .revenue_loss_by_server_type
+            .entry(server_type.to_string())
+            .or_default() += revenue_loss;
+    }
+
+    /// Combines the attributions of two breakdowns using $f$. Missing attributions are treated as $0$.
+    fn merge(self, other: Self, f: impl Fn(f64, f64) -> f64) -> Self {
+        Self {
+            energy_cost_by_location: merge(
+                self.energy_cost_by_location,
+                other.energy_cost_by_location,
+                &f,
+            ),
+            energy_cost_by_server_type: merge(
+                self.energy_cost_by_server_type,
+                other.energy_cost_by_server_type,
+                &f,
+            ),
+            revenue_loss_by_location: merge(
+                self.revenue_loss_by_location,
+                other.revenue_loss_by_location,
+                &f,
+            ),
+            revenue_loss_by_server_type: merge(
+                self.revenue_loss_by_server_type,
+                other.revenue_loss_by_server_type,
+                &f,
+            ),
+        }
+    }
+
+    /// Scales all attributions by $factor$.
+    fn scale(self, factor: f64) -> Self {
+        Self {
+            energy_cost_by_location: scale(
+                self.energy_cost_by_location,
+                factor,
+            ),
+            energy_cost_by_server_type: scale(
+                self.energy_cost_by_server_type,
+                factor,
+            ),
+            revenue_loss_by_location: scale(
+                self.revenue_loss_by_location,
+                factor,
+            ),
+            revenue_loss_by_server_type: scale(
+                self.revenue_loss_by_server_type,
+                factor,
+            ),
+        }
+    }
+
+    /// Named attributions.
+    fn report(&self) -> Vec<(String, f64)> {
+        [
+            ("energy_cost_by_location", &self.energy_cost_by_location),
+            (
+                "energy_cost_by_server_type",
+                &self.energy_cost_by_server_type,
+            ),
+            ("revenue_loss_by_location", &self.revenue_loss_by_location),
+            (
+                "revenue_loss_by_server_type",
+                &self.revenue_loss_by_server_type,
+            ),
+        ]
+        .iter()
+        .flat_map(|&(name, attributions)| {
+            attributions
+                .iter()
+                .map(move |(key, &value)| (format!("{}.{}", name, key), value))
+        })
+        .collect()
+    }
+}
+
+/// Combines the values of two maps using $f$. Missing values are treated as $0$.
+fn merge(
+    a: BTreeMap<String, f64>,
+    b: BTreeMap<String, f64>,
+    f: impl Fn(f64, f64) -> f64,
+) -> BTreeMap<String, f64> {
+    let keys: Vec<_> = a.keys().chain(b.keys()).cloned().collect();
+    keys.into_iter()
+        .map(|key| {
+            let value =
+                f(*a.get(&key).unwrap_or(&0.), *b.get(&key).unwrap_or(&0.));
+            (key, value)
+        })
+        .collect()
+}
+
+/// Scales the values of a map by $factor$.
+fn scale(map: BTreeMap<String, f64>, factor: f64) -> BTreeMap<String, f64> {
+    map.into_iter()
+        .map(|(key, value)| (key, factor * value))
+        .collect()
+}
+
 /// Model failure.
 #[derive(Clone, Debug, Error, Deserialize, PartialEq, Serialize)]
 pub enum DataCenterModelOutputFailure {
+    #[error("A battery cannot be charged or discharged at the required rate.")]
+    BatteryRateExceeded,
+    #[error("More load is deferred or processed than was deferred before.")]
+    DeferredLoadExceeded,
     #[error("The configuration is unable to support the given load profile.")]
     DemandExceedingSupply,
     #[error("The delay is infinite. The arrival rate is too close to (or larger than) the service rate.")]
@@ -74,10 +299,16 @@ pub enum DataCenterModelOutputFailure {
         number_of_jobs: f64,
         mean_job_duration: f64,
     },
+    #[error("The load routed from a source to a location exceeds the capacity of their link.")]
+    LinkCapacityExceeded,
     #[error("A positive load was assigned to a server type without any active servers.")]
     LoadToInactiveServer,
     #[error("The configuration is outside the decision space.")]
     OutsideDecisionSpace,
+    #[error("The load assigned to a server type exceeds the capacity of one of its resources.")]
+    ResourceCapacityExceeded,
+    #[error("More servers are ready to process load than have been active for their boot delay.")]
+    ServersNotReady,
     #[error("A server cannot handle more than one job during a time slot.")]
     SLOMaxUtilizationExceeded,
 }
@@ -101,19 +332,57 @@ pub type DataCenterModelOutput =
 pub struct DataCenterObjective {
     pub energy_cost: N64,
     pub revenue_loss: N64,
+    /// Cost of transferring jobs from their sources to the locations processing them.
+    pub transfer_cost: N64,
+    /// Cost of renting servers.
+    pub rental_cost: N64,
+    /// Carbon emissions, which are priced as part of the energy cost (if at all).
+    pub emissions: N64,
 }
 impl DataCenterObjective {
     pub fn new(energy_cost: N64, revenue_loss: N64) -> Self {
         Self {
             energy_cost,
             revenue_loss,
+            transfer_cost: n64(0.),
+            rental_cost: n64(0.),
+            emissions: n64(0.),
+        }
+    }
+
+    pub fn with_transfer_cost(self, transfer_cost: N64) -> Self {
+        Self {
+            transfer_cost,
+            ..self
+        }
+    }
+
+    pub fn with_rental_cost(self, rental_cost: N64) -> Self {
+        Self {
+            rental_cost,
+            ..self
         }
     }
 
+    pub fn with_emissions(self, emissions: N64) -> Self {
+        Self { emissions, ..self }
+    }
+
+    /// Total cost, i.e. energy cost, revenue loss, transfer cost, and rental cost.
+    pub fn cost(&self) -> N64 {
+        self.energy_cost
+            + self.revenue_loss
+            + self.transfer_cost
+            + self.rental_cost
+    }
+
     pub fn failure(_failure: DataCenterModelOutputFailure) -> Self {
         Self {
             energy_cost: n64(f64::INFINITY),
             revenue_loss: n64(f64::INFINITY),
+            transfer_cost: n64(f64::INFINITY),
+            rental_cost: n64(f64::INFINITY),
+            emissions: n64(f64::INFINITY),
         }
     }
 }
@@ -122,6 +391,9 @@ impl Default for DataCenterObjective {
         Self {
             energy_cost: n64(0.),
             revenue_loss: n64(0.),
+            transfer_cost: n64(0.),
+            rental_cost: n64(0.),
+            emissions: n64(0.),
         }
     }
 }
@@ -131,6 +403,9 @@ impl Add for DataCenterObjective {
         DataCenterObjective {
             energy_cost: self.energy_cost + rhs.energy_cost,
             revenue_loss: self.revenue_loss + rhs.revenue_loss,
+            transfer_cost: self.transfer_cost + rhs.transfer_cost,
+            rental_cost: self.rental_cost + rhs.rental_cost,
+            emissions: self.emissions + rhs.emissions,
         }
     }
 }
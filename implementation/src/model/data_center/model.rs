@@ -2,34 +2,53 @@
 
 use super::loads::PredictedLoadProfile;
 use super::{
-    DataCenterModelOutputFailure, DataCenterModelOutputSuccess,
+    CostBreakdown, DataCenterModelOutputFailure, DataCenterModelOutputSuccess,
     DataCenterObjective, IntermediateObjective, IntermediateResult,
 };
 use crate::config::Config;
-use crate::cost::{CostFn, FailableCost, FailableCostFn, SingleCostFn};
+use crate::cost::{
+    Cost, CostFn, FailableCost, FailableCostFn, PeakCharge, RiskMeasure,
+    SingleCostFn,
+};
 use crate::model::data_center::loads::{
-    apply_loads_over_time, apply_predicted_loads, LoadFractions, LoadProfile,
+    apply_chance_constrained_loads, apply_loads, apply_loads_over_time,
+    apply_predicted_loads, LoadCapacity, LoadFractions, LoadObjective,
+    LoadProfile, ResourceCapacity,
+};
+use crate::model::data_center::models::cooling::CoolingModel;
+use crate::model::data_center::models::delay::{
+    average_delay, percentile_delay, DelayModel,
 };
-use crate::model::data_center::models::delay::average_delay;
 use crate::model::data_center::models::energy_consumption::EnergyConsumptionModel;
-use crate::model::data_center::models::energy_cost::EnergyCostModel;
+use crate::model::data_center::models::energy_cost::{
+    DemandCharge, EnergyCostModel,
+};
+use crate::model::data_center::models::energy_storage::Battery;
+use crate::model::data_center::models::rental_cost::RentalCostModel;
 use crate::model::data_center::models::revenue_loss::RevenueLossModel;
 use crate::model::data_center::models::switching_cost::SwitchingCostModel;
 use crate::model::data_center::safe_balancing;
-use crate::model::{verify_update, Model, OfflineInput, OnlineInput};
+use crate::model::{
+    verify_update, Model, ModelOutput, OfflineInput, OnlineInput,
+};
+use crate::numerics::TOLERANCE;
 use crate::problem::{
     BaseProblem, Online, SimplifiedSmoothedConvexOptimization,
     SmoothedBalancedLoadOptimization, SmoothedConvexOptimization,
     SmoothedLoadOptimization,
 };
+use crate::result;
+use crate::schedule::Schedule;
+use crate::utils::{access, unshift_time};
 use crate::value::Value;
 use crate::vec_wrapper::VecWrapper;
 use log::info;
 use noisy_float::prelude::*;
 use num::NumCast;
 use pyo3::prelude::*;
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use serde_derive::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::sync::Arc;
 
 /// Key used in homogeneous settings.
@@ -45,12 +64,28 @@ pub struct ServerType {
     /// Maximum allowed utilization. Between $0$ and $1$.
     #[pyo3(get, set)]
     pub max_utilization: f64,
+    /// Number of time slots a server needs to boot after being powered up. Booting servers consume idle power but cannot process any load.
+    /// Default is $0$, i.e. servers are available during the time slot in which they are powered up.
+    #[pyo3(get, set)]
+    pub boot_delay: i32,
+    /// Units of resources other than processing time (e.g. memory or accelerators) of a single server, keyed by resource.
+    /// Resources which are not listed are unavailable. By default, servers have no other resources.
+    #[pyo3(get, set)]
+    pub capacities: HashMap<String, f64>,
+    /// Weights of the utilizations of resources in the utilization determining the energy consumption, keyed by resource.
+    /// The utilization of the processing time is weighted by the remaining weight. Weights are non-negative and sum to at most $1$.
+    /// By default, the energy consumption is determined by the bottleneck, i.e. the most utilized resource.
+    #[pyo3(get, set)]
+    pub utilization_weights: HashMap<String, f64>,
 }
 impl Default for ServerType {
     fn default() -> Self {
         ServerType {
             key: DEFAULT_KEY.to_string(),
             max_utilization: 1.,
+            boot_delay: 0,
+            capacities: HashMap::new(),
+            utilization_weights: HashMap::new(),
         }
     }
 }
@@ -62,14 +97,64 @@ impl ServerType {
             n64(f64::INFINITY)
         }
     }
+
+    /// Utilization determining the energy consumption given the utilization $s$ of the processing time and the utilizations of other resources.
+    pub fn effective_utilization(
+        &self,
+        s: N64,
+        resource_utilizations: &HashMap<String, N64>,
+    ) -> N64 {
+        if self.utilization_weights.is_empty() {
+            resource_utilizations.values().fold(s, |acc, &u| acc.max(u))
+        } else {
+            let weight: f64 = self.utilization_weights.values().sum();
+            self.utilization_weights.iter().fold(
+                n64(1. - weight) * s,
+                |acc, (resource, &w)| {
+                    acc + n64(w)
+                        * resource_utilizations
+                            .get(resource)
+                            .copied()
+                            .unwrap_or(n64(0.))
+                },
+            )
+        }
+    }
+
+    /// Equips servers with resources other than processing time.
+    pub fn with_resources(
+        self,
+        capacities: HashMap<String, f64>,
+        utilization_weights: HashMap<String, f64>,
+    ) -> Self {
+        Self {
+            capacities,
+            utilization_weights,
+            ..self
+        }
+    }
 }
 #[pymethods]
 impl ServerType {
     #[new]
-    fn constructor(key: String, max_utilization: f64) -> Self {
+    #[args(
+        boot_delay = "0",
+        capacities = "HashMap::new()",
+        utilization_weights = "HashMap::new()"
+    )]
+    fn constructor(
+        key: String,
+        max_utilization: f64,
+        boot_delay: i32,
+        capacities: HashMap<String, f64>,
+        utilization_weights: HashMap<String, f64>,
+    ) -> Self {
         ServerType {
             key,
             max_utilization,
+            boot_delay,
+            capacities,
+            utilization_weights,
         }
     }
 }
@@ -84,12 +169,30 @@ pub struct JobType {
     /// Processing time $\eta_{k,i}$ in units time of a job on some server type $k$ (assuming full utilization).
     /// Must be less or equals to the length of a time slot $\delta$ for some server type.
     pub processing_time_on: Arc<dyn Fn(&ServerType) -> f64 + Send + Sync>,
+    /// Number of time slots $s$ by which jobs may be deferred, i.e. jobs arriving during time slot $t$ must be processed by time slot $t + s$.
+    /// Jobs missing their deadline are dropped and priced by the revenue loss model. By default, jobs cannot be deferred.
+    ///
+    /// The deferred load is bounded by the largest load of the offline input. Load which is still deferred after the final time slot misses its deadline.
+    /// The bounds are fixed once the problem is generated, i.e. online updates do not extend them to larger loads.
+    /// Deferred loads are continuous quantities, so problems with deferrable jobs cannot be solved by the integral graph searches.
+    #[pyo3(get, set)]
+    pub slack: Option<i32>,
+    /// Positive cost of deferring a job. Used as the switching cost of the dimensions tracking the deferred load.
+    #[pyo3(get, set)]
+    pub deferral_cost: f64,
+    /// Units of resources other than processing time (e.g. memory or accelerators) held by a job while it is processed, keyed by resource.
+    /// By default, jobs only require processing time.
+    #[pyo3(get, set)]
+    pub demands: HashMap<String, f64>,
 }
 impl Default for JobType {
     fn default() -> Self {
         JobType {
             key: DEFAULT_KEY.to_string(),
             processing_time_on: Arc::new(|_| 1.),
+            slack: None,
+            deferral_cost: 0.,
+            demands: HashMap::new(),
         }
     }
 }
@@ -100,9 +203,26 @@ impl JobType {
             processing_time_on: Arc::new(move |server_type| {
                 processing_times[&server_type.key]
             }),
+            slack: None,
+            deferral_cost: 0.,
+            demands: HashMap::new(),
+        }
+    }
+
+    /// Allows jobs to be deferred by up to $slack$ time slots at cost $deferral_cost$.
+    pub fn with_slack(self, slack: i32, deferral_cost: f64) -> Self {
+        Self {
+            slack: Some(slack),
+            deferral_cost,
+            ..self
         }
     }
 
+    /// Requires jobs to hold some units of resources other than processing time while they are processed.
+    pub fn with_demands(self, demands: HashMap<String, f64>) -> Self {
+        Self { demands, ..self }
+    }
+
     pub fn processing_time_on(&self, server_type: &ServerType) -> N64 {
         n64((self.processing_time_on)(server_type))
     }
@@ -110,7 +230,14 @@ impl JobType {
 #[pymethods]
 impl JobType {
     #[new]
-    fn constructor(key: String, processing_time_on: Py<PyAny>) -> Self {
+    #[args(slack = "None", deferral_cost = "0.", demands = "HashMap::new()")]
+    fn constructor(
+        key: String,
+        processing_time_on: Py<PyAny>,
+        slack: Option<i32>,
+        deferral_cost: f64,
+        demands: HashMap<String, f64>,
+    ) -> Self {
         JobType {
             key,
             processing_time_on: Arc::new(move |server_type| {
@@ -122,6 +249,9 @@ impl JobType {
                         .expect("job type `processing_time_on` method invalid")
                 })
             }),
+            slack,
+            deferral_cost,
+            demands,
         }
     }
 
@@ -135,6 +265,9 @@ impl JobType {
             processing_time_on: Arc::new(move |server_type| {
                 processing_time_on[&server_type.key]
             }),
+            slack: None,
+            deferral_cost: 0.,
+            demands: HashMap::new(),
         }
     }
 
@@ -143,9 +276,52 @@ impl JobType {
         JobType {
             key,
             processing_time_on: Arc::new(move |_server_type| processing_time),
+            slack: None,
+            deferral_cost: 0.,
+            demands: HashMap::new(),
+        }
+    }
+}
+
+/// Placement constraints of a job type, i.e. the server types and locations which may process its jobs.
+#[pyclass]
+#[derive(Clone, Debug, Default)]
+pub struct Affinity {
+    /// Server types which may process jobs. By default, all server types are allowed.
+    #[pyo3(get, set)]
+    pub server_types: Option<Vec<String>>,
+    /// Locations which may process jobs. By default, all locations are allowed.
+    #[pyo3(get, set)]
+    pub locations: Option<Vec<String>>,
+}
+#[pymethods]
+impl Affinity {
+    #[new]
+    #[args(server_types = "None", locations = "None")]
+    fn constructor(
+        server_types: Option<Vec<String>>,
+        locations: Option<Vec<String>>,
+    ) -> Self {
+        Affinity {
+            server_types,
+            locations,
         }
     }
 }
+impl Affinity {
+    /// Whether servers of some type at some location may process jobs.
+    pub fn allows(
+        &self,
+        location: &Location,
+        server_type: &ServerType,
+    ) -> bool {
+        let allowed = |keys: &Option<Vec<String>>, key: &String| {
+            keys.as_ref().is_none_or(|keys| keys.contains(key))
+        };
+        allowed(&self.locations, &location.key)
+            && allowed(&self.server_types, &server_type.key)
+    }
+}
 
 /// Geographical source of jobs.
 #[pyclass]
@@ -156,24 +332,68 @@ pub struct Source {
     pub key: String,
     /// Routing delay $\delta_{t,j,s}$ to location $j$ during time slot $t$.
     pub routing_delay_to: Arc<dyn Fn(i32, &Location) -> f64 + Send + Sync>,
+    /// Capacities of the links to locations (keyed by location), i.e. the maximum number of jobs routed to a location during a time slot.
+    /// By default, links are unlimited.
+    #[pyo3(get, set)]
+    pub link_capacities: HashMap<String, f64>,
+    /// Prices of transferring a job to locations (keyed by location). By default, transfers are free.
+    #[pyo3(get, set)]
+    pub transfer_prices: HashMap<String, f64>,
 }
 impl Source {
     pub fn routing_delay_to(&self, t: i32, location: &Location) -> N64 {
         n64((self.routing_delay_to)(t, location))
     }
+
+    pub fn transfer_price_to(&self, location: &Location) -> N64 {
+        n64(*self.transfer_prices.get(&location.key).unwrap_or(&0.))
+    }
+
+    /// Limits the number of jobs routed to locations.
+    pub fn with_link_capacities(
+        self,
+        link_capacities: HashMap<String, f64>,
+    ) -> Self {
+        Self {
+            link_capacities,
+            ..self
+        }
+    }
+
+    /// Prices the transfer of jobs to locations.
+    pub fn with_transfer_prices(
+        self,
+        transfer_prices: HashMap<String, f64>,
+    ) -> Self {
+        Self {
+            transfer_prices,
+            ..self
+        }
+    }
 }
 impl Default for Source {
     fn default() -> Self {
         Source {
             key: DEFAULT_KEY.to_string(),
             routing_delay_to: Arc::new(|_, _| 0.),
+            link_capacities: HashMap::new(),
+            transfer_prices: HashMap::new(),
         }
     }
 }
 #[pymethods]
 impl Source {
     #[new]
-    fn constructor(key: String, routing_delay_to: Py<PyAny>) -> Self {
+    #[args(
+        link_capacities = "HashMap::new()",
+        transfer_prices = "HashMap::new()"
+    )]
+    fn constructor(
+        key: String,
+        routing_delay_to: Py<PyAny>,
+        link_capacities: HashMap<String, f64>,
+        transfer_prices: HashMap<String, f64>,
+    ) -> Self {
         Source {
             key,
             routing_delay_to: Arc::new(move |t, location| {
@@ -185,6 +405,8 @@ impl Source {
                         .expect("source `routing_delay_to` method invalid")
                 })
             }),
+            link_capacities,
+            transfer_prices,
         }
     }
 
@@ -198,6 +420,8 @@ impl Source {
             routing_delay_to: Arc::new(move |_t, location| {
                 routing_delay_to[&location.key]
             }),
+            link_capacities: HashMap::new(),
+            transfer_prices: HashMap::new(),
         }
     }
 
@@ -206,6 +430,8 @@ impl Source {
         Source {
             key,
             routing_delay_to: Arc::new(move |_t, _location| routing_delay),
+            link_capacities: HashMap::new(),
+            transfer_prices: HashMap::new(),
         }
     }
 }
@@ -220,12 +446,36 @@ pub struct Location {
     /// Maximum number of servers of each type.
     #[pyo3(get, set)]
     pub m: HashMap<String, i32>,
+    /// Charge on the peak power drawn by the servers (including their cooling overhead) during each billing window. By default, there is no demand charge.
+    ///
+    /// Demand charges are peak charges of the hitting costs (see `PeakCharge`), i.e. they are paid by the objective function rather than during individual time slots.
+    /// Loads are dispatched without regard to demand charges, and booting servers and charging batteries do not contribute to the peak power.
+    #[pyo3(get, set)]
+    pub demand_charge: Option<DemandCharge>,
 }
 #[pymethods]
 impl Location {
     #[new]
-    fn constructor(key: String, m: HashMap<String, i32>) -> Self {
-        Location { key, m }
+    #[args(demand_charge = "None")]
+    fn constructor(
+        key: String,
+        m: HashMap<String, i32>,
+        demand_charge: Option<DemandCharge>,
+    ) -> Self {
+        Location {
+            key,
+            m,
+            demand_charge,
+        }
+    }
+}
+impl Location {
+    /// Charges the peak power drawn during each billing window.
+    pub fn with_demand_charge(self, demand_charge: DemandCharge) -> Self {
+        Self {
+            demand_charge: Some(demand_charge),
+            ..self
+        }
     }
 }
 
@@ -260,6 +510,45 @@ pub struct DataCenterModel {
     /// Switching cost model.
     #[pyo3(set)]
     pub switching_cost_model: SwitchingCostModel,
+    /// Risk measure used to reduce the costs of predicted loads. Default is the mean.
+    #[pyo3(set)]
+    pub risk_measure: RiskMeasure,
+    /// Maximum fraction $\epsilon \in \[0,1)$ of predicted samples whose load may be dropped (priced by the revenue loss model).
+    /// By default, a configuration must be able to serve the load of all samples.
+    #[pyo3(get, set)]
+    pub chance_constraint: Option<f64>,
+    /// Batteries of locations (keyed by location). By default, no location has a battery.
+    /// The state of charge of each battery is an additional dimension following the dimensions of all server types.
+    ///
+    /// (Dis)charging is paid as a transition cost which is accounted for by `convex_optimization` and horizon control.
+    /// Energy drawn from a battery offsets energy drawn from the grid at its current cost.
+    #[pyo3(get, set)]
+    pub batteries: HashMap<String, Battery>,
+    /// Cooling model scaling the energy consumption of the servers of each location by its PUE. By default, the cooling overhead is ignored.
+    ///
+    /// As the PUE increases with the utilization, the energy consumption is convex in the load for a fixed configuration but not jointly convex in the configuration and the load.
+    #[pyo3(set)]
+    pub cooling_model: Option<CoolingModel>,
+    /// Delay model of the servers of each server type. By default, each server is modeled as a M/GI/1 Processor Sharing Queue.
+    #[pyo3(set)]
+    pub delay_model: Option<DelayModel>,
+    /// Rental cost model of rented server types. By default, all servers are owned.
+    ///
+    /// The rental cost of active servers is part of the hitting cost, and the remaining cost of the minimum term of on-demand instances is added to the switching cost.
+    #[pyo3(set)]
+    pub rental_cost_model: Option<RentalCostModel>,
+    /// Placement constraints of job types (keyed by job type). By default, jobs may be processed by all server types at all locations.
+    #[pyo3(get, set)]
+    pub affinities: HashMap<String, Affinity>,
+    /// Maximum number of hitting costs (keyed by time slot and configuration) which are cached by generated (simplified) smoothed convex optimization problems. By default, hitting costs are not cached.
+    ///
+    /// Graph searches and lazy budgeting evaluate the hitting costs of the same configurations repeatedly, each of which requires dispatching the loads.
+    #[pyo3(get, set)]
+    pub cache_capacity: Option<usize>,
+    /// Positive step to which configurations are rounded when looking up cached hitting costs (see `CostFn::with_quantized_cache`).
+    /// By default, only identical configurations share cached hitting costs.
+    #[pyo3(get, set)]
+    pub cache_resolution: Option<f64>,
 }
 
 #[pymethods]
@@ -288,11 +577,157 @@ impl DataCenterModel {
             energy_cost_model,
             revenue_loss_model,
             switching_cost_model,
+            risk_measure: RiskMeasure::default(),
+            chance_constraint: None,
+            batteries: HashMap::new(),
+            cooling_model: None,
+            delay_model: None,
+            rental_cost_model: None,
+            affinities: HashMap::new(),
+            cache_capacity: None,
+            cache_resolution: None,
         }
     }
 }
 
 impl DataCenterModel {
+    /// Uses the given risk measure to reduce the costs of predicted loads.
+    pub fn with_risk_measure(self, risk_measure: RiskMeasure) -> Self {
+        Self {
+            risk_measure,
+            ..self
+        }
+    }
+
+    /// Allows the load of at most an $\epsilon$-fraction of predicted samples to be dropped.
+    pub fn with_chance_constraint(self, epsilon: f64) -> Self {
+        Self {
+            chance_constraint: Some(epsilon),
+            ..self
+        }
+    }
+
+    /// Places the given batteries at their locations.
+    pub fn with_batteries(self, batteries: HashMap<String, Battery>) -> Self {
+        Self { batteries, ..self }
+    }
+
+    /// Accounts for the cooling overhead of each location.
+    pub fn with_cooling_model(self, cooling_model: CoolingModel) -> Self {
+        Self {
+            cooling_model: Some(cooling_model),
+            ..self
+        }
+    }
+
+    /// Uses the given delay model for the servers of each server type.
+    pub fn with_delay_model(self, delay_model: DelayModel) -> Self {
+        Self {
+            delay_model: Some(delay_model),
+            ..self
+        }
+    }
+
+    /// Rents the server types of the given rental cost model.
+    pub fn with_rental_cost_model(
+        self,
+        rental_cost_model: RentalCostModel,
+    ) -> Self {
+        Self {
+            rental_cost_model: Some(rental_cost_model),
+            ..self
+        }
+    }
+
+    /// Restricts the server types and locations which may process the jobs of each job type.
+    pub fn with_affinities(
+        self,
+        affinities: HashMap<String, Affinity>,
+    ) -> Self {
+        Self { affinities, ..self }
+    }
+
+    /// Caches up to $capacity$ hitting costs of the generated problems.
+    pub fn with_cache(self, capacity: usize) -> Self {
+        Self {
+            cache_capacity: Some(capacity),
+            ..self
+        }
+    }
+
+    /// Caches up to $capacity$ hitting costs of the generated problems where configurations are rounded to multiples of $resolution$.
+    pub fn with_quantized_cache(
+        self,
+        capacity: usize,
+        resolution: f64,
+    ) -> Self {
+        Self {
+            cache_capacity: Some(capacity),
+            cache_resolution: Some(resolution),
+            ..self
+        }
+    }
+
+    /// Whether the servers of dimension $k_$ may process the load of load type $i_$.
+    pub fn allows(&self, k_: usize, i_: usize) -> bool {
+        let (j, k) = parse(self.server_types.len(), k_);
+        let (_, i) = parse(self.job_types.len(), i_);
+        self.affinities
+            .get(&self.job_types[i].key)
+            .is_none_or(|affinity| {
+                affinity.allows(&self.locations[j], &self.server_types[k])
+            })
+    }
+
+    /// Whether some server type has or some job type demands resources other than processing time.
+    fn has_resources(&self) -> bool {
+        self.server_types
+            .iter()
+            .any(|server_type| !server_type.capacities.is_empty())
+            || self
+                .job_types
+                .iter()
+                .any(|job_type| !job_type.demands.is_empty())
+    }
+
+    /// Units of some resource used by a unit of load of load type $i_$ on servers of some type, i.e. the units held by a job over its processing time.
+    fn resource_demand(
+        &self,
+        server_type: &ServerType,
+        resource: &str,
+        i_: usize,
+    ) -> N64 {
+        let (_, i) = parse(self.job_types.len(), i_);
+        let job_type = &self.job_types[i];
+        job_type.demands.get(resource).map_or(n64(0.), |&demand| {
+            n64(demand) * job_type.processing_time_on(server_type)
+        })
+    }
+
+    /// Utilizations of the resources other than processing time of $x$ servers of some type when they are assigned the load profile $loads$.
+    fn resource_utilizations(
+        &self,
+        server_type: &ServerType,
+        x: N64,
+        loads: &LoadProfile,
+    ) -> HashMap<String, N64> {
+        server_type
+            .capacities
+            .iter()
+            .filter(|(_, &capacity)| capacity > 0.)
+            .map(|(resource, &capacity)| {
+                let usage: N64 = loads
+                    .iter()
+                    .enumerate()
+                    .map(|(i_, &load)| {
+                        load * self.resource_demand(server_type, resource, i_)
+                    })
+                    .sum();
+                (resource.clone(), usage / (x * capacity * self.delta))
+            })
+            .collect()
+    }
+
     /// Calculates cumulative sub jobs of servers of some type, i.e. the number
     /// of sub jobs handled by all servers of this type, when they are assigned
     /// the load profile $loads$.
@@ -315,7 +750,9 @@ impl DataCenterModel {
 
     /// Energy cost. Non-negative convex operating cost of data center $j$
     /// during time slot $t$ with configuration $x$ load profile $\lambda$ and load fractions $zs$.
-    /// Referred to as $e$ in the paper.
+    /// Referred to as $e$ in the paper. Also reports the carbon emissions of data center $j$.
+    ///
+    /// If a cooling model is given, the energy consumption of the servers is scaled by the PUE of data center $j$.
     fn energy_cost<'a, T>(
         &self,
         t: i32,
@@ -323,12 +760,70 @@ impl DataCenterModel {
         x: &Config<T>,
         lambda: &LoadProfile,
         zs: &LoadFractions,
+    ) -> IntermediateObjective
+    where
+        T: Value<'a>,
+    {
+        let location = &self.locations[j];
+        let p = self.cooled_energy_consumption(t, j, x, lambda, zs)?;
+        Ok(DataCenterObjective::new(
+            self.energy_cost_model.cost(t, location, p),
+            n64(0.),
+        )
+        .with_emissions(self.energy_cost_model.emissions(t, location, p)))
+    }
+
+    /// Energy consumption of data center $j$ during time slot $t$ with configuration $x$, load profile $\lambda$, and load fractions $zs$
+    /// including the overhead of cooling (if a cooling model is given).
+    fn cooled_energy_consumption<'a, T>(
+        &self,
+        t: i32,
+        j: usize,
+        x: &Config<T>,
+        lambda: &LoadProfile,
+        zs: &LoadFractions,
     ) -> IntermediateResult
     where
         T: Value<'a>,
     {
-        let p = self.energy_consumption(j, x, lambda, zs)?;
-        Ok(self.energy_cost_model.cost(t, &self.locations[j], p))
+        match &self.cooling_model {
+            Some(cooling_model) => {
+                let s = self.utilization(j, lambda, zs)?;
+                Ok(cooling_model.pue(t, &self.locations[j], s)
+                    * self.energy_consumption(j, x, lambda, zs)?)
+            }
+            None => self.energy_consumption(j, x, lambda, zs),
+        }
+    }
+
+    /// Utilization of all servers of data center $j$ (whether active or not) with load profile $\lambda$ and load fractions $zs$.
+    fn utilization(
+        &self,
+        j: usize,
+        lambda: &LoadProfile,
+        zs: &LoadFractions,
+    ) -> IntermediateResult {
+        let location = &self.locations[j];
+        let (total_load, servers) = (0..self.server_types.len()).try_fold(
+            (n64(0.), n64(0.)),
+            |(total_load, servers), k| {
+                let k_ = encode(self.server_types.len(), j, k);
+                let server_type = &self.server_types[k];
+                Ok((
+                    total_load
+                        + self.total_sub_jobs(
+                            server_type,
+                            &zs.select_loads(lambda, k_),
+                        )?,
+                    servers + n64(location.m[&server_type.key] as f64),
+                ))
+            },
+        )?;
+        Ok(if servers > 0. {
+            total_load / (servers * self.delta)
+        } else {
+            n64(0.)
+        })
     }
 
     /// Energy consumption of data center $j$ with configuration $x_$, load profile
@@ -347,26 +842,45 @@ impl DataCenterModel {
         (0..self.server_types.len())
             .map(|k| {
                 let k_ = encode(self.server_types.len(), j, k);
-                let server_type = &self.server_types[k];
-                let total_load = self.total_sub_jobs(
-                    server_type,
+                self.server_energy_consumption(
+                    k_,
+                    x_[k_],
                     &zs.select_loads(lambda, k_),
-                )?;
-                let x = NumCast::from(x_[k_]).unwrap();
-                safe_balancing(x, total_load, || {
-                    let s = total_load / (x * self.delta);
-                    Ok(server_type.limit_utilization(s, || {
-                        x * self.energy_consumption_model.consumption(
-                            self.delta,
-                            server_type,
-                            s,
-                        )
-                    }))
-                })
+                )
             })
             .sum()
     }
 
+    /// Energy consumption of the $x_$ servers of dimension $k_$ when they are assigned the load profile $loads$.
+    fn server_energy_consumption<'a, T>(
+        &self,
+        k_: usize,
+        x_: T,
+        loads: &LoadProfile,
+    ) -> IntermediateResult
+    where
+        T: Value<'a>,
+    {
+        let (_, k) = parse(self.server_types.len(), k_);
+        let server_type = &self.server_types[k];
+        let total_load = self.total_sub_jobs(server_type, loads)?;
+        let x = NumCast::from(x_).unwrap();
+        safe_balancing(x, total_load, || {
+            let s = total_load / (x * self.delta);
+            let utilization = server_type.effective_utilization(
+                s,
+                &self.resource_utilizations(server_type, x, loads),
+            );
+            Ok(server_type.limit_utilization(s, || {
+                x * self.energy_consumption_model.consumption(
+                    self.delta,
+                    server_type,
+                    utilization,
+                )
+            }))
+        })
+    }
+
     /// Revenue loss. Non-negative convex cost incurred by processing some job
     /// on some server during time slot $t$ when a total of $l$ sub jobs are
     /// processed on the server.
@@ -384,10 +898,22 @@ impl DataCenterModel {
         number_of_jobs: N64,
         mean_job_duration: N64,
     ) -> IntermediateResult {
-        let delay =
-            average_delay(self.delta, number_of_jobs, mean_job_duration)
-                + source.routing_delay_to(t, location)
-                + job_type.processing_time_on(server_type);
+        let queueing_delay = match &self.delay_model {
+            Some(delay_model) => delay_model.delay(
+                self.delta,
+                server_type,
+                number_of_jobs,
+                mean_job_duration,
+            ),
+            None => {
+                average_delay(self.delta, number_of_jobs, mean_job_duration)
+            }
+        };
+        let delay = match self.revenue_loss_model.percentile(job_type) {
+            Some(p) => percentile_delay(p, queueing_delay),
+            None => queueing_delay,
+        } + source.routing_delay_to(t, location)
+            + job_type.processing_time_on(server_type);
         if delay.is_infinite() {
             Err(DataCenterModelOutputFailure::InfiniteDelay {
                 server_type: server_type.key.clone(),
@@ -458,26 +984,143 @@ impl DataCenterModel {
     {
         (0..self.locations.len())
             .map(|j| {
-                Ok(DataCenterObjective::new(
-                    self.energy_cost(t, j, x, lambda, zs)?,
-                    (0..self.server_types.len())
-                        .map(|k| {
-                            let k_ = encode(self.server_types.len(), j, k);
-                            let loads = zs.select_loads(lambda, k_);
-                            self.overall_revenue_loss(
-                                t,
-                                &self.locations[j],
-                                &self.server_types[k],
-                                x[k_],
-                                loads,
-                            )
-                        })
-                        .sum::<IntermediateResult>()?,
-                ))
+                Ok(self.energy_cost(t, j, x, lambda, zs)?
+                    + DataCenterObjective::default()
+                        .with_transfer_cost(self.transfer_cost(j, lambda, zs))
+                        .with_rental_cost(self.rental_cost(j, x))
+                    + DataCenterObjective::new(
+                        n64(0.),
+                        (0..self.server_types.len())
+                            .map(|k| {
+                                let k_ = encode(self.server_types.len(), j, k);
+                                let loads = zs.select_loads(lambda, k_);
+                                self.overall_revenue_loss(
+                                    t,
+                                    &self.locations[j],
+                                    &self.server_types[k],
+                                    x[k_],
+                                    loads,
+                                )
+                            })
+                            .sum::<IntermediateResult>()?,
+                    ))
+            })
+            .sum()
+    }
+
+    /// Cost of transferring the loads assigned to data center $j$ with load profile $\lambda$ and load fractions $zs$ from their sources.
+    fn transfer_cost(
+        &self,
+        j: usize,
+        lambda: &LoadProfile,
+        zs: &LoadFractions,
+    ) -> N64 {
+        let location = &self.locations[j];
+        (0..self.server_types.len())
+            .map(|k| {
+                self.loads_transfer_cost(
+                    location,
+                    &zs.select_loads(
+                        lambda,
+                        encode(self.server_types.len(), j, k),
+                    ),
+                )
+            })
+            .sum()
+    }
+
+    /// Cost of transferring the load profile $loads$ from their sources to some location.
+    fn loads_transfer_cost(
+        &self,
+        location: &Location,
+        loads: &LoadProfile,
+    ) -> N64 {
+        (0..self.sources.len())
+            .map(|s| {
+                self.sources[s].transfer_price_to(location)
+                    * (0..self.job_types.len())
+                        .map(|i| loads[encode(self.job_types.len(), s, i)])
+                        .sum::<N64>()
+            })
+            .sum()
+    }
+
+    /// Cost of renting the servers of data center $j$ with configuration $x$.
+    fn rental_cost<'a, T>(&self, j: usize, x: &Config<T>) -> N64
+    where
+        T: Value<'a>,
+    {
+        let rental_cost_model = match &self.rental_cost_model {
+            Some(rental_cost_model) => rental_cost_model,
+            None => return n64(0.),
+        };
+        (0..self.server_types.len())
+            .filter_map(|k| {
+                let k_ = encode(self.server_types.len(), j, k);
+                rental_cost_model.model(&self.server_types[k]).map(|model| {
+                    model
+                        .cost(&self.locations[j], NumCast::from(x[k_]).unwrap())
+                })
             })
             .sum()
     }
 
+    /// Per server rental cost of each server type during a time slot, assuming that no instances are reserved (see `DataCenterModel::verify_on_demand`).
+    fn on_demand_rates(&self) -> Vec<f64> {
+        self.server_types
+            .iter()
+            .map(|server_type| {
+                self.rental_cost_model
+                    .as_ref()
+                    .and_then(|rental_cost_model| {
+                        rental_cost_model.model(server_type)
+                    })
+                    .map_or(0., |model| model.on_demand_rate().raw())
+            })
+            .collect()
+    }
+
+    /// Switching costs of all server types, including the remaining cost of the minimum term of rented server types.
+    fn server_switching_costs(&self) -> Vec<f64> {
+        self.switching_cost_model
+            .switching_costs(&self.server_types)
+            .into_iter()
+            .zip(&self.server_types)
+            .map(|(switching_cost, server_type)| {
+                switching_cost
+                    + self
+                        .rental_cost_model
+                        .as_ref()
+                        .and_then(|rental_cost_model| {
+                            rental_cost_model.model(server_type)
+                        })
+                        .map_or(0., |model| model.minimum_term_cost().raw())
+            })
+            .collect()
+    }
+
+    /// Capacities of the links between sources and locations, bounding the load of each source assigned to the server types of a location.
+    fn link_capacities(&self) -> Vec<LoadCapacity> {
+        (0..self.sources.len())
+            .flat_map(|s| {
+                (0..self.locations.len()).filter_map(move |j| {
+                    self.sources[s]
+                        .link_capacities
+                        .get(&self.locations[j].key)
+                        .map(|&capacity| LoadCapacity {
+                            dimensions: (0..self.server_types.len())
+                                .map(|k| encode(self.server_types.len(), j, k))
+                                .collect(),
+                            load_types: (0..self.job_types.len())
+                                .map(|i| encode(self.job_types.len(), s, i))
+                                .collect(),
+                            capacity: n64(capacity),
+                        })
+                })
+            })
+            .collect()
+    }
+
     /// Optimally applies (certain) loads to the model of a data center to obtain a cost function.
     /// Referred to as $f$ in the paper.
     ///
@@ -496,14 +1139,43 @@ impl DataCenterModel {
     where
         T: Value<'a>,
     {
-        let model = self.clone();
-        apply_loads_over_time(
+        let objective = self.clone();
+        if !self.deferral_dimensions().is_empty() {
+            let (d, e) = (self.d_(), self.e_());
+            let capacities = self.link_capacities();
+            let model = self.clone();
+            return CostFn::new(
+                t_start,
+                SingleCostFn::certain(move |t, x: Config<T>| {
+                    let lambda =
+                        access(&loads, unshift_time(t, t_start)).unwrap();
+                    apply_loads(
+                        d,
+                        e,
+                        &objective,
+                        &capacities,
+                        &model.processed_loads(lambda, &x),
+                        t,
+                        model.ready_servers(&x),
+                    )
+                }),
+            );
+        }
+
+        let hitting_cost = apply_loads_over_time(
             self.d_(),
             self.e_(),
-            move |t, x, lambda, zs| model.objective(t, x, lambda, zs),
+            objective,
+            self.link_capacities(),
             loads,
             t_start,
-        )
+        );
+        if self.readiness_dimensions().is_empty() {
+            hitting_cost
+        } else {
+            let model = self.clone();
+            hitting_cost.map_config(move |x| model.ready_servers(&x))
+        }
     }
 
     /// Optimally applies loads from a single to the model of a data center to obtain a cost function.
@@ -524,14 +1196,75 @@ impl DataCenterModel {
     where
         T: Value<'a>,
     {
-        let model = self.clone();
-        apply_predicted_loads(
-            self.d_(),
-            self.e_(),
-            move |t, x, lambda, zs| model.objective(t, x, lambda, zs),
-            predicted_loads,
-            t_start,
-        )
+        let objective = self.clone();
+        // chance constraints are not combined with deferrable jobs (see `DataCenterModel::verify`)
+        if !self.deferral_dimensions().is_empty() {
+            let (d, e) = (self.d_(), self.e_());
+            let capacities = self.link_capacities();
+            let model = self.clone();
+            return SingleCostFn::predictive(move |t, x: Config<T>| {
+                access(&predicted_loads, unshift_time(t, t_start))
+                    .unwrap()
+                    .sample_load_profiles()
+                    .into_par_iter()
+                    .map(|lambda| {
+                        apply_loads(
+                            d,
+                            e,
+                            &objective,
+                            &capacities,
+                            &model.processed_loads(&lambda, &x),
+                            t,
+                            model.ready_servers(&x),
+                        )
+                    })
+                    .collect()
+            });
+        }
+
+        let hitting_cost = match self.chance_constraint {
+            None => apply_predicted_loads(
+                self.d_(),
+                self.e_(),
+                objective,
+                self.link_capacities(),
+                predicted_loads,
+                t_start,
+            ),
+            Some(epsilon) => {
+                let model = self.clone();
+                apply_chance_constrained_loads(
+                    self.d_(),
+                    self.e_(),
+                    objective,
+                    self.link_capacities(),
+                    move |t, lambda| model.unserved_revenue_loss(t, lambda),
+                    epsilon,
+                    predicted_loads,
+                    t_start,
+                )
+            }
+        };
+        if self.readiness_dimensions().is_empty() {
+            hitting_cost
+        } else {
+            let model = self.clone();
+            hitting_cost.map_config(move |x| model.ready_servers(&x))
+        }
+    }
+
+    /// Revenue loss of dropping the load profile $\lambda$ during time slot $t$.
+    fn unserved_revenue_loss(&self, t: i32, lambda: &LoadProfile) -> N64 {
+        lambda
+            .iter()
+            .enumerate()
+            .filter(|(_, &load)| load > 0.)
+            .map(|(i_, &load)| {
+                let (_, i) = parse(self.job_types.len(), i_);
+                self.revenue_loss_model.unserved_loss(t, &self.job_types[i])
+                    * load
+            })
+            .sum()
     }
 
     /// Number of dimensions of the underlying problem.
@@ -557,17 +1290,668 @@ impl DataCenterModel {
             })
             .collect()
     }
+
+    /// Switching costs of all dimensions, i.e. of each server type at each location followed by all readiness dimensions.
+    /// The switching cost of a server type with boot delay $b$ is split evenly between powering up and each of the $b$ time slots of booting.
+    fn generate_switching_costs(&self) -> Vec<f64> {
+        let switching_costs = self.server_switching_costs();
+        let switching_cost = |k_| {
+            let (_, k) = parse(self.server_types.len(), k_);
+            switching_costs[k] / (self.boot_delay(k_) + 1) as f64
+        };
+        (0..self.d_() as usize)
+            .map(switching_cost)
+            .chain(
+                self.readiness_dimensions()
+                    .into_iter()
+                    .map(|(k_, _)| switching_cost(k_)),
+            )
+            .collect()
+    }
+
+    /// Boot delay of the servers of dimension $k_$.
+    fn boot_delay(&self, k_: usize) -> usize {
+        let (_, k) = parse(self.server_types.len(), k_);
+        self.server_types[k].boot_delay as usize
+    }
+
+    /// Dimensions tracking the number of servers which have been active for at least $1, \dots, b$ time slots (where $b$ is the boot delay of their server type),
+    /// i.e. the number of servers which may have finished (parts of) their boot.
+    /// They follow the dimensions of all server types. Returns the respective server dimension and number of time slots.
+    fn readiness_dimensions(&self) -> Vec<(usize, usize)> {
+        (0..self.d_() as usize)
+            .flat_map(|k_| (1..=self.boot_delay(k_)).map(move |i| (k_, i)))
+            .collect()
+    }
+
+    /// Index of the dimension holding the number of servers of dimension $k_$ which are ready to process load.
+    fn ready_dimension(&self, k_: usize) -> usize {
+        let b = self.boot_delay(k_);
+        if b == 0 {
+            k_
+        } else {
+            self.d_() as usize
+                + (0..k_).map(|l| self.boot_delay(l)).sum::<usize>()
+                + b
+                - 1
+        }
+    }
+
+    /// Number of servers of each dimension which are ready to process load with configuration $x$.
+    pub fn ready_servers<'a, T>(&self, x: &Config<T>) -> Config<T>
+    where
+        T: Value<'a>,
+    {
+        Config::new(
+            (0..self.d_() as usize)
+                .map(|k_| x[self.ready_dimension(k_)])
+                .collect(),
+        )
+    }
+
+    /// Number of active servers of each dimension which are still booting, i.e. cannot process load yet, when following schedule $xs$
+    /// (of the dimensions of all server types) starting with all servers powered down.
+    /// A model without boot delays hides this shortfall of capacity as servers are assumed to be available immediately.
+    pub fn capacity_shortfall<'a, T>(&self, xs: &Schedule<T>) -> Schedule<T>
+    where
+        T: Value<'a>,
+    {
+        let d = self.d_() as usize;
+        Schedule::new(
+            (1..=xs.t_end())
+                .map(|t| {
+                    let x = xs.get(t).unwrap();
+                    Config::new(
+                        (0..d)
+                            .map(|k_| {
+                                let ready = (t - self.boot_delay(k_) as i32..t)
+                                    .map(|s| match xs.get(s) {
+                                        Some(prev_x) => prev_x[k_],
+                                        None => NumCast::from(0).unwrap(),
+                                    })
+                                    .fold(x[k_], |ready, j| {
+                                        if j < ready {
+                                            j
+                                        } else {
+                                            ready
+                                        }
+                                    });
+                                x[k_] - ready
+                            })
+                            .collect(),
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    /// Indexes of all locations with a battery in the order of their dimensions.
+    fn battery_locations(&self) -> Vec<usize> {
+        (0..self.locations.len())
+            .filter(|&j| self.batteries.contains_key(&self.locations[j].key))
+            .collect()
+    }
+
+    /// Load types (of all sources) whose jobs may be deferred and their slack.
+    fn deferrable_load_types(&self) -> Vec<(usize, usize)> {
+        (0..self.e_() as usize)
+            .filter_map(|i_| {
+                let (_, i) = parse(self.job_types.len(), i_);
+                match self.job_types[i].slack {
+                    Some(slack) if slack > 0 => Some((i_, slack as usize)),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+
+    /// Dimensions tracking the deferred load of each deferrable load type. They follow all other dimensions.
+    /// For a load type with slack $s$, the first $s$ dimensions hold the load which arrived $0, \dots, s - 1$ time slots ago and is deferred beyond the current time slot,
+    /// and the final dimension holds the previously deferred load which is processed during the current time slot.
+    /// Returns the load type, the index of its first dimension, and its slack.
+    fn deferral_dimensions(&self) -> Vec<(usize, usize, usize)> {
+        let mut offset = self.d_() as usize
+            + self.readiness_dimensions().len()
+            + self.battery_locations().len();
+        self.deferrable_load_types()
+            .into_iter()
+            .map(|(i_, slack)| {
+                let dimensions = (i_, offset, slack);
+                offset += slack + 1;
+                dimensions
+            })
+            .collect()
+    }
+
+    /// Charges on the peak power drawn by each location with a demand charge during time slots $1, \dots, t_end$.
+    fn peak_charges(&self, t_end: i32) -> Vec<PeakCharge> {
+        self.locations
+            .iter()
+            .enumerate()
+            .filter_map(|(j, location)| {
+                let demand_charge = location.demand_charge.as_ref()?;
+                Some(PeakCharge::new(
+                    location.key.clone(),
+                    demand_charge.price,
+                    demand_charge.window,
+                    self.peak_bound(j, t_end),
+                ))
+            })
+            .collect()
+    }
+
+    /// Upper bound of the power drawn by data center $j$ during time slots $1, \dots, t_end$.
+    fn peak_bound(&self, j: usize, t_end: i32) -> f64 {
+        let location = &self.locations[j];
+        let energy: N64 = self
+            .server_types
+            .iter()
+            .map(|server_type| {
+                n64(location.m[&server_type.key] as f64)
+                    * self.energy_consumption_model.consumption(
+                        self.delta,
+                        server_type,
+                        n64(server_type.max_utilization),
+                    )
+            })
+            .sum();
+        let pue = match &self.cooling_model {
+            Some(cooling_model) => {
+                let s = self
+                    .server_types
+                    .iter()
+                    .map(|server_type| n64(server_type.max_utilization))
+                    .max()
+                    .unwrap_or_else(|| n64(0.));
+                (1..=t_end.max(1))
+                    .map(|t| cooling_model.pue(t, location, s))
+                    .max()
+                    .unwrap()
+            }
+            None => n64(1.),
+        };
+        (energy * pue / self.delta).raw().ceil()
+    }
+
+    /// Load which is processed with configuration $x$ when load profile $\lambda$ arrives,
+    /// i.e. the arriving load which is not deferred and the processed previously deferred load.
+    /// Deferring more load than arrives has no effect on the arriving load.
+    fn processed_loads<'a, T>(
+        &self,
+        lambda: &LoadProfile,
+        x: &Config<T>,
+    ) -> LoadProfile
+    where
+        T: Value<'a>,
+    {
+        let mut loads = lambda.to_vec();
+        for (i_, offset, slack) in self.deferral_dimensions() {
+            let deferred: N64 = NumCast::from(x[offset]).unwrap();
+            let processed: N64 = NumCast::from(x[offset + slack]).unwrap();
+            loads[i_] = (loads[i_] - deferred).max(n64(0.)) + processed;
+        }
+        LoadProfile::new(loads)
+    }
+
+    /// Energy cost of booting servers and of (dis)charging batteries, and revenue loss of jobs missing their deadline when moving from configuration $prev_x$ to configuration $x$ during time slot $t$.
+    /// Fails if a readiness dimension exceeds the number of servers which have been active long enough, or if more load is deferred than was deferred before.
+    fn transition_cost<'a, T>(
+        &self,
+        t: i32,
+        prev_x: &Config<T>,
+        x: &Config<T>,
+    ) -> Cost<DataCenterModelOutputSuccess, DataCenterModelOutputFailure>
+    where
+        T: Value<'a>,
+    {
+        let objective = self.readiness(prev_x, x).and_then(|_| {
+            Ok(self.boot_cost(t, x)?
+                + self.battery_cost(t, prev_x, x)?
+                + self.deferral_cost(t, prev_x, x)?)
+        });
+        Self::transition_output(objective)
+    }
+
+    /// Revenue loss of the load which is still deferred with configuration $x$ at the end of the time horizon, i.e. during the final time slot $t$.
+    fn final_transition_cost<'a, T>(
+        &self,
+        t: i32,
+        x: &Config<T>,
+    ) -> Cost<DataCenterModelOutputSuccess, DataCenterModelOutputFailure>
+    where
+        T: Value<'a>,
+    {
+        Self::transition_output(self.final_deferral_cost(t, x))
+    }
+
+    /// Cost and output of a transition from one configuration to the next.
+    fn transition_output(
+        objective: IntermediateObjective,
+    ) -> Cost<DataCenterModelOutputSuccess, DataCenterModelOutputFailure> {
+        match objective {
+            Ok(DataCenterObjective {
+                energy_cost,
+                revenue_loss,
+                transfer_cost,
+                rental_cost,
+                emissions,
+            }) => Cost::new(
+                energy_cost + revenue_loss + transfer_cost + rental_cost,
+                ModelOutput::Success(DataCenterModelOutputSuccess {
+                    energy_cost: energy_cost.raw(),
+                    revenue_loss: revenue_loss.raw(),
+                    transfer_cost: transfer_cost.raw(),
+                    rental_cost: rental_cost.raw(),
+                    emissions: emissions.raw(),
+                    assignments: vec![],
+                    samples: vec![],
+                    breakdown: CostBreakdown::default(),
+                    peak_power: BTreeMap::new(),
+                }),
+            ),
+            Err(failure) => {
+                Cost::new(n64(f64::INFINITY), ModelOutput::Failure(failure))
+            }
+        }
+    }
+
+    /// Projects configuration $x$ onto the configurations which may follow configuration $prev_x$,
+    /// i.e. limits the number of ready servers to those which have been active long enough, the states of charge to the (dis)charging rates,
+    /// and the deferred load to the load deferred before.
+    fn project<'a, T>(&self, prev_x: &Config<T>, mut x: Config<T>) -> Config<T>
+    where
+        T: Value<'a>,
+    {
+        let d = self.d_() as usize;
+        let readiness_dimensions = self.readiness_dimensions();
+        for (l, &(k_, i)) in readiness_dimensions.iter().enumerate() {
+            let prev = if i == 1 { k_ } else { d + l - 1 };
+            if x[d + l] > x[k_] {
+                x[d + l] = x[k_];
+            }
+            if x[d + l] > prev_x[prev] {
+                x[d + l] = prev_x[prev];
+            }
+        }
+
+        let d = d + readiness_dimensions.len();
+        for (b, j) in self.battery_locations().into_iter().enumerate() {
+            let soc = self.batteries[&self.locations[j].key].project(
+                self.delta,
+                NumCast::from(prev_x[d + b]).unwrap(),
+                NumCast::from(x[d + b]).unwrap(),
+            );
+            x[d + b] = NumCast::from(soc).unwrap();
+        }
+
+        for (_, offset, slack) in self.deferral_dimensions() {
+            for a in 1..slack {
+                if x[offset + a] > prev_x[offset + a - 1] {
+                    x[offset + a] = prev_x[offset + a - 1];
+                }
+            }
+            let backlog = self.backlog(prev_x, &x, offset, slack);
+            if x[offset + slack] > backlog {
+                x[offset + slack] = backlog;
+            }
+        }
+
+        x
+    }
+
+    /// Ensures that servers which have been active for $i$ time slots during configuration $x$ have been active for $i - 1$ time slots during configuration $prev_x$.
+    fn readiness<'a, T>(
+        &self,
+        prev_x: &Config<T>,
+        x: &Config<T>,
+    ) -> Result<(), DataCenterModelOutputFailure>
+    where
+        T: Value<'a>,
+    {
+        let d = self.d_() as usize;
+        let ready = self.readiness_dimensions().into_iter().enumerate().all(
+            |(l, (k_, i))| {
+                let prev = if i == 1 { k_ } else { d + l - 1 };
+                x[d + l] <= x[k_] && x[d + l] <= prev_x[prev]
+            },
+        );
+        if ready {
+            Ok(())
+        } else {
+            Err(DataCenterModelOutputFailure::ServersNotReady)
+        }
+    }
+
+    /// Cost of the idle power consumed by booting servers with configuration $x$ during time slot $t$, and their rental cost.
+    fn boot_cost<'a, T>(&self, t: i32, x: &Config<T>) -> IntermediateObjective
+    where
+        T: Value<'a>,
+    {
+        let ready = self.ready_servers(x);
+        (0..self.locations.len())
+            .map(|j| {
+                let location = &self.locations[j];
+                let energy = (0..self.server_types.len())
+                    .map(|k| {
+                        let k_ = encode(self.server_types.len(), j, k);
+                        let booting: N64 =
+                            NumCast::from(x[k_] - ready[k_]).unwrap();
+                        booting
+                            * self.energy_consumption_model.consumption(
+                                self.delta,
+                                &self.server_types[k],
+                                n64(0.),
+                            )
+                    })
+                    .sum();
+                Ok(DataCenterObjective::new(
+                    self.energy_cost_model.cost(t, location, energy),
+                    n64(0.),
+                )
+                .with_rental_cost(
+                    self.rental_cost(j, x) - self.rental_cost(j, &ready),
+                )
+                .with_emissions(
+                    self.energy_cost_model.emissions(t, location, energy),
+                ))
+            })
+            .sum()
+    }
+
+    /// Cost of (dis)charging all batteries from the states of charge of $prev_x$ to the states of charge of $x$ during time slot $t$.
+    fn battery_cost<'a, T>(
+        &self,
+        t: i32,
+        prev_x: &Config<T>,
+        x: &Config<T>,
+    ) -> IntermediateObjective
+    where
+        T: Value<'a>,
+    {
+        let d = self.d_() as usize + self.readiness_dimensions().len();
+        self.battery_locations()
+            .into_iter()
+            .enumerate()
+            .map(|(b, j)| {
+                let location = &self.locations[j];
+                let energy = self.batteries[&location.key].grid_energy(
+                    self.delta,
+                    NumCast::from(prev_x[d + b]).unwrap(),
+                    NumCast::from(x[d + b]).unwrap(),
+                )?;
+                Ok(DataCenterObjective::new(
+                    self.energy_cost_model.cost(t, location, energy),
+                    n64(0.),
+                )
+                .with_emissions(
+                    self.energy_cost_model.emissions(t, location, energy),
+                ))
+            })
+            .sum()
+    }
+
+    /// Previously deferred load of the deferrable load type whose dimensions start at $offset$ which is not deferred further with configuration $x$,
+    /// i.e. which must be processed or misses its deadline.
+    fn backlog<'a, T>(
+        &self,
+        prev_x: &Config<T>,
+        x: &Config<T>,
+        offset: usize,
+        slack: usize,
+    ) -> T
+    where
+        T: Value<'a>,
+    {
+        let deferred = (0..slack)
+            .fold(NumCast::from(0).unwrap(), |acc, a| acc + prev_x[offset + a]);
+        (1..slack).fold(deferred, |acc, a| acc - x[offset + a])
+    }
+
+    /// Revenue loss of all deferred jobs which miss their deadline when moving from configuration $prev_x$ to configuration $x$ during time slot $t$.
+    fn deferral_cost<'a, T>(
+        &self,
+        t: i32,
+        prev_x: &Config<T>,
+        x: &Config<T>,
+    ) -> IntermediateObjective
+    where
+        T: Value<'a>,
+    {
+        let tolerance = n64(TOLERANCE);
+        self.deferral_dimensions()
+            .into_iter()
+            .map(|(i_, offset, slack)| {
+                let deferred = (1..slack).all(|a| {
+                    let deferred: N64 = NumCast::from(x[offset + a]).unwrap();
+                    let prev_deferred: N64 =
+                        NumCast::from(prev_x[offset + a - 1]).unwrap();
+                    deferred <= prev_deferred + tolerance
+                });
+                let backlog: N64 =
+                    NumCast::from(self.backlog(prev_x, x, offset, slack))
+                        .unwrap();
+                let processed: N64 = NumCast::from(x[offset + slack]).unwrap();
+                if !deferred || processed > backlog + tolerance {
+                    return Err(
+                        DataCenterModelOutputFailure::DeferredLoadExceeded,
+                    );
+                }
+
+                let missed = backlog - processed;
+                let revenue_loss = if missed > tolerance {
+                    let (_, i) = parse(self.job_types.len(), i_);
+                    self.revenue_loss_model.unserved_loss(t, &self.job_types[i])
+                        * missed
+                } else {
+                    n64(0.)
+                };
+                Ok(DataCenterObjective::new(n64(0.), revenue_loss))
+            })
+            .sum()
+    }
+
+    /// Revenue loss of all jobs which are still deferred with configuration $x$ during the final time slot $t$ and thus miss their deadline.
+    fn final_deferral_cost<'a, T>(
+        &self,
+        t: i32,
+        x: &Config<T>,
+    ) -> IntermediateObjective
+    where
+        T: Value<'a>,
+    {
+        self.deferral_dimensions()
+            .into_iter()
+            .map(|(i_, offset, slack)| {
+                let deferred: N64 = (0..slack)
+                    .map(|a| -> N64 { NumCast::from(x[offset + a]).unwrap() })
+                    .sum();
+                let (_, i) = parse(self.job_types.len(), i_);
+                Ok(DataCenterObjective::new(
+                    n64(0.),
+                    self.revenue_loss_model
+                        .unserved_loss(t, &self.job_types[i])
+                        * deferred,
+                ))
+            })
+            .sum()
+    }
+}
+
+impl<'a, T> LoadObjective<T> for DataCenterModel
+where
+    T: Value<'a>,
+{
+    fn cost(
+        &self,
+        t: i32,
+        x: &Config<T>,
+        lambda: &LoadProfile,
+        zs: &LoadFractions,
+    ) -> IntermediateObjective {
+        self.objective(t, x, lambda, zs)
+    }
+
+    /// Energy cost, transfer cost, and revenue loss of dimension $k_$. Rental costs do not depend on the loads and are omitted.
+    /// The energy cost is only separable if it is linear in the energy consumption of each location, i.e. there are no cooling overheads.
+    fn dimension_cost(
+        &self,
+        t: i32,
+        x: &Config<T>,
+        k_: usize,
+        loads: &LoadProfile,
+    ) -> Option<IntermediateResult> {
+        let separable = matches!(
+            self.energy_cost_model,
+            EnergyCostModel::Linear(_) | EnergyCostModel::CarbonAware(_)
+        ) && self.cooling_model.is_none();
+        if !separable {
+            return None;
+        }
+
+        let (j, k) = parse(self.server_types.len(), k_);
+        let location = &self.locations[j];
+        Some(
+            self.server_energy_consumption(k_, x[k_], loads)
+                .and_then(|p| {
+                    Ok(self.energy_cost_model.cost(t, location, p)
+                        + self.loads_transfer_cost(location, loads)
+                        + self.overall_revenue_loss(
+                            t,
+                            location,
+                            &self.server_types[k],
+                            x[k_],
+                            loads.clone(),
+                        )?)
+                }),
+        )
+    }
+
+    fn allows(&self, k_: usize, i_: usize) -> bool {
+        DataCenterModel::allows(self, k_, i_)
+    }
+
+    /// Resources of the active servers of each dimension. Resources which are demanded by some job type but unavailable on a server type have no capacity.
+    fn resource_capacities(&self, x: &Config<T>) -> Vec<ResourceCapacity> {
+        let resources: BTreeSet<&String> = self
+            .server_types
+            .iter()
+            .flat_map(|server_type| server_type.capacities.keys())
+            .chain(
+                self.job_types
+                    .iter()
+                    .flat_map(|job_type| job_type.demands.keys()),
+            )
+            .collect();
+        (0..self.d_() as usize)
+            .filter(|&k_| x[k_] > NumCast::from(0).unwrap())
+            .flat_map(|k_| {
+                let (_, k) = parse(self.server_types.len(), k_);
+                let server_type = &self.server_types[k];
+                let servers: N64 = NumCast::from(x[k_]).unwrap();
+                resources
+                    .iter()
+                    .filter_map(|&resource| {
+                        let demands: Vec<_> = (0..self.e_() as usize)
+                            .map(|i_| {
+                                self.resource_demand(server_type, resource, i_)
+                            })
+                            .collect();
+                        if demands.iter().all(|&demand| demand == 0.) {
+                            return None;
+                        }
+                        let capacity = server_type
+                            .capacities
+                            .get(resource)
+                            .copied()
+                            .unwrap_or(0.);
+                        Some(ResourceCapacity {
+                            dimension: k_,
+                            demands,
+                            capacity: servers * capacity * self.delta,
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Attributes the energy cost of each location to its server types proportionally to their energy consumption.
+    fn breakdown(
+        &self,
+        t: i32,
+        x: &Config<T>,
+        lambda: &LoadProfile,
+        zs: &LoadFractions,
+    ) -> Result<CostBreakdown, DataCenterModelOutputFailure> {
+        let mut breakdown = CostBreakdown::default();
+        for (j, location) in self.locations.iter().enumerate() {
+            let energy_cost =
+                self.energy_cost(t, j, x, lambda, zs)?.energy_cost;
+            let consumptions = (0..self.server_types.len())
+                .map(|k| {
+                    let k_ = encode(self.server_types.len(), j, k);
+                    self.server_energy_consumption(
+                        k_,
+                        x[k_],
+                        &zs.select_loads(lambda, k_),
+                    )
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let total_consumption: N64 = consumptions.iter().copied().sum();
+            for (k, server_type) in self.server_types.iter().enumerate() {
+                let k_ = encode(self.server_types.len(), j, k);
+                let share = if total_consumption > 0. {
+                    consumptions[k] / total_consumption
+                } else {
+                    n64(0.)
+                };
+                let revenue_loss = self.overall_revenue_loss(
+                    t,
+                    location,
+                    server_type,
+                    x[k_],
+                    zs.select_loads(lambda, k_),
+                )?;
+                breakdown.attribute(
+                    &location.key,
+                    &server_type.key,
+                    (share * energy_cost).raw(),
+                    revenue_loss.raw(),
+                );
+            }
+        }
+        Ok(breakdown)
+    }
+
+    /// Power drawn by each location with a demand charge (including its cooling overhead).
+    fn power(
+        &self,
+        t: i32,
+        x: &Config<T>,
+        lambda: &LoadProfile,
+        zs: &LoadFractions,
+    ) -> Result<BTreeMap<String, f64>, DataCenterModelOutputFailure> {
+        self.locations
+            .iter()
+            .enumerate()
+            .filter(|(_, location)| location.demand_charge.is_some())
+            .map(|(j, location)| {
+                let p = self.cooled_energy_consumption(t, j, x, lambda, zs)?;
+                Ok((location.key.clone(), (p / self.delta).raw()))
+            })
+            .collect()
+    }
 }
 
 /// Parses index of underlying representation, returns outer and inner indexes.
-fn parse(inner_len: usize, i: usize) -> (usize, usize) {
+pub fn parse(inner_len: usize, i: usize) -> (usize, usize) {
     let outer = i / inner_len;
     let inner = i - outer * inner_len;
     (outer, inner)
 }
 
 /// Encodes index of underlying representation from the outer and inner indexes.
-fn encode(inner_len: usize, outer: usize, inner: usize) -> usize {
+pub fn encode(inner_len: usize, outer: usize, inner: usize) -> usize {
     outer * inner_len + inner
 }
 
@@ -646,6 +2030,7 @@ where
         >,
         DataCenterOnlineInput { loads }: DataCenterOnlineInput,
     ) {
+        // the bounds (in particular, those of the deferred loads) remain as derived from the initial input
         o.p.inc_t_end();
         let t = o.p.t_end();
         let span = loads.len() as i32;
@@ -654,6 +2039,10 @@ where
             .add(t, self.apply_predicted_loads(loads, t));
         verify_update(o, span);
     }
+
+    fn verify(&self) -> result::Result<()> {
+        DataCenterModel::verify(self)
+    }
 }
 
 impl<'a, T>
@@ -682,13 +2071,94 @@ where
         DataCenterModelOutputSuccess,
         DataCenterModelOutputFailure,
     > {
-        let d = self.d_();
+        let readiness_dimensions = self.readiness_dimensions();
+        let battery_locations = self.battery_locations();
+        let deferral_dimensions = self.deferral_dimensions();
+        let deferral_bounds: Vec<T> = deferral_dimensions
+            .iter()
+            .flat_map(|&(i_, _, slack)| {
+                let max_load = loads
+                    .iter()
+                    .map(|lambda| lambda[i_])
+                    .max()
+                    .unwrap_or_else(|| n64(0.))
+                    .raw();
+                let bound: T = NumCast::from(max_load.ceil()).unwrap();
+                let processed_bound: T =
+                    NumCast::from((slack as f64 * max_load).ceil()).unwrap();
+                vec![bound; slack].into_iter().chain(vec![processed_bound])
+            })
+            .collect();
+        let d = self.d_()
+            + readiness_dimensions.len() as i32
+            + battery_locations.len() as i32
+            + deferral_bounds.len() as i32;
         let t_end = loads.len() as i32;
-        let bounds = self.generate_bounds();
+        let batteries: Vec<_> = battery_locations
+            .into_iter()
+            .map(|j| &self.batteries[&self.locations[j].key])
+            .collect();
+        let server_bounds = self.generate_bounds();
+        let bounds = server_bounds
+            .iter()
+            .copied()
+            .chain(
+                readiness_dimensions
+                    .iter()
+                    .map(|&(k_, _)| server_bounds[k_]),
+            )
+            .chain(batteries.iter().map(|battery| battery.bound()))
+            .chain(deferral_bounds.iter().copied())
+            .collect();
         let switching_cost = self
-            .switching_cost_model
-            .switching_costs(&self.server_types);
-        let hitting_cost = self.apply_loads_over_time(loads, 1);
+            .generate_switching_costs()
+            .into_iter()
+            .chain(batteries.iter().map(|battery| battery.degradation_cost))
+            .chain(deferral_dimensions.iter().flat_map(|&(i_, _, slack)| {
+                let (_, i) = parse(self.job_types.len(), i_);
+                vec![self.job_types[i].deferral_cost; slack + 1]
+            }))
+            .collect();
+        // states of charge and deferred loads are continuous quantities
+        let continuous_dimensions =
+            (self.d_() as usize + readiness_dimensions.len()..d as usize)
+                .collect();
+        let hitting_cost = self
+            .apply_loads_over_time(loads, 1)
+            .with_risk_measure(self.risk_measure)
+            .with_peak_charges(self.peak_charges(t_end))
+            .with_continuous_dimensions(continuous_dimensions);
+        let hitting_cost = match (self.cache_capacity, self.cache_resolution) {
+            (Some(capacity), Some(resolution)) => {
+                hitting_cost.with_quantized_cache(capacity, resolution)
+            }
+            (Some(capacity), None) => hitting_cost.with_cache(capacity),
+            (None, _) => hitting_cost,
+        };
+        let hitting_cost = if readiness_dimensions.is_empty()
+            && batteries.is_empty()
+            && deferral_dimensions.is_empty()
+        {
+            hitting_cost
+        } else {
+            let model = self.clone();
+            let projection_model = self.clone();
+            let hitting_cost = hitting_cost
+                .with_transition(move |t, prev_x, x| {
+                    model.transition_cost(t, &prev_x, &x)
+                })
+                .with_projection(move |_, prev_x, x| {
+                    projection_model.project(&prev_x, x)
+                });
+            if deferral_dimensions.is_empty() {
+                hitting_cost
+            } else {
+                let model = self.clone();
+                hitting_cost.with_final_transition(move |t, x| {
+                    model.final_transition_cost(t, &x)
+                })
+            }
+        };
         SimplifiedSmoothedConvexOptimization {
             d,
             t_end,
@@ -710,6 +2180,7 @@ where
         >,
         DataCenterOnlineInput { loads }: DataCenterOnlineInput,
     ) {
+        // the bounds (in particular, those of the deferred loads) remain as derived from the initial input
         o.p.inc_t_end();
         let t = o.p.t_end();
         let span = loads.len() as i32;
@@ -718,6 +2189,10 @@ where
             .add(t, self.apply_predicted_loads(loads, t));
         verify_update(o, span);
     }
+
+    fn verify(&self) -> result::Result<()> {
+        DataCenterModel::verify(self)
+    }
 }
 
 impl<'a, T>
@@ -734,6 +2209,7 @@ where
 {
     /// Notes:
     /// * Only allows for a single location, source, and job type.
+    /// * Does not support boot delays, deferrable jobs, link capacities and transfer prices, demand charges, cooling overheads, reserved instances, affinities, resources other than processing time, batteries, chance constraints, or queueing delays.
     fn to(
         &self,
         DataCenterOfflineInput { loads }: DataCenterOfflineInput,
@@ -742,20 +2218,31 @@ where
         let location = &self.locations[0];
         assert!(self.sources.len() == 1);
         assert!(self.job_types.len() == 1);
+        assert!(self.readiness_dimensions().is_empty());
+        assert!(self.deferrable_load_types().is_empty());
+        assert!(self.sources[0].link_capacities.is_empty());
+        assert!(self.sources[0].transfer_prices.is_empty());
+        assert!(location.demand_charge.is_none());
+        assert!(self.cooling_model.is_none());
+        assert!(self.affinities.is_empty());
+        assert!(!self.has_resources());
+        assert!(self.batteries.is_empty());
+        assert!(self.chance_constraint.is_none());
+        assert!(self.delay_model.is_none());
 
         let d = self.d_();
         let t_end = loads.len() as i32;
         let bounds = self.generate_bounds();
-        let switching_cost = self
-            .switching_cost_model
-            .switching_costs(&self.server_types);
+        let switching_cost = self.server_switching_costs();
+        let on_demand_rates = self.on_demand_rates();
         let hitting_cost: Vec<
             FailableCostFn<'a, f64, DataCenterModelOutputFailure>,
         > = self
             .server_types
             .clone()
             .into_iter()
-            .map(move |server_type| {
+            .zip(on_demand_rates)
+            .map(move |(server_type, on_demand_rate)| {
                 let location = location.clone();
                 let energy_consumption_model =
                     self.energy_consumption_model.clone();
@@ -773,7 +2260,8 @@ where
                             )
                         });
                         FailableCost::raw(
-                            energy_cost_model.cost(t, &location, p),
+                            energy_cost_model.cost(t, &location, p)
+                                + n64(on_demand_rate),
                         )
                     }),
                 )
@@ -813,6 +2301,11 @@ where
             .push(NumCast::from(predicted_load_profile[0][0]).unwrap());
         verify_update(o, span);
     }
+
+    fn verify(&self) -> result::Result<()> {
+        DataCenterModel::verify(self)?;
+        self.verify_on_demand()
+    }
 }
 
 impl<'a, T>
@@ -830,6 +2323,7 @@ where
     /// Notes:
     /// * Only allows for a single location, source, and job type.
     /// * Assumes full utilization and averages the energy cost over the time horizon.
+    /// * Does not support boot delays, deferrable jobs, link capacities and transfer prices, demand charges, cooling overheads, reserved instances, affinities, resources other than processing time, batteries, chance constraints, or queueing delays.
     fn to(
         &self,
         DataCenterOfflineInput { loads }: DataCenterOfflineInput,
@@ -838,32 +2332,46 @@ where
         let location = &self.locations[0];
         assert!(self.sources.len() == 1);
         assert!(self.job_types.len() == 1);
+        assert!(self.readiness_dimensions().is_empty());
+        assert!(self.deferrable_load_types().is_empty());
+        assert!(self.sources[0].link_capacities.is_empty());
+        assert!(self.sources[0].transfer_prices.is_empty());
+        assert!(location.demand_charge.is_none());
+        assert!(self.cooling_model.is_none());
+        assert!(self.affinities.is_empty());
+        assert!(!self.has_resources());
+        assert!(self.batteries.is_empty());
+        assert!(self.chance_constraint.is_none());
+        assert!(self.delay_model.is_none());
 
         let d = self.d_();
         let t_end = loads.len() as i32;
         let bounds = self.generate_bounds();
-        let switching_cost = self
-            .switching_cost_model
-            .switching_costs(&self.server_types);
+        let switching_cost = self.server_switching_costs();
+        let on_demand_rates = self.on_demand_rates();
         let hitting_cost = self
             .server_types
             .iter()
-            .map(move |server_type| {
+            .zip(on_demand_rates)
+            .map(move |(server_type, on_demand_rate)| {
                 let p = self.energy_consumption_model.consumption(
                     self.delta,
                     server_type,
                     n64(1.),
                 );
-                if t_end > 0 {
-                    (0..t_end)
-                        .map(|t| {
-                            self.energy_cost_model.cost(t, location, p).raw()
-                        })
-                        .sum::<f64>()
-                        / t_end as f64
-                } else {
-                    self.energy_cost_model.cost(1, location, p).raw()
-                }
+                on_demand_rate
+                    + if t_end > 0 {
+                        (0..t_end)
+                            .map(|t| {
+                                self.energy_cost_model
+                                    .cost(t, location, p)
+                                    .raw()
+                            })
+                            .sum::<f64>()
+                            / t_end as f64
+                    } else {
+                        self.energy_cost_model.cost(1, location, p).raw()
+                    }
             })
             .collect();
         let load = loads
@@ -900,4 +2408,9 @@ where
             .push(NumCast::from(predicted_load_profile[0][0]).unwrap());
         verify_update(o, span);
     }
+
+    fn verify(&self) -> result::Result<()> {
+        DataCenterModel::verify(self)?;
+        self.verify_on_demand()
+    }
 }
@@ -0,0 +1,86 @@
+//! Cooling model, i.e. the energy overhead of the facility.
+
+use crate::model::data_center::model::Location;
+use crate::utils::pos;
+use noisy_float::prelude::*;
+use pyo3::prelude::*;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Cooling model. Parameters are provided separately for each location.
+#[derive(Clone, FromPyObject)]
+pub enum CoolingModel {
+    /// Power usage effectiveness (PUE) increasing linearly with the utilization of a location and the outside temperature above some reference temperature.
+    TemperatureDependent(HashMap<String, TemperatureDependentCoolingModel>),
+}
+
+#[pyclass]
+#[derive(Clone)]
+pub struct TemperatureDependentCoolingModel {
+    /// PUE of an idle location if the outside temperature does not exceed the reference temperature. $\geq 1$.
+    #[pyo3(get, set)]
+    pub pue_min: f64,
+    /// Increase of the PUE when the location is fully utilized. $\geq 0$.
+    #[pyo3(get, set)]
+    pub load_factor: f64,
+    /// Increase of the PUE per degree of the outside temperature above the reference temperature. $\geq 0$.
+    #[pyo3(get, set)]
+    pub temperature_factor: f64,
+    /// Outside temperature up to which the location is cooled without any additional overhead.
+    #[pyo3(get, set)]
+    pub reference_temperature: f64,
+    /// Average outside temperature during time slot $t$.
+    pub outside_temperature: Arc<dyn Fn(i32) -> f64 + Send + Sync>,
+}
+impl TemperatureDependentCoolingModel {
+    fn outside_temperature(&self, t: i32) -> N64 {
+        n64((self.outside_temperature)(t))
+    }
+}
+#[pymethods]
+impl TemperatureDependentCoolingModel {
+    #[new]
+    fn constructor(
+        pue_min: f64,
+        load_factor: f64,
+        temperature_factor: f64,
+        reference_temperature: f64,
+        outside_temperature: Py<PyAny>,
+    ) -> Self {
+        TemperatureDependentCoolingModel {
+            pue_min,
+            load_factor,
+            temperature_factor,
+            reference_temperature,
+            outside_temperature: Arc::new(move |t| {
+                Python::with_gil(|py| {
+                    outside_temperature
+                        .call1(py, (t,))
+                        .expect("temperature dependent cooling model `outside_temperature` method invalid")
+                        .extract(py)
+                        .expect("temperature dependent cooling model `outside_temperature` method invalid")
+                })
+            }),
+        }
+    }
+}
+
+impl CoolingModel {
+    /// PUE at some location during time slot $t$ with utilization $s \in \[0,1\]$ of all servers.
+    /// The location must have cooling parameters (see `DataCenterModel::verify`).
+    ///
+    /// As the PUE is non-decreasing and affine in $s$, the energy consumption scaled by the PUE remains convex in the load for a fixed configuration $x$.
+    /// It is not jointly convex in $x$ and the load, as the product of the PUE and the consumption of active servers contains a bilinear term in $x$ and the load.
+    pub fn pue(&self, t: i32, location: &Location, s: N64) -> N64 {
+        match self {
+            CoolingModel::TemperatureDependent(models) => {
+                let model = &models[&location.key];
+                n64(model.pue_min)
+                    + n64(model.load_factor) * s
+                    + n64(model.temperature_factor)
+                        * pos(model.outside_temperature(t)
+                            - n64(model.reference_temperature))
+            }
+        }
+    }
+}
@@ -1,6 +1,9 @@
 //! Delay model.
 
+use crate::model::data_center::model::ServerType;
 use noisy_float::prelude::*;
+use pyo3::prelude::*;
+use std::collections::HashMap;
 
 /// Average delay of a job processed on a server handling a total of
 /// $number_of_jobs$ jobs with average duration $mean_job_duration$ using
@@ -24,3 +27,105 @@ pub fn average_delay(
         n64(0.)
     }
 }
+
+/// Delay model of the servers of a server type. Parameters are provided separately for each server type.
+/// By default, each server is modeled as a M/GI/1 Processor Sharing Queue (see `average_delay`).
+#[derive(Clone, FromPyObject)]
+pub enum DelayModel {
+    /// Active servers are pooled into M/M/c queues whose delay is given by the Erlang-C formula.
+    ErlangC(HashMap<String, ErlangCDelayModel>),
+    /// Each server is a M/G/1 First-Come-First-Served Queue whose delay is given by the Pollaczek-Khinchine formula.
+    FirstComeFirstServed(HashMap<String, FirstComeFirstServedDelayModel>),
+}
+
+#[pyclass]
+#[derive(Clone)]
+pub struct ErlangCDelayModel {
+    /// Number of servers sharing a queue. $c \geq 1$.
+    #[pyo3(get, set)]
+    pub c: i32,
+}
+#[pymethods]
+impl ErlangCDelayModel {
+    #[new]
+    fn constructor(c: i32) -> Self {
+        ErlangCDelayModel { c }
+    }
+}
+
+#[pyclass]
+#[derive(Clone)]
+pub struct FirstComeFirstServedDelayModel {
+    /// Squared coefficient of variation of the job durations, i.e. their variance divided by their squared mean. $\geq 0$.
+    /// For $1$, the delay is the delay of a M/M/1 queue.
+    #[pyo3(get, set)]
+    pub scv: f64,
+}
+#[pymethods]
+impl FirstComeFirstServedDelayModel {
+    #[new]
+    fn constructor(scv: f64) -> Self {
+        FirstComeFirstServedDelayModel { scv }
+    }
+}
+
+impl DelayModel {
+    /// Average delay of a job processed on a server of some type handling a total of
+    /// $number_of_jobs$ jobs with average duration $mean_job_duration$.
+    /// $\delta$ is the duration of a time slot.
+    pub fn delay(
+        &self,
+        delta: f64,
+        server_type: &ServerType,
+        number_of_jobs: N64,
+        mean_job_duration: N64,
+    ) -> N64 {
+        if number_of_jobs <= n64(0.) || mean_job_duration <= n64(0.) {
+            return n64(0.);
+        }
+
+        let service_rate = n64(1.) / mean_job_duration;
+        let arrival_rate = number_of_jobs / delta;
+        match self {
+            DelayModel::ErlangC(models) => {
+                let c = models[&server_type.key].c;
+                let service_capacity = n64(c as f64) * service_rate;
+                let pooled_arrival_rate = n64(c as f64) * arrival_rate;
+                if pooled_arrival_rate < service_capacity {
+                    erlang_c(c, pooled_arrival_rate / service_rate)
+                        / (service_capacity - pooled_arrival_rate)
+                        + mean_job_duration
+                } else {
+                    n64(f64::INFINITY)
+                }
+            }
+            DelayModel::FirstComeFirstServed(models) => {
+                let scv = n64(models[&server_type.key].scv);
+                let utilization = arrival_rate / service_rate;
+                if utilization < 1. {
+                    arrival_rate * (n64(1.) + scv) * mean_job_duration.powi(2)
+                        / (n64(2.) * (n64(1.) - utilization))
+                        + mean_job_duration
+                } else {
+                    n64(f64::INFINITY)
+                }
+            }
+        }
+    }
+}
+
+/// Probability that a job has to wait in a M/M/c queue with offered load $a < c$.
+fn erlang_c(c: i32, a: N64) -> N64 {
+    // iteratively computes the Erlang-B formula which is numerically stable
+    let erlang_b =
+        (1..=c).fold(n64(1.), |b, k| a * b / (n64(k as f64) + a * b));
+    let utilization = a / n64(c as f64);
+    erlang_b / (n64(1.) - utilization * (n64(1.) - erlang_b))
+}
+
+/// Approximates the $p$-th percentile of the delay with average $delay$, assuming that delays are exponentially distributed.
+/// This is exact for the M/M/1 queue and preserves the convexity of the average delay.
+/// Requires $p \in \[0,1)$ (see `DataCenterModel::verify`).
+pub fn percentile_delay(p: f64, delay: N64) -> N64 {
+    -n64(1. - p).ln() * delay
+}
@@ -77,6 +77,8 @@ pub enum EnergyCostModel {
     /// Energy cost model using (maximum) quotas.
     /// Maximum profit across all energy sources must not exceed overall energy cost.
     Quotas(HashMap<String, QuotasEnergyCostModel>),
+    /// Linear energy cost where carbon emissions are priced in addition to the energy itself.
+    CarbonAware(HashMap<String, CarbonAwareEnergyCostModel>),
 }
 
 #[pyclass]
@@ -133,6 +135,69 @@ impl QuotasEnergyCostModel {
     }
 }
 
+#[pyclass]
+#[derive(Clone)]
+pub struct CarbonAwareEnergyCostModel {
+    /// Average cost of a unit of energy during time slot $t$.
+    pub cost: Arc<dyn Fn(i32) -> f64 + Send + Sync>,
+    /// Average carbon intensity of the grid, i.e. emissions per unit of energy, during time slot $t$.
+    pub carbon_intensity: Arc<dyn Fn(i32) -> f64 + Send + Sync>,
+    /// Price of a unit of emissions.
+    #[pyo3(get, set)]
+    pub carbon_price: f64,
+}
+impl CarbonAwareEnergyCostModel {
+    fn cost(&self, t: i32) -> N64 {
+        n64((self.cost)(t)) + n64(self.carbon_price) * self.carbon_intensity(t)
+    }
+    fn carbon_intensity(&self, t: i32) -> N64 {
+        n64((self.carbon_intensity)(t))
+    }
+}
+#[pymethods]
+impl CarbonAwareEnergyCostModel {
+    #[new]
+    fn constructor(
+        cost: Py<PyAny>,
+        carbon_intensity: Py<PyAny>,
+        carbon_price: f64,
+    ) -> Self {
+        CarbonAwareEnergyCostModel {
+            cost: Arc::new(move |t| {
+                Python::with_gil(|py| {
+                    cost.call1(py, (t,))
+                        .expect("carbon-aware energy cost model `cost` method invalid")
+                        .extract(py)
+                        .expect("carbon-aware energy cost model `cost` method invalid")
+                })
+            }),
+            carbon_intensity: Arc::new(move |t| {
+                Python::with_gil(|py| {
+                    carbon_intensity
+                        .call1(py, (t,))
+                        .expect("carbon-aware energy cost model `carbon_intensity` method invalid")
+                        .extract(py)
+                        .expect("carbon-aware energy cost model `carbon_intensity` method invalid")
+                })
+            }),
+            carbon_price,
+        }
+    }
+
+    #[staticmethod]
+    pub fn from_const(
+        cost: f64,
+        carbon_intensity: f64,
+        carbon_price: f64,
+    ) -> Self {
+        CarbonAwareEnergyCostModel {
+            cost: Arc::new(move |_t| cost),
+            carbon_intensity: Arc::new(move |_t| carbon_intensity),
+            carbon_price,
+        }
+    }
+}
+
 impl EnergyCostModel {
     /// Energy cost at some location during time slot $t$ with energy consumption $p$.
     /// Referred to as $\nu$ in the paper.
@@ -161,6 +226,110 @@ impl EnergyCostModel {
                 }
                 result
             }
+            EnergyCostModel::CarbonAware(models) => {
+                let model = &models[&location.key];
+                model.cost(t) * p
+            }
+        }
+    }
+
+    /// Carbon emissions at some location during time slot $t$ with energy consumption $p$.
+    /// Only carbon-aware energy cost models account for emissions.
+    pub fn emissions(&self, t: i32, location: &Location, p: N64) -> N64 {
+        match self {
+            EnergyCostModel::CarbonAware(models) => {
+                let model = &models[&location.key];
+                model.carbon_intensity(t) * p
+            }
+            _ => n64(0.),
+        }
+    }
+
+    /// Energy cost model where the prices of energy (including the profits of unused energy) are scaled by $factor$.
+    /// Prices of carbon emissions are not scaled.
+    pub fn scale(&self, factor: f64) -> Self {
+        match self {
+            EnergyCostModel::Linear(models) => EnergyCostModel::Linear(
+                models
+                    .iter()
+                    .map(|(key, model)| {
+                        (
+                            key.clone(),
+                            LinearEnergyCostModel {
+                                cost: scale_price(&model.cost, factor),
+                            },
+                        )
+                    })
+                    .collect(),
+            ),
+            EnergyCostModel::Quotas(models) => EnergyCostModel::Quotas(
+                models
+                    .iter()
+                    .map(|(key, model)| {
+                        (
+                            key.clone(),
+                            QuotasEnergyCostModel {
+                                sources: model
+                                    .sources
+                                    .iter()
+                                    .map(|source| EnergySource {
+                                        cost: scale_price(&source.cost, factor),
+                                        profit: scale_price(
+                                            &source.profit,
+                                            factor,
+                                        ),
+                                        limit: source.limit.clone(),
+                                    })
+                                    .collect(),
+                            },
+                        )
+                    })
+                    .collect(),
+            ),
+            EnergyCostModel::CarbonAware(models) => {
+                EnergyCostModel::CarbonAware(
+                    models
+                        .iter()
+                        .map(|(key, model)| {
+                            (
+                                key.clone(),
+                                CarbonAwareEnergyCostModel {
+                                    cost: scale_price(&model.cost, factor),
+                                    ..model.clone()
+                                },
+                            )
+                        })
+                        .collect(),
+                )
+            }
         }
     }
 }
+
+/// Price which is scaled by $factor$ during each time slot.
+fn scale_price(
+    price: &Arc<dyn Fn(i32) -> f64 + Send + Sync>,
+    factor: f64,
+) -> Arc<dyn Fn(i32) -> f64 + Send + Sync> {
+    let price = price.clone();
+    Arc::new(move |t| factor * price(t))
+}
+
+/// Charge on the peak power drawn by a location during each billing window, which is billed in addition to its energy cost.
+#[pyclass]
+#[derive(Clone)]
+pub struct DemandCharge {
+    /// Non-negative price of a unit of peak power.
+    #[pyo3(get, set)]
+    pub price: f64,
+    /// Positive length $w$ of a billing window in time slots. Billing windows start with time slots $1, w + 1, 2w + 1, \dots$
+    #[pyo3(get, set)]
+    pub window: i32,
+}
+#[pymethods]
+impl DemandCharge {
+    #[new]
+    fn constructor(price: f64, window: i32) -> Self {
+        DemandCharge { price, window }
+    }
+}
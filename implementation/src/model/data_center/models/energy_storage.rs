@@ -0,0 +1,94 @@
+//! Energy storage model.
+
+use crate::{
+    model::data_center::{DataCenterModelOutputFailure, IntermediateResult},
+    value::Value,
+};
+use noisy_float::prelude::*;
+use num::NumCast;
+use pyo3::prelude::*;
+
+/// On-site battery. Its state of charge (i.e. the stored energy at the end of a time slot) is an additional dimension of the decision space.
+/// As the state of charge is a continuous quantity, problems with batteries cannot be solved by the integral graph searches.
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct Battery {
+    /// Maximum amount of stored energy.
+    #[pyo3(get, set)]
+    pub capacity: f64,
+    /// Maximum amount of energy which can be stored in a unit of time.
+    #[pyo3(get, set)]
+    pub charge_rate: f64,
+    /// Maximum amount of energy which can be drawn in a unit of time.
+    #[pyo3(get, set)]
+    pub discharge_rate: f64,
+    /// Round-trip efficiency $\eta \in (0,1\]$. Losses are split evenly between charging and discharging.
+    #[pyo3(get, set)]
+    pub efficiency: f64,
+    /// Positive cost of wear when storing a unit of energy. Used as the switching cost of the state of charge.
+    #[pyo3(get, set)]
+    pub degradation_cost: f64,
+}
+#[pymethods]
+impl Battery {
+    #[new]
+    fn constructor(
+        capacity: f64,
+        charge_rate: f64,
+        discharge_rate: f64,
+        efficiency: f64,
+        degradation_cost: f64,
+    ) -> Self {
+        Battery {
+            capacity,
+            charge_rate,
+            discharge_rate,
+            efficiency,
+            degradation_cost,
+        }
+    }
+}
+
+impl Battery {
+    /// Upper bound of the state of charge. Integral problems only store whole units of energy, i.e. their capacity is rounded down.
+    pub fn bound<'a, T>(&self) -> T
+    where
+        T: Value<'a>,
+    {
+        let bound: T = NumCast::from(self.capacity).unwrap();
+        if bound.to_f64() == Some(self.capacity) {
+            bound
+        } else {
+            NumCast::from(self.capacity.floor()).unwrap()
+        }
+    }
+
+    /// Closest state of charge to $soc$ which can be reached from $prev_soc$ during a time slot of length $delta$.
+    pub fn project(&self, delta: f64, prev_soc: N64, soc: N64) -> N64 {
+        soc.min(prev_soc + self.charge_rate * delta)
+            .max(prev_soc - self.discharge_rate * delta)
+    }
+
+    /// Energy drawn from the grid (negative if energy is fed into the data center) when the state of charge changes
+    /// from $prev_soc$ to $soc$ during a time slot of length $delta$.
+    pub fn grid_energy(
+        &self,
+        delta: f64,
+        prev_soc: N64,
+        soc: N64,
+    ) -> IntermediateResult {
+        let stored = soc - prev_soc;
+        if stored > n64(self.charge_rate * delta)
+            || -stored > n64(self.discharge_rate * delta)
+        {
+            return Err(DataCenterModelOutputFailure::BatteryRateExceeded);
+        }
+
+        let efficiency = n64(self.efficiency).sqrt();
+        Ok(if stored >= 0. {
+            stored / efficiency
+        } else {
+            stored * efficiency
+        })
+    }
+}
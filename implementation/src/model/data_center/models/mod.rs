@@ -1,7 +1,10 @@
 //! Models of the cost of server load.
 
+pub mod cooling;
 pub mod delay;
 pub mod energy_consumption;
 pub mod energy_cost;
+pub mod energy_storage;
+pub mod rental_cost;
 pub mod revenue_loss;
 pub mod switching_cost;
@@ -0,0 +1,92 @@
+//! Rental cost model, i.e. the cost of servers which are rented rather than owned.
+
+use crate::model::data_center::model::{Location, ServerType};
+use noisy_float::prelude::*;
+use pyo3::prelude::*;
+use std::collections::HashMap;
+
+/// Rental cost model. Parameters are provided separately for each rented server type, server types without parameters are owned.
+#[derive(Clone, FromPyObject)]
+pub struct RentalCostModel(HashMap<String, RentalCost>);
+
+/// Pricing of a rented server type with an on-demand and a reserved tier.
+///
+/// The reserved instances of a location form the lower portion of the bounds of the server type and are paid whether they are used or not.
+/// Instances beyond the reserved instances are rented on demand, they are billed per started billing period and for at least the minimum term.
+/// As this rounding is not convex, on-demand instances are paid pro rata for each time slot they are active, and the remaining cost of the minimum term is paid when they are rented,
+/// i.e. the rental cost never underestimates the bill. The cost of the minimum term is also paid when reserved instances are powered up.
+#[pyclass]
+#[derive(Clone)]
+pub struct RentalCost {
+    /// Price of an on-demand instance per billing period.
+    #[pyo3(get, set)]
+    pub on_demand_price: f64,
+    /// Price of a reserved instance per billing period.
+    #[pyo3(get, set)]
+    pub reserved_price: f64,
+    /// Length of a billing period in time slots.
+    #[pyo3(get, set)]
+    pub billing_period: i32,
+    /// Minimum number of billing periods an on-demand instance is paid for. Default is $1$.
+    #[pyo3(get, set)]
+    pub minimum_term: i32,
+    /// Number of reserved instances of each location. By default, no instances are reserved.
+    #[pyo3(get, set)]
+    pub reserved: HashMap<String, i32>,
+}
+#[pymethods]
+impl RentalCost {
+    #[new]
+    #[args(minimum_term = "1", reserved = "HashMap::new()")]
+    fn constructor(
+        on_demand_price: f64,
+        reserved_price: f64,
+        billing_period: i32,
+        minimum_term: i32,
+        reserved: HashMap<String, i32>,
+    ) -> Self {
+        RentalCost {
+            on_demand_price,
+            reserved_price,
+            billing_period,
+            minimum_term,
+            reserved,
+        }
+    }
+}
+impl RentalCost {
+    /// Number of reserved instances at some location.
+    pub fn reserved_at(&self, location: &Location) -> i32 {
+        *self.reserved.get(&location.key).unwrap_or(&0)
+    }
+
+    /// Pro rata cost of an on-demand instance during a time slot.
+    pub fn on_demand_rate(&self) -> N64 {
+        n64(self.on_demand_price / self.billing_period as f64)
+    }
+
+    /// Cost of renting $x$ instances at some location during a time slot.
+    /// Reserved instances are paid whether they are used or not.
+    pub fn cost(&self, location: &Location, x: N64) -> N64 {
+        let reserved = n64(self.reserved_at(location) as f64);
+        n64(self.reserved_price / self.billing_period as f64) * reserved
+            + self.on_demand_rate() * (x - reserved).max(n64(0.))
+    }
+
+    /// Cost of the minimum term of an on-demand instance which exceeds the pro rata cost of its first time slot.
+    pub fn minimum_term_cost(&self) -> N64 {
+        n64(self.on_demand_price) * n64(self.minimum_term.max(1) as f64)
+            - self.on_demand_rate()
+    }
+}
+
+impl RentalCostModel {
+    pub fn new(rental_costs: HashMap<String, RentalCost>) -> Self {
+        RentalCostModel(rental_costs)
+    }
+
+    /// Returns model of some server type if it is rented.
+    pub fn model(&self, server_type: &ServerType) -> Option<&RentalCost> {
+        self.0.get(&server_type.key)
+    }
+}
@@ -13,6 +13,8 @@ pub enum RevenueLossModel {
     MinimalDetectableDelay(
         HashMap<String, MinimalDetectableDelayRevenueLossModel>,
     ),
+    /// Linear loss based on a percentile of the delay exceeding the delay guaranteed by a service level agreement.
+    TailLatency(HashMap<String, TailLatencyRevenueLossModel>),
 }
 
 #[pyclass]
@@ -24,25 +26,91 @@ pub struct MinimalDetectableDelayRevenueLossModel {
     /// Minimal detectable delay of a job type. $\delta \geq 0$.
     #[pyo3(get, set)]
     pub delta: f64,
+    /// Revenue loss of a job which is not served. Default is $\infty$.
+    #[pyo3(get, set)]
+    pub penalty: f64,
 }
 impl Default for MinimalDetectableDelayRevenueLossModel {
     fn default() -> Self {
         MinimalDetectableDelayRevenueLossModel {
             gamma: 1.,
             delta: 0.,
+            penalty: f64::INFINITY,
         }
     }
 }
 #[pymethods]
 impl MinimalDetectableDelayRevenueLossModel {
     #[new]
-    fn constructor(gamma: f64, delta: f64) -> Self {
-        MinimalDetectableDelayRevenueLossModel { gamma, delta }
+    #[args(penalty = "f64::INFINITY")]
+    fn constructor(gamma: f64, delta: f64, penalty: f64) -> Self {
+        MinimalDetectableDelayRevenueLossModel {
+            gamma,
+            delta,
+            penalty,
+        }
+    }
+}
+
+#[pyclass]
+#[derive(Clone)]
+pub struct TailLatencyRevenueLossModel {
+    /// Revenue loss factor. $\gamma \geq 0$.
+    #[pyo3(get, set)]
+    pub gamma: f64,
+    /// Percentile of the delay which is guaranteed by the service level agreement, e.g. $0.95$ or $0.99$. $p \in \[0,1)$.
+    #[pyo3(get, set)]
+    pub percentile: f64,
+    /// Guaranteed delay of a job type. $\geq 0$.
+    #[pyo3(get, set)]
+    pub sla: f64,
+    /// Revenue loss of a job which is not served. Default is $\infty$.
+    #[pyo3(get, set)]
+    pub penalty: f64,
+}
+#[pymethods]
+impl TailLatencyRevenueLossModel {
+    #[new]
+    #[args(penalty = "f64::INFINITY")]
+    fn constructor(
+        gamma: f64,
+        percentile: f64,
+        sla: f64,
+        penalty: f64,
+    ) -> Self {
+        TailLatencyRevenueLossModel {
+            gamma,
+            percentile,
+            sla,
+            penalty,
+        }
     }
 }
 
 impl RevenueLossModel {
-    /// Revenue loss if jobs of some type have average delay $delay$ during time slot $t$.
+    /// Percentile of the delay on which the revenue loss of jobs of some type is based. The average delay is used if `None`.
+    pub fn percentile(&self, job_type: &JobType) -> Option<f64> {
+        match self {
+            RevenueLossModel::MinimalDetectableDelay(_) => None,
+            RevenueLossModel::TailLatency(models) => {
+                Some(models[&job_type.key].percentile)
+            }
+        }
+    }
+
+    /// Delay of jobs of some type beyond which revenue is lost, i.e. the minimal detectable delay or the delay guaranteed by the service level agreement.
+    pub fn threshold(&self, job_type: &JobType) -> N64 {
+        match self {
+            RevenueLossModel::MinimalDetectableDelay(models) => {
+                n64(models[&job_type.key].delta)
+            }
+            RevenueLossModel::TailLatency(models) => {
+                n64(models[&job_type.key].sla)
+            }
+        }
+    }
+
+    /// Revenue loss if jobs of some type have delay $delay$ (see `percentile`) during time slot $t$.
     /// Referred to as $r$ in the paper.
     pub fn loss(&self, _t: i32, job_type: &JobType, delay: N64) -> N64 {
         match self {
@@ -50,6 +118,22 @@ impl RevenueLossModel {
                 let model = &models[&job_type.key];
                 n64(model.gamma) * pos(delay - n64(model.delta))
             }
+            RevenueLossModel::TailLatency(models) => {
+                let model = &models[&job_type.key];
+                n64(model.gamma) * pos(delay - n64(model.sla))
+            }
+        }
+    }
+
+    /// Revenue loss if a job of some type is not served during time slot $t$.
+    pub fn unserved_loss(&self, _t: i32, job_type: &JobType) -> N64 {
+        match self {
+            RevenueLossModel::MinimalDetectableDelay(models) => {
+                n64(models[&job_type.key].penalty)
+            }
+            RevenueLossModel::TailLatency(models) => {
+                n64(models[&job_type.key].penalty)
+            }
         }
     }
 }
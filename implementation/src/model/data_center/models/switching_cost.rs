@@ -150,6 +150,31 @@ impl SwitchingCostModel {
             .collect()
     }
 
+    /// Switching cost model where the switching cost of each server type is replaced such that its normalized switching cost is $normalized_switching_cost$.
+    /// Here, $\delta$ is the time slot length. Energy costs and idle power are retained.
+    pub fn normalized(
+        &self,
+        delta: f64,
+        normalized_switching_cost: f64,
+    ) -> Self {
+        SwitchingCostModel(
+            self.0
+                .iter()
+                .map(|(key, switching_cost)| {
+                    (
+                        key.clone(),
+                        SwitchingCost::from_normalized(
+                            delta,
+                            normalized_switching_cost,
+                            switching_cost.energy_cost,
+                            switching_cost.phi_min,
+                        ),
+                    )
+                })
+                .collect(),
+        )
+    }
+
     /// Returns model of some server type.
     fn model(&self, server_type: &ServerType) -> &SwitchingCost {
         &self.0[&server_type.key]
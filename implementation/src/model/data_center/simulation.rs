@@ -0,0 +1,562 @@
+//! Discrete-event simulation of a data center replaying a trace of jobs to validate the predictions of the model.
+//!
+//! Jobs are dispatched to server types according to the load assignments of the model. Each server is simulated as a processor sharing queue.
+
+use super::loads::LoadProfile;
+use super::model::{encode, parse, DataCenterModel, DataCenterOfflineInput};
+use super::{DataCenterModelOutputFailure, DataCenterModelOutputSuccess};
+use crate::cost::Cost;
+use crate::model::{Model, ModelOutput};
+use crate::numerics::TOLERANCE;
+use crate::problem::{FractionalSimplifiedSmoothedConvexOptimization, Problem};
+use crate::report::columns_to_numpy;
+use crate::result::Result;
+use crate::schedule::Schedule;
+use noisy_float::prelude::*;
+use pyo3::prelude::*;
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use std::collections::HashMap;
+
+/// Job of a trace.
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct Job {
+    /// Time of arrival. Jobs arriving during $[(t-1)\delta, t\delta)$ arrive during time slot $t$.
+    #[pyo3(get, set)]
+    pub arrival: f64,
+    /// Source.
+    #[pyo3(get, set)]
+    pub source: String,
+    /// Job type.
+    #[pyo3(get, set)]
+    pub job_type: String,
+    /// Size relative to the processing time of the job type, i.e. the job needs $size \cdot \eta_{k,i}$ units of time on a server of type $k$.
+    /// Default is $1$.
+    #[pyo3(get, set)]
+    pub size: f64,
+}
+#[pymethods]
+impl Job {
+    #[new]
+    #[args(size = "1.")]
+    fn constructor(
+        arrival: f64,
+        source: String,
+        job_type: String,
+        size: f64,
+    ) -> Self {
+        Job {
+            arrival,
+            source,
+            job_type,
+            size,
+        }
+    }
+}
+impl Job {
+    /// Time slot during which the job arrives.
+    pub fn time_slot(&self, delta: f64) -> i32 {
+        (self.arrival / delta).floor() as i32 + 1
+    }
+}
+
+/// Realized behavior of a data center during time slot $t$ next to the behavior predicted by the model.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SimulationReportEntry {
+    /// Time slot.
+    pub t: i32,
+    /// Number of jobs arriving.
+    pub jobs: usize,
+    /// Number of arriving jobs which are not served as the model does not assign their load to any active servers.
+    pub unserved_jobs: usize,
+    /// Delays of the served jobs (in order of arrival), i.e. the time from their arrival until their completion plus the routing delay.
+    pub delays: Vec<f64>,
+    /// Number of served jobs whose delay exceeds the threshold of the revenue loss model, e.g. the delay guaranteed by the service level agreement.
+    pub sla_violations: usize,
+    /// Utilization of the servers of each dimension, i.e. the fraction of time they are busy.
+    pub utilizations: Vec<f64>,
+    /// Utilization of the servers of each dimension predicted by the model.
+    pub predicted_utilizations: Vec<f64>,
+    /// Energy consumption of all servers including cooling overheads.
+    pub energy_consumption: f64,
+    /// Energy cost. Demand charges are not accounted for.
+    pub energy_cost: f64,
+    /// Revenue loss of the served and unserved jobs.
+    pub revenue_loss: f64,
+    /// Hitting cost predicted by the model.
+    pub predicted:
+        Cost<DataCenterModelOutputSuccess, DataCenterModelOutputFailure>,
+}
+
+/// Realized and predicted behavior of a data center for each time slot.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SimulationReport {
+    pub entries: Vec<SimulationReportEntry>,
+}
+impl SimulationReport {
+    /// Named columns of the report with a value for each time slot.
+    ///
+    /// Predictions of the model are `NaN` during time slots in which the model fails. The mean delay is `NaN` during time slots without served jobs.
+    pub fn columns(&self) -> Vec<(String, Vec<f64>)> {
+        let column = |name: &str, f: &dyn Fn(&SimulationReportEntry) -> f64| {
+            (name.to_string(), self.entries.iter().map(f).collect())
+        };
+        let predicted =
+            |entry: &SimulationReportEntry,
+             f: &dyn Fn(&DataCenterModelOutputSuccess) -> f64| {
+                match &entry.predicted.output {
+                    ModelOutput::Success(output) => f(output),
+                    _ => f64::NAN,
+                }
+            };
+        let mut columns = vec![
+            column("t", &|entry| entry.t as f64),
+            column("jobs", &|entry| entry.jobs as f64),
+            column("unserved_jobs", &|entry| entry.unserved_jobs as f64),
+            column("sla_violations", &|entry| entry.sla_violations as f64),
+            column("mean_delay", &|entry| {
+                entry.delays.iter().sum::<f64>() / entry.delays.len() as f64
+            }),
+            column("energy_consumption", &|entry| entry.energy_consumption),
+            column("energy_cost", &|entry| entry.energy_cost),
+            column("predicted_energy_cost", &|entry| {
+                predicted(entry, &|output| output.energy_cost)
+            }),
+            column("revenue_loss", &|entry| entry.revenue_loss),
+            column("predicted_revenue_loss", &|entry| {
+                predicted(entry, &|output| output.revenue_loss)
+            }),
+            column("predicted_cost", &|entry| entry.predicted.cost.raw()),
+        ];
+        let d = self
+            .entries
+            .iter()
+            .map(|entry| entry.utilizations.len())
+            .max()
+            .unwrap_or(0);
+        for k in 0..d {
+            columns.push(column(&format!("utilization.{}", k), &|entry| {
+                entry.utilizations[k]
+            }));
+            columns.push(column(
+                &format!("predicted_utilization.{}", k),
+                &|entry| entry.predicted_utilizations[k],
+            ));
+        }
+        columns
+    }
+
+    /// Report as a Python dictionary mapping each column to a NumPy array of its values.
+    pub fn to_numpy(&self, py: Python) -> PyResult<PyObject> {
+        columns_to_numpy(py, self.columns())
+    }
+}
+
+/// Replays the trace of jobs against schedule $xs$ of the data center.
+///
+/// The load of each time slot is the number of arriving jobs of each source and job type. Jobs are dispatched to the dimensions
+/// according to the load fractions which the model assigns to this load (deterministically by smooth weighted round-robin)
+/// and within a dimension to its servers by round-robin. If the model fails, jobs are dispatched to the allowed dimensions proportionally
+/// to their number of servers. Fractional numbers of servers are rounded up.
+///
+/// Jobs are dispatched during the time slot of their arrival, i.e. deferral is not simulated. Jobs which remain on a server
+/// after it is powered down are completed as if the server remained active. Servers consume energy while they are active or busy.
+pub fn simulate(
+    model: &DataCenterModel,
+    xs: &Schedule<f64>,
+    trace: &[Job],
+) -> Result<SimulationReport> {
+    model.verify()?;
+    for job in trace {
+        job.verify(model, xs.t_end())?;
+    }
+
+    let t_end = xs.t_end() as usize;
+    let (d, e) = (model.d_() as usize, model.e_() as usize);
+    let load_type = |job: &Job| {
+        let s = model
+            .sources
+            .iter()
+            .position(|source| source.key == job.source)
+            .unwrap();
+        let i = model
+            .job_types
+            .iter()
+            .position(|job_type| job_type.key == job.job_type)
+            .unwrap();
+        encode(model.job_types.len(), s, i)
+    };
+
+    let mut loads = vec![vec![0.; e]; t_end];
+    for job in trace {
+        loads[job.time_slot(model.delta) as usize - 1][load_type(job)] += 1.;
+    }
+    let p: FractionalSimplifiedSmoothedConvexOptimization<_, _> =
+        model.to(DataCenterOfflineInput {
+            loads: loads.iter().cloned().map(LoadProfile::raw).collect(),
+        });
+    let predictions: Vec<_> = (1..=t_end as i32)
+        .into_par_iter()
+        .map(|t| p.hit_cost(t, xs.get(t).unwrap().clone()))
+        .collect();
+    let ready: Vec<_> = (1..=t_end as i32)
+        .map(|t| model.ready_servers(xs.get(t).unwrap()))
+        .collect();
+    let servers: Vec<Vec<usize>> = ready
+        .iter()
+        .map(|x| {
+            (0..d)
+                .map(|k_| (x[k_] - TOLERANCE).ceil().max(0.) as usize)
+                .collect()
+        })
+        .collect();
+
+    let fractions: Vec<Vec<f64>> = (0..t_end)
+        .map(|t_| match &predictions[t_].output {
+            ModelOutput::Success(output) => output.assignments[0][0].clone(),
+            _ => {
+                let mut zs = vec![0.; d * e];
+                for i_ in 0..e {
+                    let allowed: Vec<_> =
+                        (0..d).filter(|&k_| model.allows(k_, i_)).collect();
+                    let total: f64 =
+                        allowed.iter().map(|&k_| ready[t_][k_]).sum();
+                    if total > 0. {
+                        for &k_ in &allowed {
+                            zs[k_ * e + i_] = ready[t_][k_] / total;
+                        }
+                    }
+                }
+                zs
+            }
+        })
+        .collect();
+
+    // dispatches jobs in order of arrival
+    let mut order: Vec<_> = (0..trace.len()).collect();
+    order.sort_by(|&a, &b| trace[a].arrival.total_cmp(&trace[b].arrival));
+    let mut dispatched: HashMap<(usize, usize), (usize, Vec<usize>)> =
+        HashMap::new();
+    let mut next_server: HashMap<(usize, usize), usize> = HashMap::new();
+    let mut queues: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+    let mut dimensions = vec![None; trace.len()];
+    for &n in &order {
+        let job = &trace[n];
+        let t = job.time_slot(model.delta) as usize;
+        let i_ = load_type(job);
+        let zs = &fractions[t - 1];
+        let (count, counts) =
+            dispatched.entry((t, i_)).or_insert_with(|| (0, vec![0; d]));
+        let k_ = match (0..d)
+            .filter(|&k_| zs[k_ * e + i_] > TOLERANCE && servers[t - 1][k_] > 0)
+            .max_by(|&a, &b| {
+                let deficit = |k_: usize| {
+                    zs[k_ * e + i_] * (*count + 1) as f64 - counts[k_] as f64
+                };
+                deficit(a).total_cmp(&deficit(b)).then(b.cmp(&a))
+            }) {
+            Some(k_) => k_,
+            None => continue,
+        };
+        *count += 1;
+        counts[k_] += 1;
+        let l = next_server.entry((t, k_)).or_insert(0);
+        queues
+            .entry((k_, *l % servers[t - 1][k_]))
+            .or_default()
+            .push(n);
+        *l += 1;
+        dimensions[n] = Some(k_);
+    }
+
+    // simulates the processor sharing queue of each server
+    let mut completions = vec![f64::NAN; trace.len()];
+    let mut busy = vec![vec![0.; d]; t_end];
+    let mut energy_consumption = vec![vec![0.; model.locations.len()]; t_end];
+    let max_servers: Vec<_> = (0..d)
+        .map(|k_| (0..t_end).map(|t| servers[t][k_]).max().unwrap_or(0))
+        .collect();
+    for k_ in 0..d {
+        let (j, k) = parse(model.server_types.len(), k_);
+        let server_type = &model.server_types[k];
+        for l in 0..max_servers[k_] {
+            let queue = queues.remove(&(k_, l)).unwrap_or_default();
+            let jobs: Vec<_> = queue
+                .iter()
+                .map(|&n| {
+                    let job = &trace[n];
+                    let job_type = model
+                        .job_types
+                        .iter()
+                        .find(|job_type| job_type.key == job.job_type)
+                        .unwrap();
+                    (
+                        job.arrival,
+                        job.size
+                            * job_type.processing_time_on(server_type).raw(),
+                    )
+                })
+                .collect();
+            let (server_completions, intervals) = processor_sharing(&jobs);
+
+            let mut server_busy = vec![0.; t_end];
+            for &(start, end) in &intervals {
+                accumulate(&mut server_busy, model.delta, start, end, 1.);
+            }
+            let mut resource_usage: HashMap<&String, Vec<f64>> = HashMap::new();
+            for (&n, &completion) in queue.iter().zip(&server_completions) {
+                completions[n] = completion;
+                let job_type = model
+                    .job_types
+                    .iter()
+                    .find(|job_type| job_type.key == trace[n].job_type)
+                    .unwrap();
+                for (resource, &demand) in &job_type.demands {
+                    accumulate(
+                        resource_usage
+                            .entry(resource)
+                            .or_insert_with(|| vec![0.; t_end]),
+                        model.delta,
+                        trace[n].arrival,
+                        completion,
+                        demand,
+                    );
+                }
+            }
+
+            for t in 0..t_end {
+                busy[t][k_] += server_busy[t];
+                if l >= servers[t][k_] && server_busy[t] == 0. {
+                    continue;
+                }
+                let resource_utilizations = server_type
+                    .capacities
+                    .iter()
+                    .filter(|(_, &capacity)| capacity > 0.)
+                    .map(|(resource, &capacity)| {
+                        let usage = resource_usage
+                            .get(resource)
+                            .map_or(0., |usage| usage[t]);
+                        (
+                            resource.clone(),
+                            n64(usage / (capacity * model.delta)),
+                        )
+                    })
+                    .collect();
+                let utilization = server_type.effective_utilization(
+                    n64(server_busy[t] / model.delta),
+                    &resource_utilizations,
+                );
+                energy_consumption[t][j] += model
+                    .energy_consumption_model
+                    .consumption(model.delta, server_type, utilization)
+                    .raw();
+            }
+        }
+    }
+
+    let entries = (0..t_end)
+        .map(|t_| {
+            let t = t_ as i32 + 1;
+            let arrived: Vec<_> = order
+                .iter()
+                .copied()
+                .filter(|&n| trace[n].time_slot(model.delta) == t)
+                .collect();
+
+            let mut delays = vec![];
+            let mut sla_violations = 0;
+            let mut groups: HashMap<(usize, usize), Vec<f64>> = HashMap::new();
+            let mut revenue_loss = 0.;
+            for &n in &arrived {
+                let job = &trace[n];
+                let i_ = load_type(job);
+                let (s, i) = parse(model.job_types.len(), i_);
+                let job_type = &model.job_types[i];
+                match dimensions[n] {
+                    Some(k_) => {
+                        let (j, _) = parse(model.server_types.len(), k_);
+                        let location = &model.locations[j];
+                        let source = &model.sources[s];
+                        let delay = completions[n] - job.arrival
+                            + source.routing_delay_to(t, location).raw();
+                        if n64(delay)
+                            > model.revenue_loss_model.threshold(job_type)
+                        {
+                            sla_violations += 1;
+                        }
+                        delays.push(delay);
+                        groups.entry((k_, i_)).or_default().push(delay);
+                    }
+                    None => {
+                        revenue_loss += model
+                            .revenue_loss_model
+                            .unserved_loss(t, job_type)
+                            .raw()
+                    }
+                }
+            }
+            // mirrors the model by pricing the (average or percentile) delay of the jobs of each load type processed by each dimension
+            for ((_, i_), mut group) in groups {
+                let (_, i) = parse(model.job_types.len(), i_);
+                let job_type = &model.job_types[i];
+                let delay = match model.revenue_loss_model.percentile(job_type)
+                {
+                    Some(p) => {
+                        group.sort_by(f64::total_cmp);
+                        let rank = (p * group.len() as f64).ceil() as usize;
+                        group[rank.clamp(1, group.len()) - 1]
+                    }
+                    None => group.iter().sum::<f64>() / group.len() as f64,
+                };
+                revenue_loss +=
+                    (model.revenue_loss_model.loss(t, job_type, n64(delay))
+                        * n64(group.len() as f64))
+                    .raw();
+            }
+
+            let mut energy_cost = 0.;
+            let mut total_energy_consumption = 0.;
+            for (j, location) in model.locations.iter().enumerate() {
+                let mut p = energy_consumption[t_][j];
+                if let Some(cooling_model) = &model.cooling_model {
+                    let total_servers: i32 = location.m.values().sum();
+                    let location_busy: f64 = (0..model.server_types.len())
+                        .map(|k| {
+                            busy[t_][encode(model.server_types.len(), j, k)]
+                        })
+                        .sum();
+                    let s = if total_servers > 0 {
+                        location_busy / (total_servers as f64 * model.delta)
+                    } else {
+                        0.
+                    };
+                    p *= cooling_model.pue(t, location, n64(s)).raw();
+                }
+                energy_cost +=
+                    model.energy_cost_model.cost(t, location, n64(p)).raw();
+                total_energy_consumption += p;
+            }
+
+            let utilizations = (0..d)
+                .map(|k_| {
+                    if servers[t_][k_] > 0 {
+                        busy[t_][k_] / (servers[t_][k_] as f64 * model.delta)
+                    } else {
+                        0.
+                    }
+                })
+                .collect();
+            let predicted_utilizations = (0..d)
+                .map(|k_| match &predictions[t_].output {
+                    ModelOutput::Success(output) if ready[t_][k_] > 0. => {
+                        let (_, k) = parse(model.server_types.len(), k_);
+                        let work: f64 = (0..e)
+                            .map(|i_| {
+                                let (_, i) = parse(model.job_types.len(), i_);
+                                loads[t_][i_]
+                                    * output.assignments[0][0][k_ * e + i_]
+                                    * model.job_types[i]
+                                        .processing_time_on(
+                                            &model.server_types[k],
+                                        )
+                                        .raw()
+                            })
+                            .sum();
+                        work / (ready[t_][k_] * model.delta)
+                    }
+                    _ => 0.,
+                })
+                .collect();
+
+            SimulationReportEntry {
+                t,
+                jobs: arrived.len(),
+                unserved_jobs: arrived
+                    .iter()
+                    .filter(|&&n| dimensions[n].is_none())
+                    .count(),
+                delays,
+                sla_violations,
+                utilizations,
+                predicted_utilizations,
+                energy_consumption: total_energy_consumption,
+                energy_cost,
+                revenue_loss,
+                predicted: predictions[t_].clone(),
+            }
+        })
+        .collect();
+    Ok(SimulationReport { entries })
+}
+
+/// Simulates a processor sharing queue serving jobs given by their arrival and their work (in order of arrival).
+/// Returns the completion of each job and the intervals during which the server is busy.
+fn processor_sharing(jobs: &[(f64, f64)]) -> (Vec<f64>, Vec<(f64, f64)>) {
+    let mut completions = vec![0.; jobs.len()];
+    let mut intervals = vec![];
+    // jobs in service with their remaining work
+    let mut active: Vec<(usize, f64)> = vec![];
+    let (mut now, mut busy_since) = (0., 0.);
+    let mut next = 0;
+    while next < jobs.len() || !active.is_empty() {
+        if active.is_empty() {
+            now = jobs[next].0;
+            busy_since = now;
+            active.push((next, jobs[next].1));
+            next += 1;
+            continue;
+        }
+
+        // all jobs in service are processed at the same rate
+        let rate = 1. / active.len() as f64;
+        let min_remaining = active
+            .iter()
+            .map(|&(_, remaining)| remaining)
+            .fold(f64::INFINITY, f64::min);
+        let completion = now + min_remaining / rate;
+        if next < jobs.len() && jobs[next].0 < completion {
+            let elapsed = jobs[next].0 - now;
+            for (_, remaining) in &mut active {
+                *remaining -= elapsed * rate;
+            }
+            now = jobs[next].0;
+            active.push((next, jobs[next].1));
+            next += 1;
+        } else {
+            now = completion;
+            active.retain_mut(|(n, remaining)| {
+                *remaining -= min_remaining;
+                if *remaining <= 0. {
+                    completions[*n] = now;
+                    false
+                } else {
+                    true
+                }
+            });
+            if active.is_empty() {
+                intervals.push((busy_since, now));
+            }
+        }
+    }
+    (completions, intervals)
+}
+
+/// Adds $weight$ times the overlap of the interval from $start$ to $end$ with each time slot of length $\delta$.
+fn accumulate(
+    slots: &mut [f64],
+    delta: f64,
+    start: f64,
+    end: f64,
+    weight: f64,
+) {
+    let first = (start / delta).floor().max(0.) as usize;
+    for (t_, slot) in slots.iter_mut().enumerate().skip(first) {
+        let (slot_start, slot_end) =
+            (t_ as f64 * delta, (t_ + 1) as f64 * delta);
+        if slot_start >= end {
+            break;
+        }
+        *slot += weight * (end.min(slot_end) - start.max(slot_start)).max(0.);
+    }
+}
@@ -1,6 +1,7 @@
 //! Abstract and concrete definitions of models that are used to generate problem instances.
 
 use crate::problem::{Online, Problem};
+use crate::result::Result;
 use log::info;
 use pyo3::{IntoPy, PyObject, Python};
 use serde::{de::DeserializeOwned, Serialize};
@@ -26,12 +27,37 @@ pub trait ModelOutputSuccess:
     /// Merge two outputs across time steps.
     fn horizontal_merge(self, output: Self) -> Self;
 
-    /// Merge two outputs within the same time step.
+    /// Sum two outputs within the same time step (see `ModelOutput::vertical_reduce`).
     fn vertical_merge(self, output: Self) -> Self;
+
+    /// Scale an output within some time step by $factor$, i.e. weigh it before it is merged with other outputs of the same time step.
+    fn vertical_scale(self, factor: f64) -> Self;
+
+    /// Records the samples (within the same time step) which determined the output.
+    fn select_samples(self, _samples: &[usize]) -> Self {
+        self
+    }
+
+    /// Carbon emissions (if accounted for by the model).
+    fn emissions(&self) -> f64 {
+        0.
+    }
+
+    /// Peak of the quantity with the given key (if accounted for by the model) which is subject to a peak charge (see `PeakCharge`),
+    /// e.g. the power drawn by a location. Merging outputs across time steps keeps the peak.
+    fn peak(&self, _key: &str) -> f64 {
+        0.
+    }
+
+    /// Named constituents of the cost which are reported in addition to the hitting cost (see `CostReport`).
+    fn report(&self) -> Vec<(String, f64)> {
+        vec![]
+    }
 }
 impl ModelOutputSuccess for () {
     fn horizontal_merge(self, _: ()) {}
     fn vertical_merge(self, _: ()) {}
+    fn vertical_scale(self, _: f64) {}
 }
 /// Failures of a model.
 pub trait ModelOutputFailure:
@@ -89,10 +115,57 @@ where
             .unwrap()
     }
 
+    /// Carbon emissions. Failures are considered to have infinite emissions.
+    pub fn emissions(&self) -> f64 {
+        match self {
+            ModelOutput::Success(output) => output.emissions(),
+            ModelOutput::Failure(_) => f64::INFINITY,
+            ModelOutput::None => 0.,
+        }
+    }
+
+    /// Peak of the quantity with the given key. Outputs other than successes have no peak.
+    pub fn peak(&self, key: &str) -> f64 {
+        match self {
+            ModelOutput::Success(output) => output.peak(key),
+            _ => 0.,
+        }
+    }
+
+    /// Records the samples (within the same time step) which determined a successful output.
+    pub fn select_samples(self, samples: &[usize]) -> Self {
+        match self {
+            ModelOutput::Success(output) => {
+                ModelOutput::Success(output.select_samples(samples))
+            }
+            output => output,
+        }
+    }
+
+    /// Averages the outputs of the same time step. Fails if any output fails; outputs which are `None` are ignored.
     pub fn vertical_reduce(outputs: Vec<Self>) -> Self {
+        Self::weighted_vertical_reduce(
+            outputs.into_iter().map(|output| (1., output)).collect(),
+        )
+    }
+
+    /// Computes the weighted average of the outputs of the same time step. Weights are normalized across the successful outputs.
+    /// Fails if any output fails; outputs which are `None` are ignored.
+    pub fn weighted_vertical_reduce(outputs: Vec<(f64, Self)>) -> Self {
         assert!(!outputs.is_empty());
+        let total_weight: f64 = outputs
+            .iter()
+            .filter(|(_, output)| matches!(output, ModelOutput::Success(_)))
+            .map(|&(weight, _)| weight)
+            .sum();
         outputs
             .into_iter()
+            .map(|(weight, output)| match output {
+                ModelOutput::Success(output) => ModelOutput::Success(
+                    output.vertical_scale(weight / total_weight),
+                ),
+                output => output,
+            })
             .reduce(|result, output| match result {
                 ModelOutput::Failure(result) => ModelOutput::Failure(result),
                 ModelOutput::Success(result) => match output {
@@ -124,6 +197,11 @@ where
 
     /// Performs an online update of the given problem instance $o$ with some $input$ (which may be uncertain).
     fn update(&self, o: &mut Online<P>, input: B);
+
+    /// Checks that the model is valid.
+    fn verify(&self) -> Result<()> {
+        Ok(())
+    }
 }
 
 /// Utility to verify that the update of an online instance is valid.
@@ -11,6 +11,11 @@ use std::sync::Arc;
 
 static MAX_ITERATIONS_PER_DIM: u32 = 1_000;
 
+/// Maximal number of evaluations of the objective in $d$ dimensions.
+pub fn max_evaluations(d: usize) -> u32 {
+    (d as u32).saturating_mul(MAX_ITERATIONS_PER_DIM)
+}
+
 /// Optimization direction.
 #[derive(Clone, Copy)]
 enum Direction {
@@ -139,14 +144,13 @@ fn optimize<C, D>(
     solver.set_xtol_rel(TOLERANCE).unwrap();
 
     // stop evaluation when solver appears to hit a dead end, this may happen when all function evaluations return infinity.
-    solver
-        .set_maxeval(d as u32 * MAX_ITERATIONS_PER_DIM)
-        .unwrap();
+    solver.set_maxeval(max_evaluations(d)).unwrap();
 
     for WrappedObjective { f, data } in constraints {
         solver
             .add_inequality_constraint(
-                |xs: &[f64], _: Option<&mut [f64]>, data: &mut D| {
+                // the constraint outlives this iteration, so it must own `f`
+                move |xs: &[f64], _: Option<&mut [f64]>, data: &mut D| {
                     evaluate(xs, data, &f)
                 },
                 data,
@@ -8,6 +8,12 @@ use finitediff::FiniteDiff;
 use log::warn;
 use noisy_float::prelude::*;
 
+/// Step size of one-sided finite differences relative to the magnitude of the argument.
+static DIFFERENCE_STEP: f64 = 1e-7;
+/// Step size of one-sided finite differences of second order relative to the magnitude of the argument.
+/// Larger than `DIFFERENCE_STEP` as second differences are more sensitive to rounding errors.
+static SECOND_ORDER_DIFFERENCE_STEP: f64 = 1e-4;
+
 pub fn derivative(f: impl Fn(f64) -> f64, x: f64) -> N64 {
     if f(x).is_infinite() {
         return n64(f64::INFINITY);
@@ -50,3 +56,70 @@ pub fn gradient(f: &impl Fn(&Vec<f64>) -> f64, xs: Vec<f64>) -> Vec<f64> {
         d
     }).collect()
 }
+
+/// Approximates the derivative of $f$ at $x$ by a forward difference whose step size is proportional to $scale$.
+/// Infinite if $f$ is infinite at $x$ or after the step, i.e. if the step leaves the domain of $f$.
+pub fn forward_derivative(f: impl Fn(f64) -> f64, x: f64, scale: f64) -> f64 {
+    let h = DIFFERENCE_STEP * scale;
+    let value = f(x);
+    let shifted = f(x + h);
+    if value.is_finite() && shifted.is_finite() {
+        (shifted - value) / h
+    } else {
+        f64::INFINITY
+    }
+}
+
+/// Approximates the first and second derivative of $f$ at $x$ (where $f(x) = value$) by one-sided finite differences of second order whose step size is proportional to $scale$.
+/// Forward differences are used unless they leave the domain of $f$, in which case backward differences are used. Returns `None` if both leave the domain.
+pub fn one_sided_derivatives(
+    f: impl Fn(f64) -> f64,
+    x: f64,
+    value: f64,
+    scale: f64,
+) -> Option<(f64, f64)> {
+    let h = SECOND_ORDER_DIFFERENCE_STEP * scale;
+    [h, -h].iter().find_map(|&h| {
+        let once = f(x + h);
+        let twice = f(x + 2. * h);
+        if once.is_finite() && twice.is_finite() {
+            Some((
+                (4. * once - 3. * value - twice) / (2. * h),
+                (twice - 2. * once + value) / (h * h),
+            ))
+        } else {
+            None
+        }
+    })
+}
+
+/// Approximates the partial derivatives of $f$ at $xs$ (where $f(xs) = value$) w.r.t. the given $coordinates$ by one-sided finite differences.
+/// Forward differences are used unless they leave the domain of $f$, in which case backward differences are used.
+/// The partial derivatives w.r.t. all other coordinates (and those where both differences leave the domain) are $0$.
+pub fn partial_derivatives(
+    f: &impl Fn(&[f64]) -> f64,
+    xs: &[f64],
+    value: f64,
+    coordinates: &[usize],
+) -> Vec<f64> {
+    let mut result = vec![0.; xs.len()];
+    let mut ys = xs.to_vec();
+    for &j in coordinates {
+        let h = DIFFERENCE_STEP * xs[j].abs().max(1.);
+        ys[j] = xs[j] + h;
+        let forward = f(&ys);
+        result[j] = if forward.is_finite() {
+            (forward - value) / h
+        } else {
+            ys[j] = xs[j] - h;
+            let backward = f(&ys);
+            if backward.is_finite() {
+                (value - backward) / h
+            } else {
+                0.
+            }
+        };
+        ys[j] = xs[j];
+    }
+    result
+}
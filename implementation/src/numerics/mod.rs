@@ -4,6 +4,7 @@ pub mod convex_optimization;
 pub mod finite_differences;
 pub mod quadrature;
 pub mod roots;
+pub mod simplex;
 
 /// Precision used for numeric computations.
 pub static PRECISION: f64 = 1e-2;
@@ -0,0 +1,464 @@
+//! Convex optimization over products of simplices.
+
+use crate::numerics::finite_differences::{
+    forward_derivative, one_sided_derivatives, partial_derivatives,
+};
+use std::cmp::Ordering;
+
+/// Maximal number of iterations of projected gradient descent.
+static MAX_ITERATIONS: usize = 1_000;
+/// Maximal number of rounds of Dykstra's alternating projections.
+static MAX_PROJECTION_ROUNDS: usize = 1_000;
+/// Maximal number of Newton steps of water-filling.
+static MAX_NEWTON_STEPS: usize = 100;
+/// Smallest curvature of the quadratic models of water-filling, which ensures that linear functions have a unique minimizer.
+static MIN_CURVATURE: f64 = 1e-12;
+/// Fraction of the decrease predicted by the linear model which is required for a step to be accepted.
+static ARMIJO: f64 = 1e-4;
+/// Maximal number of bisections when searching for a marginal cost.
+static BISECTIONS: usize = 100;
+/// Precision of water-filling relative to the total of all coordinates.
+static WATER_FILLING_PRECISION: f64 = 1e-6;
+/// Maximal number of times an upper bound is doubled when searching for a value.
+static MAX_DOUBLINGS: usize = 200;
+/// Maximal number of sweeps of block-coordinate water-filling over all simplices.
+static MAX_SWEEPS: usize = 100;
+/// Smallest step size of projected gradient descent before it is assumed to be stationary.
+static MIN_STEP: f64 = 1e-20;
+/// Change of the argument below which an optimization is assumed to have converged.
+static CONVERGENCE: f64 = 1e-9;
+
+/// Coordinates which are non-negative and sum to $total$.
+#[derive(Clone, Debug)]
+pub struct Simplex {
+    pub coordinates: Vec<usize>,
+    pub total: f64,
+}
+
+/// Half-space $\{x \mid a^T x \leq b\}$ where $a$ is given by its non-zero coefficients.
+#[derive(Clone, Debug)]
+pub struct HalfSpace {
+    pub coefficients: Vec<(usize, f64)>,
+    pub bound: f64,
+}
+
+impl HalfSpace {
+    /// Amount by which $x$ violates the half-space (negative if $x$ is strictly inside).
+    pub fn excess(&self, x: &[f64]) -> f64 {
+        self.coefficients
+            .iter()
+            .map(|&(j, a)| a * x[j])
+            .sum::<f64>()
+            - self.bound
+    }
+
+    fn project(&self, x: &mut [f64]) {
+        let excess = self.excess(x);
+        if excess <= 0. {
+            return;
+        }
+        let norm = self.coefficients.iter().map(|&(_, a)| a * a).sum::<f64>();
+        if norm > 0. {
+            for &(j, a) in &self.coefficients {
+                x[j] -= excess / norm * a;
+            }
+        }
+    }
+}
+
+/// Euclidean projection of $v$ onto the simplex of non-negative vectors summing to $total$.
+pub fn project_onto_simplex(v: &[f64], total: f64) -> Vec<f64> {
+    let mut sorted = v.to_vec();
+    sorted.sort_by(|a, b| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+    let mut sum = 0.;
+    let mut theta = 0.;
+    for (i, &u) in sorted.iter().enumerate() {
+        sum += u;
+        let candidate = (sum - total) / (i + 1) as f64;
+        if u - candidate > 0. {
+            theta = candidate;
+        }
+    }
+    v.iter().map(|&u| (u - theta).max(0.)).collect()
+}
+
+/// Euclidean projection of $x$ onto the intersection of the product of $simplices$ and $half_spaces$.
+/// Coordinates which do not belong to any simplex are fixed to their current value.
+/// Uses Dykstra's alternating projections if there are any half-spaces.
+pub fn project(
+    x: &[f64],
+    simplices: &[Simplex],
+    half_spaces: &[HalfSpace],
+) -> Vec<f64> {
+    let project_onto_simplices = |y: &mut Vec<f64>| {
+        for simplex in simplices {
+            let v: Vec<_> = simplex.coordinates.iter().map(|&j| y[j]).collect();
+            for (&j, u) in simplex
+                .coordinates
+                .iter()
+                .zip(project_onto_simplex(&v, simplex.total))
+            {
+                y[j] = u;
+            }
+        }
+    };
+
+    let mut y = x.to_vec();
+    if half_spaces.is_empty() {
+        project_onto_simplices(&mut y);
+        return y;
+    }
+
+    // corrections of Dykstra's algorithm for each of the sets, the product of simplices is projected onto last
+    // such that the result remains within the simplices if the intersection is empty
+    let mut corrections = vec![vec![0.; x.len()]; half_spaces.len() + 1];
+    for _ in 0..MAX_PROJECTION_ROUNDS {
+        let prev_y = y.clone();
+        for (l, correction) in corrections.iter_mut().enumerate() {
+            let z: Vec<_> = y
+                .iter()
+                .zip(correction.iter())
+                .map(|(a, b)| a + b)
+                .collect();
+            let mut projected = z.clone();
+            match half_spaces.get(l) {
+                Some(half_space) => half_space.project(&mut projected),
+                None => project_onto_simplices(&mut projected),
+            }
+            for j in 0..x.len() {
+                correction[j] = z[j] - projected[j];
+            }
+            y = projected;
+        }
+        if distance(&y, &prev_y) < CONVERGENCE {
+            break;
+        }
+    }
+    y
+}
+
+/// Minimizes a convex function $f$ over the intersection of the product of $simplices$ and $half_spaces$ using projected gradient descent with a backtracking line search.
+/// $f$ may be infinite outside of its domain, optimization begins at (the projection of) $init$ which should be within the domain.
+/// Gradients are approximated by finite differences. Returns the argmin and min.
+pub fn minimize_on_simplices(
+    f: impl Fn(&[f64]) -> f64,
+    simplices: &[Simplex],
+    half_spaces: &[HalfSpace],
+    init: &[f64],
+) -> (Vec<f64>, f64) {
+    let free: Vec<_> = simplices
+        .iter()
+        .flat_map(|simplex| simplex.coordinates.iter().copied())
+        .collect();
+    let mut x = project(init, simplices, half_spaces);
+    let mut value = f(&x);
+    if !value.is_finite() || free.is_empty() {
+        return (x, value);
+    }
+
+    let mut step = 1.;
+    for _ in 0..MAX_ITERATIONS {
+        let gradient = partial_derivatives(&f, &x, value, &free);
+        let (y, y_value) = loop {
+            let shifted: Vec<_> =
+                x.iter().zip(&gradient).map(|(a, g)| a - step * g).collect();
+            let y = project(&shifted, simplices, half_spaces);
+            if distance(&x, &y) < CONVERGENCE {
+                return (x, value);
+            }
+            let y_value = f(&y);
+            let bound = value
+                + (0..x.len())
+                    .map(|j| {
+                        let dy = y[j] - x[j];
+                        gradient[j] * dy + dy * dy / (2. * step)
+                    })
+                    .sum::<f64>();
+            if y_value.is_finite() && y_value <= bound {
+                break (y, y_value);
+            }
+            step /= 2.;
+            if step < MIN_STEP {
+                return (x, value);
+            }
+        };
+        let improvement = value - y_value;
+        x = y;
+        value = y_value;
+        if improvement <= CONVERGENCE * value.abs().max(1.) {
+            break;
+        }
+        step *= 2.;
+    }
+    (x, value)
+}
+
+/// Minimizes $\sum_k f_k(x_k)$ for convex $f_k$ such that $x$ is non-negative and sums to $total$.
+/// At the optimum, the marginal costs of all positive coordinates are equal (and do not exceed the marginal costs of all zero coordinates).
+///
+/// Beginning at (the projection of) $init$, these KKT conditions are solved by Newton's method: each step water-fills the quadratic models of all $f_k$ exactly,
+/// followed by a backtracking line search. $f_k$ may be infinite outside of its domain. If $init$ is outside of the domain,
+/// the marginal cost at the optimum is found by bisection instead, which requires many more evaluations.
+pub fn water_filling(
+    fs: &[impl Fn(f64) -> f64],
+    total: f64,
+    init: &[f64],
+) -> Vec<f64> {
+    if fs.is_empty() || total <= 0. {
+        return vec![0.; fs.len()];
+    }
+    if fs.len() == 1 {
+        return vec![total];
+    }
+    let evaluate = |xs: &[f64]| -> Vec<f64> {
+        xs.iter().enumerate().map(|(k, &x)| fs[k](x)).collect()
+    };
+
+    let mut xs = project_onto_simplex(init, total);
+    let mut values = evaluate(&xs);
+    let mut value = values.iter().sum::<f64>();
+    if !value.is_finite() {
+        return bisect_marginal_cost(fs, total);
+    }
+    let precision = WATER_FILLING_PRECISION * total;
+    for _ in 0..MAX_NEWTON_STEPS {
+        // coordinates whose derivatives cannot be approximated within the domain remain fixed
+        let (gradient, curvature): (Vec<_>, Vec<_>) = (0..fs.len())
+            .map(|k| {
+                match one_sided_derivatives(&fs[k], xs[k], values[k], total) {
+                    Some((derivative, second_derivative)) => {
+                        (derivative, second_derivative.max(MIN_CURVATURE))
+                    }
+                    None => (0., f64::INFINITY),
+                }
+            })
+            .unzip();
+        let direction: Vec<_> =
+            minimize_quadratic_on_simplex(&xs, &gradient, &curvature, total)
+                .iter()
+                .zip(&xs)
+                .map(|(y, x)| y - x)
+                .collect();
+        let length = direction.iter().map(|d| d.abs()).fold(0., f64::max);
+        if length < precision {
+            break;
+        }
+        let slope = gradient
+            .iter()
+            .zip(&direction)
+            .map(|(g, d)| g * d)
+            .sum::<f64>();
+
+        let mut step = 1.;
+        loop {
+            let ys: Vec<_> = xs
+                .iter()
+                .zip(&direction)
+                .map(|(x, d)| (x + step * d).max(0.))
+                .collect();
+            let y_values = evaluate(&ys);
+            let y_value = y_values.iter().sum::<f64>();
+            if y_value.is_finite() && y_value <= value + ARMIJO * step * slope {
+                xs = ys;
+                values = y_values;
+                value = y_value;
+                break;
+            }
+            step /= 2.;
+            if step * length < precision {
+                return xs;
+            }
+        }
+        if step * length < precision {
+            break;
+        }
+    }
+    xs
+}
+
+/// Minimizes $\sum_k g_k (y_k - x_k) + c_k (y_k - x_k)^2 / 2$ for positive (or infinite) $c_k$ such that $y$ is non-negative and sums to $total$ where $x$ sums to $total$.
+/// Coordinates with infinite curvature remain fixed.
+///
+/// The positive coordinates of the minimizer are $y_k = (\nu - b_k) / c_k$ with breakpoints $b_k = g_k - c_k x_k$ where $\nu$ is the marginal cost,
+/// which is found exactly by adding coordinates in order of their breakpoints.
+fn minimize_quadratic_on_simplex(
+    xs: &[f64],
+    gradient: &[f64],
+    curvature: &[f64],
+    total: f64,
+) -> Vec<f64> {
+    let mut free: Vec<_> = (0..xs.len())
+        .filter(|&k| curvature[k].is_finite())
+        .collect();
+    let remaining = total
+        - (0..xs.len())
+            .filter(|&k| curvature[k].is_infinite())
+            .map(|k| xs[k])
+            .sum::<f64>();
+    if free.is_empty() || remaining <= 0. {
+        return xs.to_vec();
+    }
+    let breakpoint = |k: usize| gradient[k] - curvature[k] * xs[k];
+    free.sort_by(|&k, &l| {
+        breakpoint(k)
+            .partial_cmp(&breakpoint(l))
+            .unwrap_or(Ordering::Equal)
+    });
+
+    let mut weight = 0.;
+    let mut weighted_breakpoints = 0.;
+    let mut nu = 0.;
+    for (i, &k) in free.iter().enumerate() {
+        weight += 1. / curvature[k];
+        weighted_breakpoints += breakpoint(k) / curvature[k];
+        nu = (remaining + weighted_breakpoints) / weight;
+        if free.get(i + 1).is_none_or(|&l| nu <= breakpoint(l)) {
+            break;
+        }
+    }
+    (0..xs.len())
+        .map(|k| {
+            if curvature[k].is_finite() {
+                ((nu - breakpoint(k)) / curvature[k]).max(0.)
+            } else {
+                xs[k]
+            }
+        })
+        .collect()
+}
+
+/// Minimizes $\sum_k f_k(x_k)$ for at least two convex $f_k$ such that $x$ is non-negative and sums to $total$
+/// by bisection over the marginal cost. $f_k$ may be infinite outside of its domain.
+fn bisect_marginal_cost(fs: &[impl Fn(f64) -> f64], total: f64) -> Vec<f64> {
+    let precision = WATER_FILLING_PRECISION * total;
+    let marginal_cost = |k: usize, x: f64| forward_derivative(&fs[k], x, total);
+    // largest value of each coordinate whose marginal cost does not exceed $mu$ within the given brackets
+    let fill = |mu: f64, lower: &[f64], upper: &[f64]| -> Vec<f64> {
+        (0..fs.len())
+            .map(|k| {
+                let (mut lower, mut upper) = (lower[k], upper[k]);
+                while upper - lower > precision {
+                    let middle = (lower + upper) / 2.;
+                    if marginal_cost(k, middle) <= mu {
+                        lower = middle;
+                    } else {
+                        upper = middle;
+                    }
+                }
+                lower
+            })
+            .collect()
+    };
+
+    let lowest_marginal_cost = (0..fs.len())
+        .map(|k| marginal_cost(k, 0.))
+        .fold(f64::INFINITY, f64::min);
+    if !lowest_marginal_cost.is_finite() {
+        return vec![total / fs.len() as f64; fs.len()];
+    }
+
+    // brackets of the marginal cost and the respective coordinates such that the coordinates sum to at most (and at least) $total$
+    let mut lower = lowest_marginal_cost - 1.;
+    let mut lower_xs = vec![0.; fs.len()];
+    let mut width = lowest_marginal_cost.abs().max(1.);
+    let mut upper = lowest_marginal_cost + width;
+    let mut upper_xs = fill(upper, &lower_xs, &vec![total; fs.len()]);
+    let mut doublings = 0;
+    while upper_xs.iter().sum::<f64>() < total {
+        if doublings == MAX_DOUBLINGS {
+            // the functions cannot absorb $total$ at a finite marginal cost
+            return vec![total / fs.len() as f64; fs.len()];
+        }
+        lower = upper;
+        lower_xs = upper_xs;
+        width *= 2.;
+        upper = lowest_marginal_cost + width;
+        upper_xs = fill(upper, &lower_xs, &vec![total; fs.len()]);
+        doublings += 1;
+    }
+    for _ in 0..BISECTIONS {
+        let gap = upper_xs.iter().sum::<f64>() - lower_xs.iter().sum::<f64>();
+        if gap <= precision {
+            break;
+        }
+        let middle = (lower + upper) / 2.;
+        let xs = fill(middle, &lower_xs, &upper_xs);
+        if xs.iter().sum::<f64>() >= total {
+            upper = middle;
+            upper_xs = xs;
+        } else {
+            lower = middle;
+            lower_xs = xs;
+        }
+    }
+
+    // interpolates between the brackets such that the coordinates sum to $total$
+    let lower_sum = lower_xs.iter().sum::<f64>();
+    let upper_sum = upper_xs.iter().sum::<f64>();
+    let theta = if upper_sum > lower_sum {
+        (total - lower_sum) / (upper_sum - lower_sum)
+    } else {
+        1.
+    };
+    lower_xs
+        .iter()
+        .zip(&upper_xs)
+        .map(|(l, u)| l + theta * (u - l))
+        .collect()
+}
+
+/// Minimizes $\sum_g f_g(x)$ for convex $f_g$ over the product of $simplices$ by block-coordinate water-filling,
+/// where $f_g$ only depends on the coordinates $j$ with $groups_j = g$ and each simplex contains at most one coordinate of each group.
+/// Coordinates which do not belong to any simplex are fixed to their value in $init$.
+///
+/// Each simplex is water-filled while all other coordinates are fixed, i.e. its coordinates satisfy the KKT conditions w.r.t. the others,
+/// until no coordinate changes by more than the precision of water-filling. A single simplex is solved by a single round of water-filling.
+pub fn minimize_separable_on_simplices(
+    f: impl Fn(usize, &[f64]) -> f64,
+    groups: &[usize],
+    simplices: &[Simplex],
+    init: &[f64],
+) -> Vec<f64> {
+    let mut x = project(init, simplices, &[]);
+    for _ in 0..MAX_SWEEPS {
+        let mut converged = true;
+        for simplex in simplices {
+            let zs = {
+                let x = &x;
+                let f = &f;
+                let fs: Vec<_> = simplex
+                    .coordinates
+                    .iter()
+                    .map(|&j| {
+                        move |z: f64| {
+                            let mut y = x.clone();
+                            y[j] = z;
+                            f(groups[j], &y)
+                        }
+                    })
+                    .collect();
+                let init: Vec<_> =
+                    simplex.coordinates.iter().map(|&j| x[j]).collect();
+                water_filling(&fs, simplex.total, &init)
+            };
+            for (&j, z) in simplex.coordinates.iter().zip(zs) {
+                if (z - x[j]).abs() > WATER_FILLING_PRECISION * simplex.total {
+                    converged = false;
+                }
+                x[j] = z;
+            }
+        }
+        if converged || simplices.len() <= 1 {
+            break;
+        }
+    }
+    x
+}
+
+/// Maximum norm of the difference of $x$ and $y$.
+fn distance(x: &[f64], y: &[f64]) -> f64 {
+    x.iter()
+        .zip(y)
+        .map(|(a, b)| (a - b).abs())
+        .fold(0., f64::max)
+}
@@ -1,7 +1,10 @@
 //! Abstract definition of problem variants.
 
 use crate::config::Config;
-use crate::cost::{Cost, CostFn, FailableCost, FailableCostFn, RawCost};
+use crate::cost::{
+    CacheStatistics, Cost, CostFn, FailableCost, FailableCostFn, PeakCharge,
+    RawCost,
+};
 use crate::distance::NormFn;
 use crate::model::data_center::loads::{
     apply_loads_over_time, LoadFractions, LoadProfile,
@@ -12,9 +15,10 @@ use crate::model::data_center::{
     DataCenterObjective, IntermediateObjective,
 };
 use crate::model::{ModelOutput, ModelOutputFailure, ModelOutputSuccess};
-use crate::result::Result;
+use crate::report::{CostReport, CostReportEntry};
+use crate::result::{Failure, Result};
 use crate::schedule::Schedule;
-use crate::utils::pos;
+use crate::utils::{assert, pos};
 use crate::value::Value;
 use crate::vec_wrapper::VecWrapper;
 use crate::verifiers::VerifiableProblem;
@@ -42,8 +46,8 @@ macro_rules! impl_base_problem {
         impl<'a, T, $C, $D> BaseProblem for $T
         where
             T: Value<'a>,
-            C: Clone,
-            D: Clone,
+            C: Clone + Send,
+            D: Clone + Send,
         {
             fn d(&self) -> i32 {
                 self.d
@@ -86,6 +90,57 @@ where
     /// Movement cost.
     fn movement(&self, prev_x: Config<T>, x: Config<T>, inverted: bool) -> N64;
 
+    /// Transition cost which is paid in addition to the hitting cost when moving from $prev_x$ to $x$ during time slot $t$.
+    /// Includes the final transition cost of $x$ if $t$ is the final time slot.
+    fn transition_cost(
+        &self,
+        _t: i32,
+        _prev_x: Config<T>,
+        _x: Config<T>,
+    ) -> Cost<C, D> {
+        Default::default()
+    }
+
+    /// Projects configuration $x$ onto the configurations which may follow configuration $prev_x$ during time slot $t$, i.e. whose transition cost is finite.
+    fn project(&self, _t: i32, _prev_x: Config<T>, x: Config<T>) -> Config<T> {
+        x
+    }
+
+    /// Statistics of the cache of hitting costs if hitting costs are cached.
+    fn cache_statistics(&self) -> Option<CacheStatistics> {
+        None
+    }
+
+    /// Charges on the peaks of the outputs of the hitting costs which are paid in addition to hitting, transition, and movement costs.
+    fn peak_charges(&self) -> &[PeakCharge] {
+        &[]
+    }
+
+    /// Projects all configurations of a schedule (starting from $default$) onto the configurations which may follow their predecessors.
+    fn project_schedule<'a>(
+        &self,
+        xs: &Schedule<T>,
+        default: &Config<T>,
+    ) -> Schedule<T>
+    where
+        T: Value<'a>,
+    {
+        let mut prev_x = default.clone();
+        Schedule::new(
+            (1..=xs.t_end())
+                .map(|t| {
+                    let x = self.project(
+                        t,
+                        prev_x.clone(),
+                        xs[t as usize - 1].clone(),
+                    );
+                    prev_x = x.clone();
+                    x
+                })
+                .collect(),
+        )
+    }
+
     /// Objective function. Calculates the cost of a schedule.
     fn objective_function<'a>(&self, xs: &Schedule<T>) -> Result<Cost<C, D>>
     where
@@ -168,18 +223,32 @@ where
     where
         T: Value<'a>,
     {
-        Ok(sum_over_schedule(t_end, xs, default, |t, prev_x, x| {
-            let hitting_cost = if t > self.t_end() {
-                Default::default()
+        let costs = map_over_schedule(t_end, xs, default, |t, prev_x, x| {
+            let (hitting_cost, transition_cost) = if t > self.t_end() {
+                (Default::default(), Default::default())
             } else {
-                self.hit_cost(t, x.clone())
+                (
+                    self.hit_cost(t, x.clone()),
+                    self.transition_cost(t, prev_x.clone(), x.clone()),
+                )
             };
-            Cost::new(
-                hitting_cost.cost
-                    + n64(alpha) * self.movement(prev_x, x, inverted),
-                hitting_cost.output,
-            )
-        }))
+            let movement_cost = Cost::new(
+                n64(alpha) * self.movement(prev_x, x, inverted),
+                ModelOutput::None,
+            );
+            vec![hitting_cost, transition_cost, movement_cost]
+                .into_iter()
+                .sum()
+        });
+        let outputs: Vec<_> = costs.iter().map(|cost| &cost.output).collect();
+        let peak_cost = Cost::new(
+            self.peak_charges()
+                .iter()
+                .map(|peak_charge| peak_charge.cost(&outputs))
+                .sum(),
+            ModelOutput::None,
+        );
+        Ok(costs.into_par_iter().chain(vec![peak_cost]).sum())
     }
 
     /// Movement in the decision space.
@@ -200,6 +269,109 @@ where
         )
     }
 
+    /// Movement cost of each dimension if the movement cost is separable across dimensions, i.e. sums to the movement cost.
+    fn movement_costs(
+        &self,
+        _prev_x: Config<T>,
+        _x: Config<T>,
+        _inverted: bool,
+    ) -> Option<Vec<N64>> {
+        None
+    }
+
+    /// Reports the hitting cost, transition cost, and movement cost (also of each dimension) as well as the output of the model of a schedule for each time slot.
+    /// Peak charges are not reported as they cannot be attributed to individual time slots.
+    fn cost_report<'a>(&self, xs: &Schedule<T>) -> Result<CostReport<C, D>>
+    where
+        T: Value<'a>,
+    {
+        self._cost_report(xs, 1., false)
+    }
+
+    /// Reports the constituents of the cost of a schedule for each time slot where the switching cost is paid for powering down rather than powering up.
+    fn inverted_cost_report<'a>(
+        &self,
+        xs: &Schedule<T>,
+    ) -> Result<CostReport<C, D>>
+    where
+        T: Value<'a>,
+    {
+        self._cost_report(xs, 1., true)
+    }
+
+    /// Reports the constituents of the $\alpha$-unfair cost of a schedule for each time slot.
+    fn alpha_unfair_cost_report<'a>(
+        &self,
+        xs: &Schedule<T>,
+        alpha: f64,
+    ) -> Result<CostReport<C, D>>
+    where
+        T: Value<'a>,
+    {
+        self._cost_report(xs, alpha, false)
+    }
+
+    fn _cost_report<'a>(
+        &self,
+        xs: &Schedule<T>,
+        alpha: f64,
+        inverted: bool,
+    ) -> Result<CostReport<C, D>>
+    where
+        T: Value<'a>,
+    {
+        assert(
+            xs.t_end() == self.t_end(),
+            Failure::Invalid(format!(
+                "schedule must have a value for each time step, `t_end` is {} and schedule contains {} steps",
+                self.t_end(),
+                xs.t_end()
+            )),
+        )?;
+
+        let default = self._default_config();
+        let entries = (1..=self.t_end())
+            .into_par_iter()
+            .map(|t| {
+                let prev_x = xs.get(t - 1).unwrap_or(&default).clone();
+                let x = xs.get(t).unwrap_or(&default).clone();
+                let hitting_cost = self.hit_cost(t, x.clone());
+                let transition_cost =
+                    self.transition_cost(t, prev_x.clone(), x.clone());
+                let movement_cost = n64(alpha)
+                    * self.movement(prev_x.clone(), x.clone(), inverted);
+                // if the movement cost is not separable (e.g. for the Euclidean norm),
+                // each dimension is attributed the movement cost of only moving along the dimension
+                let movement_costs = self
+                    .movement_costs(prev_x.clone(), x.clone(), inverted)
+                    .unwrap_or_else(|| {
+                        (0..self.d() as usize)
+                            .map(|k| {
+                                let mut y = prev_x.clone();
+                                y[k] = x[k];
+                                self.movement(prev_x.clone(), y, inverted)
+                            })
+                            .collect()
+                    })
+                    .into_iter()
+                    .map(|cost| n64(alpha) * cost)
+                    .collect();
+                CostReportEntry {
+                    t,
+                    hitting_cost: hitting_cost.cost,
+                    transition_cost: transition_cost.cost,
+                    movement_cost,
+                    movement_costs,
+                    output: ModelOutput::horizontal_reduce(vec![
+                        hitting_cost.output,
+                        transition_cost.output,
+                    ]),
+                }
+            })
+            .collect();
+        Ok(CostReport { entries })
+    }
+
     fn _default_config<'a>(&self) -> Config<T>
     where
         T: Value<'a>,
@@ -288,7 +460,7 @@ where
 {
     fn hit_cost(&self, t: i32, x: Config<T>) -> Cost<C, D> {
         self.hitting_cost
-            .call_mean_within_bounds(t, x, &self.bounds)
+            .call_risk_adjusted_within_bounds(t, x, &self.bounds)
     }
 
     fn movement(&self, prev_x: Config<T>, x: Config<T>, inverted: bool) -> N64 {
@@ -296,6 +468,35 @@ where
 
         (self.switching_cost)(x - prev_x)
     }
+    fn transition_cost(
+        &self,
+        t: i32,
+        prev_x: Config<T>,
+        x: Config<T>,
+    ) -> Cost<C, D> {
+        if t == self.t_end {
+            vec![
+                self.hitting_cost.call_transition(t, prev_x, x.clone()),
+                self.hitting_cost.call_final_transition(t, x),
+            ]
+            .into_iter()
+            .sum()
+        } else {
+            self.hitting_cost.call_transition(t, prev_x, x)
+        }
+    }
+
+    fn project(&self, t: i32, prev_x: Config<T>, x: Config<T>) -> Config<T> {
+        self.hitting_cost.call_projection(t, prev_x, x)
+    }
+
+    fn cache_statistics(&self) -> Option<CacheStatistics> {
+        self.hitting_cost.cache_statistics()
+    }
+
+    fn peak_charges(&self) -> &[PeakCharge] {
+        self.hitting_cost.peak_charges()
+    }
 }
 pub type IntegralSmoothedConvexOptimization<'a, C, D> =
     SmoothedConvexOptimization<'a, i32, C, D>;
@@ -331,12 +532,80 @@ where
 {
     fn hit_cost(&self, t: i32, x: Config<T>) -> Cost<C, D> {
         self.hitting_cost
-            .call_mean_within_bounds(t, x, &self.bounds)
+            .call_risk_adjusted_within_bounds(t, x, &self.bounds)
     }
 
     fn movement(&self, prev_x: Config<T>, x: Config<T>, inverted: bool) -> N64 {
         scaled_movement(&self.switching_cost, &x, &prev_x, inverted)
     }
+
+    fn movement_costs(
+        &self,
+        prev_x: Config<T>,
+        x: Config<T>,
+        inverted: bool,
+    ) -> Option<Vec<N64>> {
+        Some(scaled_movements(
+            &self.switching_cost,
+            &x,
+            &prev_x,
+            inverted,
+        ))
+    }
+
+    fn transition_cost(
+        &self,
+        t: i32,
+        prev_x: Config<T>,
+        x: Config<T>,
+    ) -> Cost<C, D> {
+        if t == self.t_end {
+            vec![
+                self.hitting_cost.call_transition(t, prev_x, x.clone()),
+                self.hitting_cost.call_final_transition(t, x),
+            ]
+            .into_iter()
+            .sum()
+        } else {
+            self.hitting_cost.call_transition(t, prev_x, x)
+        }
+    }
+
+    fn project(&self, t: i32, prev_x: Config<T>, x: Config<T>) -> Config<T> {
+        self.hitting_cost.call_projection(t, prev_x, x)
+    }
+
+    fn cache_statistics(&self) -> Option<CacheStatistics> {
+        self.hitting_cost.cache_statistics()
+    }
+
+    fn peak_charges(&self) -> &[PeakCharge] {
+        self.hitting_cost.peak_charges()
+    }
+}
+impl<'a, T, C, D> SimplifiedSmoothedConvexOptimization<'a, T, C, D>
+where
+    T: Value<'a>,
+    C: ModelOutputSuccess,
+    D: ModelOutputFailure,
+{
+    /// Number of samples of the hitting cost at time $t$, i.e. the number of scenarios it distinguishes.
+    pub fn samples(&self, t: i32, x: Config<T>) -> usize {
+        self.hitting_cost
+            .call_predictive_within_bounds(t, x, &self.bounds)
+            .len()
+    }
+
+    /// Hitting cost in the $s$-th scenario.
+    pub fn sample_hit_cost(
+        &self,
+        t: i32,
+        x: Config<T>,
+        s: usize,
+    ) -> Cost<C, D> {
+        self.hitting_cost
+            .call_sample_within_bounds(t, x, s, &self.bounds)
+    }
 }
 pub type IntegralSimplifiedSmoothedConvexOptimization<'a, C, D> =
     SimplifiedSmoothedConvexOptimization<'a, i32, C, D>;
@@ -398,7 +667,10 @@ where
                         Ok(DataCenterObjective::new(
                             safe_balancing(x, total_load, || {
                                 Ok(x * self.hitting_cost[k]
-                                    .call_mean(t, (total_load / x).raw())
+                                    .call_risk_adjusted(
+                                        t,
+                                        (total_load / x).raw(),
+                                    )
                                     .cost)
                             })?,
                             n64(0.),
@@ -406,6 +678,7 @@ where
                     })
                     .sum::<IntermediateObjective>()
             },
+            vec![],
             loads,
             1,
         )
@@ -415,6 +688,20 @@ where
     fn movement(&self, prev_x: Config<T>, x: Config<T>, inverted: bool) -> N64 {
         scaled_movement(&self.switching_cost, &x, &prev_x, inverted)
     }
+
+    fn movement_costs(
+        &self,
+        prev_x: Config<T>,
+        x: Config<T>,
+        inverted: bool,
+    ) -> Option<Vec<N64>> {
+        Some(scaled_movements(
+            &self.switching_cost,
+            &x,
+            &prev_x,
+            inverted,
+        ))
+    }
 }
 pub type IntegralSmoothedBalancedLoadOptimization<'a> =
     SmoothedBalancedLoadOptimization<'a, i32>;
@@ -473,6 +760,20 @@ where
     fn movement(&self, prev_x: Config<T>, x: Config<T>, inverted: bool) -> N64 {
         scaled_movement(&self.switching_cost, &x, &prev_x, inverted)
     }
+
+    fn movement_costs(
+        &self,
+        prev_x: Config<T>,
+        x: Config<T>,
+        inverted: bool,
+    ) -> Option<Vec<N64>> {
+        Some(scaled_movements(
+            &self.switching_cost,
+            &x,
+            &prev_x,
+            inverted,
+        ))
+    }
 }
 pub type IntegralSmoothedLoadOptimization = SmoothedLoadOptimization<i32>;
 
@@ -482,6 +783,23 @@ fn sum_over_schedule<'a, T, C, D>(
     default: &Config<T>,
     f: impl Fn(i32, Config<T>, Config<T>) -> Cost<C, D> + Send + Sync,
 ) -> Cost<C, D>
+where
+    T: Value<'a>,
+    C: ModelOutputSuccess,
+    D: ModelOutputFailure,
+{
+    map_over_schedule(t_end, xs, default, f)
+        .into_par_iter()
+        .sum()
+}
+
+/// Cost of each time slot $t \in \[t_end\]$.
+fn map_over_schedule<'a, T, C, D>(
+    t_end: i32,
+    xs: &Schedule<T>,
+    default: &Config<T>,
+    f: impl Fn(i32, Config<T>, Config<T>) -> Cost<C, D> + Send + Sync,
+) -> Vec<Cost<C, D>>
 where
     T: Value<'a>,
     C: ModelOutputSuccess,
@@ -494,7 +812,7 @@ where
             let x = xs.get(t).unwrap_or(default).clone();
             f(t, prev_x, x)
         })
-        .sum()
+        .collect()
 }
 
 /// Movement between two values (one dimensional points).
@@ -527,6 +845,21 @@ pub fn scaled_movement<'a, T>(
     prev_x: &Config<T>,
     inverted: bool,
 ) -> N64
+where
+    T: Value<'a>,
+{
+    scaled_movements(switching_cost, x, prev_x, inverted)
+        .into_iter()
+        .sum()
+}
+
+/// Movement of each dimension scaled by its switching cost.
+pub fn scaled_movements<'a, T>(
+    switching_cost: &Vec<f64>,
+    x: &Config<T>,
+    prev_x: &Config<T>,
+    inverted: bool,
+) -> Vec<N64>
 where
     T: Value<'a>,
 {
@@ -535,5 +868,5 @@ where
         .map(|&delta| -> N64 { NumCast::from(delta).unwrap() })
         .enumerate()
         .map(|(k, delta)| -> N64 { n64(switching_cost[k]) * delta })
-        .sum()
+        .collect()
 }
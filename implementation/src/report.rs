@@ -0,0 +1,199 @@
+//! Time series of the constituents of the cost of a schedule.
+
+use crate::model::{ModelOutput, ModelOutputFailure, ModelOutputSuccess};
+use noisy_float::prelude::*;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+/// Constituents of the cost of a schedule during time slot $t$.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CostReportEntry<C, D> {
+    /// Time slot.
+    pub t: i32,
+    /// Hitting cost.
+    pub hitting_cost: N64,
+    /// Transition cost which is paid in addition to the hitting cost.
+    pub transition_cost: N64,
+    /// Movement cost.
+    pub movement_cost: N64,
+    /// Movement cost of each dimension (see `Problem::movement_costs`).
+    /// If the movement cost is not separable across dimensions, this is the movement cost of only moving along the dimension which does not sum to the movement cost.
+    pub movement_costs: Vec<N64>,
+    /// Output of the model during time slot $t$.
+    pub output: ModelOutput<C, D>,
+}
+
+/// Constituents of the cost of a schedule for each time slot.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CostReport<C, D> {
+    pub entries: Vec<CostReportEntry<C, D>>,
+}
+impl<C, D> CostReport<C, D>
+where
+    C: ModelOutputSuccess,
+    D: ModelOutputFailure,
+{
+    /// Named columns of the report with a value for each time slot.
+    ///
+    /// Columns are the time slot, hitting cost, transition cost, movement cost, the movement cost of each dimension,
+    /// and all constituents which are reported by the model (see `ModelOutputSuccess::report`).
+    /// Constituents which are not reported during some time slot (e.g. due to a failure of the model) are `NaN`.
+    pub fn columns(&self) -> Vec<(String, Vec<f64>)> {
+        let d = self
+            .entries
+            .iter()
+            .map(|entry| entry.movement_costs.len())
+            .max()
+            .unwrap_or(0);
+        let mut columns: Vec<(String, Vec<f64>)> = vec![
+            (
+                "t".to_string(),
+                self.entries.iter().map(|entry| entry.t as f64).collect(),
+            ),
+            (
+                "hitting_cost".to_string(),
+                self.entries
+                    .iter()
+                    .map(|entry| entry.hitting_cost.raw())
+                    .collect(),
+            ),
+            (
+                "transition_cost".to_string(),
+                self.entries
+                    .iter()
+                    .map(|entry| entry.transition_cost.raw())
+                    .collect(),
+            ),
+            (
+                "movement_cost".to_string(),
+                self.entries
+                    .iter()
+                    .map(|entry| entry.movement_cost.raw())
+                    .collect(),
+            ),
+        ];
+        columns.extend((0..d).map(|k| {
+            (
+                format!("movement_cost.{}", k),
+                self.entries
+                    .iter()
+                    .map(|entry| {
+                        entry
+                            .movement_costs
+                            .get(k)
+                            .map_or(f64::NAN, |cost| cost.raw())
+                    })
+                    .collect(),
+            )
+        }));
+
+        let n = columns.len();
+        for (t, entry) in self.entries.iter().enumerate() {
+            if let ModelOutput::Success(output) = &entry.output {
+                for (name, value) in output.report() {
+                    let l = match columns[n..]
+                        .iter()
+                        .position(|(other_name, _)| *other_name == name)
+                    {
+                        Some(l) => n + l,
+                        None => {
+                            columns.push((
+                                name,
+                                vec![f64::NAN; self.entries.len()],
+                            ));
+                            columns.len() - 1
+                        }
+                    };
+                    columns[l].1[t] = value;
+                }
+            }
+        }
+        columns
+    }
+
+    /// Report as comma-separated values with a header and a row for each time slot.
+    pub fn to_csv(&self) -> String {
+        let columns = self.columns();
+        let mut lines = vec![columns
+            .iter()
+            .map(|(name, _)| escape_csv(name))
+            .collect::<Vec<_>>()
+            .join(",")];
+        lines.extend((0..self.entries.len()).map(|t| {
+            columns
+                .iter()
+                .map(|(_, values)| values[t].to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        }));
+        lines.join("\n") + "\n"
+    }
+
+    /// Report as a JSON object mapping each column to its values. Non-finite values are `null`.
+    pub fn to_json(&self) -> String {
+        let columns = self
+            .columns()
+            .into_iter()
+            .map(|(name, values)| {
+                format!(
+                    "{}:[{}]",
+                    escape_json(&name),
+                    values
+                        .into_iter()
+                        .map(|value| if value.is_finite() {
+                            value.to_string()
+                        } else {
+                            "null".to_string()
+                        })
+                        .collect::<Vec<_>>()
+                        .join(",")
+                )
+            })
+            .collect::<Vec<_>>();
+        format!("{{{}}}", columns.join(","))
+    }
+
+    /// Report as a Python dictionary mapping each column to a NumPy array of its values.
+    pub fn to_numpy(&self, py: Python) -> PyResult<PyObject> {
+        columns_to_numpy(py, self.columns())
+    }
+}
+
+/// Python dictionary mapping each of the named columns to a NumPy array of its values.
+pub fn columns_to_numpy(
+    py: Python,
+    columns: Vec<(String, Vec<f64>)>,
+) -> PyResult<PyObject> {
+    let numpy = py.import("numpy")?;
+    let dict = PyDict::new(py);
+    for (name, values) in columns {
+        dict.set_item(name, numpy.call_method1("array", (values,))?)?;
+    }
+    Ok(dict.into())
+}
+
+/// Quotes a field of comma-separated values if necessary.
+pub fn escape_csv(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Encodes a JSON string.
+fn escape_json(value: &str) -> String {
+    let mut result = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            c if (c as u32) < 0x20 => {
+                result.push_str(&format!("\\u{:04x}", c as u32))
+            }
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
@@ -5,8 +5,18 @@ use thiserror::Error;
 /// Interface errors.
 #[derive(Debug, Error)]
 pub enum Failure {
+    #[error("The algorithm was cancelled.")]
+    Cancelled,
+    #[error("The scenarios of two-stage stochastic programs must be sampled consistently, yet there are {scenarios} scenarios and time slot {t} has {samples} samples (must be 1 or {scenarios}).")]
+    InconsistentSamples {
+        t: i32,
+        samples: usize,
+        scenarios: usize,
+    },
     #[error("A verifier determined an invalidity: {0}")]
     Invalid(String),
+    #[error("Failed to access the layers of the graph search which were spilled to disk: {0}")]
+    LayerStorage(String),
     #[error("The given matrix must be invertible to compute the Mahalanobis distance.")]
     MatrixMustBeInvertible,
     #[error("When solving an online problem from a given time slot, the property `t_end` (current time slot) must always be one time slot ahead of the length of the obtained schedule (number of previous time slots). Yet, the number of previous time slots is {previous_time_slots} and the current time slot is {current_time_slot}.")]
@@ -19,6 +29,14 @@ pub enum Failure {
         previous_time_slots: i32,
         memory_entries: i32,
     },
+    #[error("The algorithm exceeded its time budget of {0} seconds.")]
+    TimeBudgetExceeded(f64),
+    #[error("Two-stage stochastic programs support at most {max} scenarios, yet the predicted hitting costs have {scenarios} samples.")]
+    TooManyScenarios { scenarios: usize, max: usize },
+    #[error("This algorithm does not support carbon budgets. Set `carbon_budget = None`.")]
+    UnsupportedCarbonBudget,
+    #[error("This algorithm only supports integral decisions, yet dimensions {0:?} model continuous quantities (e.g. stored energy or deferred load). Solve the fractional problem instead.")]
+    UnsupportedContinuousDimensions(Vec<usize>),
     #[error("This algorithm does not support inverted movement costs. Set `inverted = false`.")]
     UnsupportedInvertedCost,
     #[error("This algorithm does not support `L`-constrained movement. Set `l = None`.")]
@@ -27,6 +45,8 @@ pub enum Failure {
     UnsupportedPredictionWindow(i32),
     #[error("This online algorithm does not support multi-dimensional problems. Set `d = 1` (was {0}).")]
     UnsupportedProblemDimension(i32),
+    #[error("The given upper bound on the optimal cost ({0}) is smaller than the optimal cost or the problem is infeasible.")]
+    UpperBoundBelowOptimum(f64),
 }
 
 /// Wrapper type for values returned by the public interface.
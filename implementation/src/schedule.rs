@@ -112,6 +112,11 @@ where
         )
     }
 
+    /// Builds the raw (flat) encoding of the schedule (used for convex optimization).
+    pub fn to_raw(&self) -> Vec<T> {
+        self.0.iter().flat_map(|x| x.iter().cloned()).collect()
+    }
+
     /// Builds a raw (flat) encoding of a schedule (used for convex optimization) by stretching a config across the time window $w$.
     pub fn build_raw(w: i32, x: &Config<T>) -> Vec<T> {
         let raw_xs: Vec<T> = (0..w as usize)
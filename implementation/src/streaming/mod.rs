@@ -2,5 +2,6 @@
 
 pub mod offline;
 pub mod online;
+pub mod sweep;
 
 mod utils;
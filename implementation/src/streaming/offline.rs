@@ -4,7 +4,9 @@ use std::{panic, time::Instant};
 
 use crate::{
     algorithms::{
-        offline::{OfflineAlgorithm, OfflineOptions, OfflineResult},
+        offline::{
+            Certificate, OfflineAlgorithm, OfflineOptions, OfflineResult,
+        },
         Options,
     },
     cost::Cost,
@@ -20,14 +22,18 @@ use crate::{
 use backtrace::Backtrace;
 use log::{info, warn};
 
+type OfflineResponse<T, C, D> =
+    (Schedule<T>, Cost<C, D>, Option<Certificate>, u128);
+
 /// Generates problem instance from model and solves it using an offline algorithm.
+/// Returns the schedule, its cost, the certified optimality gap (if supported by the algorithm), and the runtime.
 pub fn solve<'a, T, R, P, O, A, B, C, D>(
     model: &'a impl Model<T, P, A, B, C, D>,
     alg: &impl OfflineAlgorithm<T, R, P, O, C, D>,
     options: O,
     offline_options: OfflineOptions,
     input: A,
-) -> Result<(Schedule<T>, Cost<C, D>, u128)>
+) -> Result<OfflineResponse<T, C, D>>
 where
     T: Value<'a>,
     R: OfflineResult<T>,
@@ -42,6 +48,7 @@ where
         warn!("\n\n{:?}", Backtrace::new());
     }));
 
+    model.verify()?;
     let p = model.to(input);
     info!("Generated a problem instance: {:?}", p);
     p.verify()?;
@@ -51,8 +58,16 @@ where
     let result = alg.solve(p.clone(), options, offline_options)?;
     let runtime = start.elapsed().as_millis();
 
+    let certificate = result.certificate();
     let xs = result.xs();
     let cost = p.objective_function(&xs)?;
     info!("Completed with {:?} and {:?}", cost, xs);
-    Ok((xs, cost, runtime))
+    if let Some(statistics) = p.cache_statistics() {
+        info!(
+            "Cached hitting costs with a hit rate of {:.3} ({:?}).",
+            statistics.hit_rate(),
+            statistics
+        );
+    }
+    Ok((xs, cost, certificate, runtime))
 }
@@ -107,6 +107,7 @@ where
     C: ModelOutputSuccess,
     D: ModelOutputFailure,
 {
+    model.verify()?;
     let mut p = model.to(input);
     let t_end = p.t_end() - w;
     if p.t_end() > 1 {
@@ -128,6 +129,13 @@ where
     let cost = o.p.objective_function(&xs)?;
     let int_xs = xs.to_i();
     let int_cost = o.p.objective_function(&int_xs.to())?;
+    if let Some(statistics) = o.p.cache_statistics() {
+        info!(
+            "Cached hitting costs with a hit rate of {:.3} ({:?}).",
+            statistics.hit_rate(),
+            statistics
+        );
+    }
 
     Ok((
         o,
@@ -140,6 +148,29 @@ where
     ))
 }
 
+/// Generates problem instance from model and streams online algorithm through the time horizon of the provided input without starting a backend.
+/// Returns the schedule, the integral schedule, and the latest memory of the algorithm.
+pub fn solve<'a, T, P, M, O, A, B, C, D>(
+    model: &impl Model<T, P, A, B, C, D>,
+    alg: &impl OnlineAlgorithm<'a, T, P, M, O, C, D>,
+    options: O,
+    w: i32,
+    input: A,
+) -> Result<OfflineResponse<T, C, D, M>>
+where
+    T: Value<'a>,
+    P: Problem<T, C, D> + 'a,
+    M: Memory<'a, T, P, C, D>,
+    O: Options<T, P, C, D> + 'a,
+    A: OfflineInput,
+    B: OnlineInput,
+    C: ModelOutputSuccess,
+    D: ModelOutputFailure,
+{
+    let (_, response) = prepare(model, alg, options, w, input)?;
+    Ok(response)
+}
+
 /// Starts backend server.
 #[allow(clippy::too_many_arguments)]
 fn run<'a, T, P, M, O, A, B, C, D>(
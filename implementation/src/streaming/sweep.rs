@@ -0,0 +1,535 @@
+//! Sensitivity sweeps of offline and online algorithms over the parameters of a data center model and the algorithms.
+
+use crate::{
+    algorithms::{
+        offline::{
+            multi_dimensional::{
+                approx_graph_search::{
+                    approx_graph_search, Options as ApproxGraphSearchOptions,
+                },
+                convex_optimization::co,
+            },
+            OfflineOptions,
+        },
+        online::multi_dimensional::horizon_control::{
+            averaging_fixed_horizon_control::afhc,
+            receding_horizon_control::rhc,
+        },
+    },
+    model::data_center::model::{DataCenterModel, DataCenterOfflineInput},
+    report::escape_csv,
+    result::{Failure, Result},
+    streaming::{offline, online},
+    utils::assert,
+};
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg64;
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use std::sync::Arc;
+
+/// Parameter which is varied during a sweep.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Parameter {
+    /// Time slot length $\delta$.
+    Delta,
+    /// Normalized switching cost of all server types, see `SwitchingCost::normalized_switching_cost`.
+    NormalizedSwitchingCost,
+    /// Factor by which all energy prices are scaled, see `EnergyCostModel::scale`.
+    EnergyPrice,
+    /// $\gamma > 1$ of `approx_graph_search`.
+    Gamma,
+    /// Prediction window $w$ of online algorithms. Values are rounded to the nearest integer.
+    W,
+    /// Named option of a custom solver.
+    Option(String),
+}
+impl Parameter {
+    /// Name of the parameter in the table of results.
+    pub fn name(&self) -> String {
+        match self {
+            Parameter::Delta => "delta".to_string(),
+            Parameter::NormalizedSwitchingCost => {
+                "normalized_switching_cost".to_string()
+            }
+            Parameter::EnergyPrice => "energy_price".to_string(),
+            Parameter::Gamma => "gamma".to_string(),
+            Parameter::W => "w".to_string(),
+            Parameter::Option(name) => name.clone(),
+        }
+    }
+
+    /// Verifies that some value of the parameter is admissible, i.e. $\delta > 0$, $\gamma > 1$, and $w \geq 0$.
+    pub fn verify(&self, value: f64) -> Result<()> {
+        let admissible = match self {
+            Parameter::Delta => value > 0.,
+            Parameter::Gamma => value > 1.,
+            Parameter::W => value >= 0.,
+            _ => true,
+        };
+        assert(
+            admissible && value.is_finite(),
+            Failure::Invalid(format!(
+                "value of parameter `{}` is not admissible, is {}",
+                self.name(),
+                value
+            )),
+        )
+    }
+}
+
+/// Values of the parameters at some point of a design.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Point(pub Vec<(Parameter, f64)>);
+impl Point {
+    /// Value of some parameter at this point.
+    pub fn get(&self, parameter: &Parameter) -> Option<f64> {
+        self.0
+            .iter()
+            .find(|(other, _)| other == parameter)
+            .map(|&(_, value)| value)
+    }
+
+    /// Prediction window $w$ at this point, $0$ if it is not varied.
+    pub fn w(&self) -> i32 {
+        self.get(&Parameter::W).map_or(0, |w| w.round() as i32)
+    }
+
+    /// Model with the model parameters of this point.
+    /// The time slot length is set before the normalized switching cost such that the latter is normalized with respect to the former.
+    pub fn apply(&self, model: &DataCenterModel) -> DataCenterModel {
+        let mut model = model.clone();
+        if let Some(delta) = self.get(&Parameter::Delta) {
+            model.delta = delta;
+        }
+        if let Some(normalized_switching_cost) =
+            self.get(&Parameter::NormalizedSwitchingCost)
+        {
+            model.switching_cost_model = model
+                .switching_cost_model
+                .normalized(model.delta, normalized_switching_cost);
+        }
+        if let Some(factor) = self.get(&Parameter::EnergyPrice) {
+            model.energy_cost_model = model.energy_cost_model.scale(factor);
+        }
+        model
+    }
+}
+
+/// Design of the points at which the solvers are run.
+#[derive(Clone, Debug)]
+pub enum Design {
+    /// Cartesian product of the values of each parameter.
+    Grid(Vec<(Parameter, Vec<f64>)>),
+    /// $n$ points whose parameters are sampled uniformly from their ranges using a seeded random number generator.
+    Random {
+        ranges: Vec<(Parameter, (f64, f64))>,
+        n: usize,
+        seed: u64,
+    },
+}
+impl Design {
+    /// Varied parameters.
+    pub fn parameters(&self) -> Vec<Parameter> {
+        match self {
+            Design::Grid(values) => values
+                .iter()
+                .map(|(parameter, _)| parameter.clone())
+                .collect(),
+            Design::Random { ranges, .. } => ranges
+                .iter()
+                .map(|(parameter, _)| parameter.clone())
+                .collect(),
+        }
+    }
+
+    /// Points of the design. Fails if some value (or the bound of some range) of a parameter is not admissible (see `Parameter::verify`).
+    pub fn points(&self) -> Result<Vec<Point>> {
+        let parameters = self.parameters();
+        for (k, parameter) in parameters.iter().enumerate() {
+            assert(
+                !parameters[..k].contains(parameter),
+                Failure::Invalid(format!(
+                    "parameter `{}` must be varied at most once",
+                    parameter.name()
+                )),
+            )?;
+        }
+
+        match self {
+            Design::Grid(values) => {
+                for (parameter, values) in values {
+                    for &value in values {
+                        parameter.verify(value)?;
+                    }
+                }
+                let mut points = vec![Point(vec![])];
+                for (parameter, values) in values {
+                    points = points
+                        .into_iter()
+                        .flat_map(|point| {
+                            values.iter().map(move |&value| {
+                                let mut point = point.clone();
+                                point.0.push((parameter.clone(), value));
+                                point
+                            })
+                        })
+                        .collect();
+                }
+                Ok(points)
+            }
+            Design::Random { ranges, n, seed } => {
+                for (parameter, (min, max)) in ranges {
+                    assert(
+                        min <= max,
+                        Failure::Invalid(format!(
+                            "range of parameter `{}` must not be empty, [{}, {}]",
+                            parameter.name(),
+                            min,
+                            max
+                        )),
+                    )?;
+                    parameter.verify(*min)?;
+                    parameter.verify(*max)?;
+                }
+                let mut rng = Pcg64::seed_from_u64(*seed);
+                Ok((0..*n)
+                    .map(|_| {
+                        Point(
+                            ranges
+                                .iter()
+                                .map(|(parameter, (min, max))| {
+                                    (
+                                        parameter.clone(),
+                                        rng.gen_range(*min..=*max),
+                                    )
+                                })
+                                .collect(),
+                        )
+                    })
+                    .collect())
+            }
+        }
+    }
+}
+
+/// Cost and runtime (in milliseconds) of a solver at some point.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Run {
+    pub cost: f64,
+    pub runtime: u128,
+}
+
+type SolveFn = dyn Fn(&DataCenterModel, DataCenterOfflineInput, &Point) -> Result<Run>
+    + Send
+    + Sync;
+
+/// Named solver which is run at each point of a sweep given the model and input of the point.
+#[derive(Clone)]
+pub struct Solver {
+    pub name: String,
+    pub solve: Arc<SolveFn>,
+}
+impl Solver {
+    /// Custom solver, e.g. using options which are given by `Parameter::Option`.
+    pub fn new(
+        name: &str,
+        solve: impl Fn(&DataCenterModel, DataCenterOfflineInput, &Point) -> Result<Run>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        Solver {
+            name: name.to_string(),
+            solve: Arc::new(solve),
+        }
+    }
+
+    /// Fractional offline optimum using `convex_optimization`.
+    pub fn convex_optimization() -> Self {
+        Solver::new("convex_optimization", |model, input, _| {
+            let (_, cost, _, runtime) = offline::solve(
+                model,
+                &co,
+                (),
+                OfflineOptions::default(),
+                input,
+            )?;
+            Ok(Run {
+                cost: cost.cost.raw(),
+                runtime,
+            })
+        })
+    }
+
+    /// Integral offline approximation using `approx_graph_search` with $\gamma$ given by `Parameter::Gamma` (default $1.1$).
+    pub fn approx_graph_search() -> Self {
+        Solver::new("approx_graph_search", |model, input, point| {
+            let options = point
+                .get(&Parameter::Gamma)
+                .map_or_else(ApproxGraphSearchOptions::default, |gamma| {
+                    ApproxGraphSearchOptions::new(gamma)
+                });
+            let (_, cost, _, runtime) = offline::solve(
+                model,
+                &approx_graph_search,
+                options,
+                OfflineOptions::default(),
+                input,
+            )?;
+            Ok(Run {
+                cost: cost.cost.raw(),
+                runtime,
+            })
+        })
+    }
+
+    /// Fractional online algorithm `rhc` with the prediction window given by `Parameter::W` (default $0$).
+    /// The final load profile is predicted to persist beyond the time horizon.
+    pub fn receding_horizon_control() -> Self {
+        Solver::new("receding_horizon_control", |model, input, point| {
+            let response = online::solve(
+                model,
+                &rhc,
+                (),
+                point.w(),
+                predicted(input, point.w()),
+            )?;
+            Ok(Run {
+                cost: response.xs.1.cost.raw(),
+                runtime: response.runtime,
+            })
+        })
+    }
+
+    /// Fractional online algorithm `afhc` with the prediction window given by `Parameter::W` (default $0$).
+    /// The final load profile is predicted to persist beyond the time horizon.
+    pub fn averaging_fixed_horizon_control() -> Self {
+        Solver::new("averaging_fixed_horizon_control", |model, input, point| {
+            let response = online::solve(
+                model,
+                &afhc,
+                (),
+                point.w(),
+                predicted(input, point.w()),
+            )?;
+            Ok(Run {
+                cost: response.xs.1.cost.raw(),
+                runtime: response.runtime,
+            })
+        })
+    }
+}
+
+/// Input of an online algorithm with prediction window $w$ whose schedule covers the time horizon of the offline input.
+/// Online algorithms are streamed until $w$ time slots before the end of their problem, which in turn ends $w$ time slots before the end of their input.
+/// To this end, the final load profile is repeated $2w$ times, i.e. predicted to persist beyond the horizon.
+fn predicted(
+    mut input: DataCenterOfflineInput,
+    w: i32,
+) -> DataCenterOfflineInput {
+    if let Some(load_profile) = input.loads.last().cloned() {
+        input
+            .loads
+            .extend(vec![load_profile; 2 * w.max(0) as usize]);
+    }
+    input
+}
+
+/// Sweep of solvers over the points of a design.
+#[derive(Clone)]
+pub struct Sweep {
+    /// Base model whose parameters are replaced at each point.
+    pub model: DataCenterModel,
+    pub input: DataCenterOfflineInput,
+    pub design: Design,
+    pub solvers: Vec<Solver>,
+    /// Name of the solver relative to whose cost the ratios are computed.
+    /// If `None`, ratios are relative to the minimal cost of all solvers at the same point.
+    pub reference: Option<String>,
+}
+impl Sweep {
+    pub fn new(
+        model: DataCenterModel,
+        input: DataCenterOfflineInput,
+        design: Design,
+        solvers: Vec<Solver>,
+    ) -> Self {
+        Sweep {
+            model,
+            input,
+            design,
+            solvers,
+            reference: None,
+        }
+    }
+
+    pub fn with_reference(mut self, reference: &str) -> Self {
+        self.reference = Some(reference.to_string());
+        self
+    }
+
+    /// Runs all solvers at all points of the design in parallel.
+    /// Failures of a solver at some point are recorded in the respective row rather than aborting the sweep.
+    pub fn run(&self) -> Result<SweepTable> {
+        for (i, solver) in self.solvers.iter().enumerate() {
+            assert(
+                !self.solvers[..i]
+                    .iter()
+                    .any(|other| other.name == solver.name),
+                Failure::Invalid(format!(
+                    "name of solver `{}` must be unique",
+                    solver.name
+                )),
+            )?;
+        }
+        if let Some(reference) = &self.reference {
+            assert(
+                self.solvers.iter().any(|solver| &solver.name == reference),
+                Failure::Invalid(format!(
+                    "reference solver `{}` must be swept",
+                    reference
+                )),
+            )?;
+        }
+
+        let points = self.design.points()?;
+        let runs: Vec<_> = (0..points.len())
+            .flat_map(|i| (0..self.solvers.len()).map(move |j| (i, j)))
+            .collect();
+        let results: Vec<_> = runs
+            .clone()
+            .into_par_iter()
+            .map(|(i, j)| {
+                let model = points[i].apply(&self.model);
+                let solver = &self.solvers[j];
+                (solver.solve)(&model, self.input.clone(), &points[i])
+            })
+            .collect();
+
+        let mut rows: Vec<SweepRow> = runs
+            .into_iter()
+            .zip(results)
+            .map(|((i, j), result)| {
+                let (cost, runtime, error) = match result {
+                    Ok(run) => (run.cost, Some(run.runtime), None),
+                    Err(failure) => (f64::NAN, None, Some(failure.to_string())),
+                };
+                SweepRow {
+                    point: i,
+                    parameters: points[i].clone(),
+                    solver: self.solvers[j].name.clone(),
+                    cost,
+                    ratio: f64::NAN,
+                    runtime,
+                    error,
+                }
+            })
+            .collect();
+        for i in 0..points.len() {
+            let costs = rows.iter().filter(|row| row.point == i);
+            let baseline = match &self.reference {
+                Some(reference) => costs
+                    .filter(|row| &row.solver == reference)
+                    .map(|row| row.cost)
+                    .next()
+                    .unwrap_or(f64::NAN),
+                None => costs
+                    .map(|row| row.cost)
+                    .filter(|cost| !cost.is_nan())
+                    .fold(f64::NAN, f64::min),
+            };
+            for row in rows.iter_mut().filter(|row| row.point == i) {
+                row.ratio = row.cost / baseline;
+            }
+        }
+
+        Ok(SweepTable {
+            parameters: self.design.parameters(),
+            rows,
+        })
+    }
+}
+
+/// Result of a solver at some point of a sweep.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SweepRow {
+    /// Index of the point in the design.
+    pub point: usize,
+    pub parameters: Point,
+    pub solver: String,
+    /// Cost of the obtained schedule, `NaN` if the solver failed.
+    pub cost: f64,
+    /// Ratio of the cost and the cost of the reference solver (or the minimal cost) at the same point.
+    pub ratio: f64,
+    /// Runtime in milliseconds, `None` if the solver failed.
+    pub runtime: Option<u128>,
+    /// Failure of the solver.
+    pub error: Option<String>,
+}
+
+/// Tidy table of the results of a sweep with a row for each pair of point and solver.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SweepTable {
+    /// Varied parameters.
+    pub parameters: Vec<Parameter>,
+    pub rows: Vec<SweepRow>,
+}
+impl SweepTable {
+    /// Named numeric columns of the table, i.e. the point, all varied parameters, cost, ratio, and runtime.
+    /// Missing values are `NaN`.
+    pub fn columns(&self) -> Vec<(String, Vec<f64>)> {
+        let mut columns = vec![(
+            "point".to_string(),
+            self.rows.iter().map(|row| row.point as f64).collect(),
+        )];
+        columns.extend(self.parameters.iter().map(|parameter| {
+            (
+                parameter.name(),
+                self.rows
+                    .iter()
+                    .map(|row| {
+                        row.parameters.get(parameter).unwrap_or(f64::NAN)
+                    })
+                    .collect(),
+            )
+        }));
+        columns.extend(vec![
+            (
+                "cost".to_string(),
+                self.rows.iter().map(|row| row.cost).collect(),
+            ),
+            (
+                "ratio".to_string(),
+                self.rows.iter().map(|row| row.ratio).collect(),
+            ),
+            (
+                "runtime".to_string(),
+                self.rows
+                    .iter()
+                    .map(|row| row.runtime.map_or(f64::NAN, |r| r as f64))
+                    .collect(),
+            ),
+        ]);
+        columns
+    }
+
+    /// Table as comma-separated values with a header and a row for each pair of point and solver.
+    /// In addition to the numeric columns, includes the solver and its failure.
+    pub fn to_csv(&self) -> String {
+        let columns = self.columns();
+        let mut header: Vec<_> =
+            columns.iter().map(|(name, _)| escape_csv(name)).collect();
+        header.extend(vec!["solver".to_string(), "error".to_string()]);
+        let mut lines = vec![header.join(",")];
+        lines.extend(self.rows.iter().enumerate().map(|(l, row)| {
+            let mut fields: Vec<_> = columns
+                .iter()
+                .map(|(_, values)| values[l].to_string())
+                .collect();
+            fields.push(escape_csv(&row.solver));
+            fields.push(escape_csv(row.error.as_deref().unwrap_or("")));
+            fields.join(",")
+        }));
+        lines.join("\n") + "\n"
+    }
+}
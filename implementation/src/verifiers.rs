@@ -1,6 +1,20 @@
 //! Functions to check that values satisfy the imposed constraints.
 
+use crate::algorithms::offline::Cache;
+use crate::algorithms::offline::multi_dimensional::approx_graph_search::Options as ApproxGraphSearchOptions;
+use crate::algorithms::offline::multi_dimensional::branch_and_bound_graph_search::Options as BranchAndBoundGraphSearchOptions;
+use crate::algorithms::offline::OfflineOptions;
 use crate::config::Config;
+use crate::cost::RiskMeasure;
+use crate::model::data_center::generators::{LoadPattern, PriceGenerator};
+use crate::model::data_center::model::{DataCenterModel, Location, ServerType};
+use crate::model::data_center::models::cooling::CoolingModel;
+use crate::model::data_center::models::energy_cost::DemandCharge;
+use crate::model::data_center::models::energy_storage::Battery;
+use crate::model::data_center::models::rental_cost::RentalCost;
+use crate::model::data_center::models::revenue_loss::RevenueLossModel;
+use crate::model::data_center::simulation::Job;
+use crate::numerics::TOLERANCE;
 use crate::problem::Online;
 use crate::problem::{
     SimplifiedSmoothedConvexOptimization, SmoothedBalancedLoadOptimization,
@@ -34,6 +48,7 @@ where
             self.bounds.len() == self.d as usize,
             format!("length of vector of upper bounds must equal dimension, {} != {}", self.bounds.len(), self.d),
         )?;
+        self.hitting_cost.risk_measure().verify()?;
 
         Ok(())
     }
@@ -61,6 +76,7 @@ where
             self.switching_cost.len() == self.d as usize,
             format!("length of vector of switching costs must equal dimension, {} != {}", self.switching_cost.len(), self.d),
         )?;
+        self.hitting_cost.risk_measure().verify()?;
 
         for k in 0..self.d as usize {
             assert_validity(
@@ -249,6 +265,536 @@ where
     }
 }
 
+impl OfflineOptions {
+    pub fn verify(&self) -> Result<()> {
+        if let Some(time_budget) = self.time_budget {
+            assert_validity(
+                time_budget.is_finite() && time_budget >= 0.,
+                format!(
+                    "time budget must be finite and non-negative, is {}",
+                    time_budget
+                ),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ApproxGraphSearchOptions {
+    pub fn verify(&self) -> Result<()> {
+        assert_validity(
+            self.gamma > 1.,
+            format!(
+                "gamma must be greater than 1 (i.e. the accuracy must be positive), is {}",
+                self.gamma
+            ),
+        )
+    }
+}
+
+impl Cache {
+    /// Verifies that the cache can be continued up to time slot `t_end` in a graph whose layers have the given number of vertices.
+    pub fn verify(&self, t_end: i32, vertices: usize) -> Result<()> {
+        assert_validity(
+            self.t >= 0 && self.t <= t_end,
+            format!(
+                "time slot of the cache must be in [0, {}], is {}",
+                t_end, self.t
+            ),
+        )?;
+        assert_validity(
+            self.layers.len() == self.t,
+            format!(
+                "cache must hold the layers of all {} time slots, holds {} layers",
+                self.t,
+                self.layers.len()
+            ),
+        )?;
+        assert_validity(
+            self.costs.len() == vertices,
+            format!(
+                "cache must hold the costs of all {} vertices of a layer (i.e. stem from the same values), holds {} costs",
+                vertices,
+                self.costs.len()
+            ),
+        )
+    }
+}
+
+impl BranchAndBoundGraphSearchOptions {
+    pub fn verify(&self, t_end: i32, bounds: &[i32]) -> Result<()> {
+        if let Some(hitting_cost_bounds) = &self.hitting_cost_bounds {
+            assert_validity(
+                hitting_cost_bounds.len() == t_end as usize,
+                format!("hitting cost bounds must be given for each time slot, `t_end` is {} and bounds are given for {} time slots", t_end, hitting_cost_bounds.len()),
+            )?;
+            for (t, slot_bounds) in hitting_cost_bounds.iter().enumerate() {
+                assert_validity(
+                    slot_bounds.len() == bounds.len(),
+                    format!("hitting cost bounds of time slot {} must be given for each dimension, `d` is {} and bounds are given for {} dimensions", t + 1, bounds.len(), slot_bounds.len()),
+                )?;
+                for (k, dimension_bounds) in slot_bounds.iter().enumerate() {
+                    assert_validity(
+                        dimension_bounds.len() == bounds[k] as usize + 1,
+                        format!("hitting cost bounds of time slot {} and dimension {} must be given for each value, upper bound is {} and bounds are given for {} values", t + 1, k, bounds[k], dimension_bounds.len()),
+                    )?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl RiskMeasure {
+    pub fn verify(&self) -> Result<()> {
+        if let RiskMeasure::ConditionalValueAtRisk(alpha) = self {
+            assert_validity(
+                (0. ..1.).contains(alpha),
+                format!(
+                    "level of the conditional value at risk must be in [0, 1), is {}",
+                    alpha
+                ),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl CoolingModel {
+    pub fn verify(&self, location: &Location) -> Result<()> {
+        match self {
+            CoolingModel::TemperatureDependent(models) => {
+                let model = models.get(&location.key).ok_or_else(|| {
+                    Failure::Invalid(format!(
+                        "cooling model must have parameters for location `{}`",
+                        location.key
+                    ))
+                })?;
+                assert_validity(
+                    model.pue_min >= 1.,
+                    format!(
+                        "minimal PUE of location `{}` must be at least 1, is {}",
+                        location.key, model.pue_min
+                    ),
+                )?;
+                assert_validity(
+                    model.load_factor >= 0. && model.temperature_factor >= 0.,
+                    format!(
+                        "load and temperature factors of the cooling model of location `{}` must be non-negative",
+                        location.key
+                    ),
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Battery {
+    pub fn verify(&self, location: &str) -> Result<()> {
+        assert_validity(
+            self.efficiency > 0. && self.efficiency <= 1.,
+            format!(
+                "efficiency of the battery at location `{}` must be in (0, 1], is {}",
+                location, self.efficiency
+            ),
+        )?;
+        assert_validity(
+            self.capacity.is_finite(),
+            format!(
+                "capacity of the battery at location `{}` must be finite, is {}",
+                location, self.capacity
+            ),
+        )?;
+        assert_validity(
+            self.capacity >= 0.
+                && self.charge_rate >= 0.
+                && self.discharge_rate >= 0.,
+            format!(
+                "capacity, charge rate, and discharge rate of the battery at location `{}` must be non-negative",
+                location
+            ),
+        )?;
+        assert_validity(
+            self.degradation_cost > 0.,
+            format!(
+                "degradation cost of the battery at location `{}` must be positive, is {}",
+                location, self.degradation_cost
+            ),
+        )
+    }
+}
+
+impl RevenueLossModel {
+    pub fn verify(&self) -> Result<()> {
+        if let RevenueLossModel::TailLatency(models) = self {
+            for (key, model) in models {
+                assert_validity(
+                    (0. ..1.).contains(&model.percentile),
+                    format!(
+                        "percentile of the tail latency of job type `{}` must be in [0, 1), is {}",
+                        key, model.percentile
+                    ),
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl DataCenterModel {
+    pub fn verify(&self) -> Result<()> {
+        self.risk_measure.verify()?;
+        self.revenue_loss_model.verify()?;
+        if let Some(resolution) = self.cache_resolution {
+            assert_validity(
+                resolution > 0.,
+                format!(
+                    "resolution of the cache must be positive, is {}",
+                    resolution
+                ),
+            )?;
+        }
+        if let Some(epsilon) = self.chance_constraint {
+            assert_validity(
+                (0. ..1.).contains(&epsilon),
+                format!("chance constraint must be in [0, 1), is {}", epsilon),
+            )?;
+            assert_validity(
+                self.job_types
+                    .iter()
+                    .all(|job_type| job_type.slack.unwrap_or(0) <= 0),
+                "chance constraints do not support deferrable jobs".to_string(),
+            )?;
+        }
+        for job_type in &self.job_types {
+            if let Some(affinity) = self.affinities.get(&job_type.key) {
+                assert_validity(
+                    self.locations.iter().any(|location| {
+                        self.server_types.iter().any(|server_type| {
+                            location
+                                .m
+                                .get(&server_type.key)
+                                .is_some_and(|&m| m > 0)
+                                && affinity.allows(location, server_type)
+                        })
+                    }),
+                    format!(
+                        "job type `{}` must be allowed on at least one server",
+                        job_type.key
+                    ),
+                )?;
+            }
+            for (resource, &demand) in &job_type.demands {
+                assert_validity(
+                    demand >= 0.,
+                    format!(
+                        "demand of job type `{}` for resource `{}` must be non-negative",
+                        job_type.key, resource
+                    ),
+                )?;
+            }
+        }
+        if let Some(cooling_model) = &self.cooling_model {
+            for location in &self.locations {
+                cooling_model.verify(location)?;
+            }
+        }
+        for location in &self.locations {
+            if let Some(demand_charge) = &location.demand_charge {
+                demand_charge.verify(&location.key)?;
+            }
+        }
+        for (location, battery) in &self.batteries {
+            assert_validity(
+                self.locations.iter().any(|l| &l.key == location),
+                format!("location `{}` of battery must exist", location),
+            )?;
+            battery.verify(location)?;
+        }
+        if let Some(rental_cost_model) = &self.rental_cost_model {
+            for server_type in &self.server_types {
+                if let Some(model) = rental_cost_model.model(server_type) {
+                    model.verify(self, server_type)?;
+                }
+            }
+        }
+        for server_type in &self.server_types {
+            assert_validity(
+                server_type.boot_delay >= 0,
+                format!(
+                    "boot delay of server type `{}` must be non-negative, is {}",
+                    server_type.key, server_type.boot_delay
+                ),
+            )?;
+            for (resource, &capacity) in &server_type.capacities {
+                assert_validity(
+                    capacity >= 0.,
+                    format!(
+                        "capacity of server type `{}` for resource `{}` must be non-negative",
+                        server_type.key, resource
+                    ),
+                )?;
+            }
+            assert_validity(
+                server_type.utilization_weights.values().all(|&w| w >= 0.)
+                    && server_type.utilization_weights.values().sum::<f64>()
+                        <= 1.,
+                format!(
+                    "utilization weights of server type `{}` must be non-negative and sum to at most 1",
+                    server_type.key
+                ),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl DemandCharge {
+    pub fn verify(&self, location: &str) -> Result<()> {
+        assert_validity(
+            self.price >= 0.,
+            format!(
+                "price of the demand charge of location `{}` must be non-negative, is {}",
+                location, self.price
+            ),
+        )?;
+        assert_validity(
+            self.window >= 1,
+            format!(
+                "billing window of the demand charge of location `{}` must be positive, is {}",
+                location, self.window
+            ),
+        )
+    }
+}
+
+impl RentalCost {
+    pub fn verify(
+        &self,
+        model: &DataCenterModel,
+        server_type: &ServerType,
+    ) -> Result<()> {
+        assert_validity(
+            self.on_demand_price >= 0. && self.reserved_price >= 0.,
+            format!(
+                "prices of rented server type `{}` must be non-negative",
+                server_type.key
+            ),
+        )?;
+        assert_validity(
+            self.billing_period >= 1,
+            format!(
+                "billing period of rented server type `{}` must be positive, is {}",
+                server_type.key, self.billing_period
+            ),
+        )?;
+        assert_validity(
+            self.minimum_term >= 1,
+            format!(
+                "minimum term of rented server type `{}` must be positive, is {}",
+                server_type.key, self.minimum_term
+            ),
+        )?;
+        for (key, &reserved) in &self.reserved {
+            let location =
+                model.locations.iter().find(|location| &location.key == key);
+            assert_validity(
+                location.is_some(),
+                format!(
+                    "location `{}` of reserved instances of server type `{}` must exist",
+                    key, server_type.key
+                ),
+            )?;
+            let m = *location.unwrap().m.get(&server_type.key).unwrap_or(&0);
+            assert_validity(
+                0 <= reserved && reserved <= m,
+                format!(
+                    "reserved instances of server type `{}` at location `{}` must be in [0, {}], is {}",
+                    server_type.key, key, m, reserved
+                ),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl DataCenterModel {
+    /// Verifies that no instances of rented server types are reserved, which is not supported by load optimization.
+    pub fn verify_on_demand(&self) -> Result<()> {
+        if let Some(rental_cost_model) = &self.rental_cost_model {
+            for server_type in &self.server_types {
+                if let Some(model) = rental_cost_model.model(server_type) {
+                    assert_validity(
+                        model.reserved.values().all(|&r| r == 0),
+                        format!(
+                            "load optimization does not support reserved instances of server type `{}`",
+                            server_type.key
+                        ),
+                    )?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Job {
+    pub fn verify(&self, model: &DataCenterModel, t_end: i32) -> Result<()> {
+        assert_validity(
+            model.sources.iter().any(|source| source.key == self.source),
+            format!("source `{}` of job must exist", self.source),
+        )?;
+        assert_validity(
+            model
+                .job_types
+                .iter()
+                .any(|job_type| job_type.key == self.job_type),
+            format!("job type `{}` of job must exist", self.job_type),
+        )?;
+        assert_validity(
+            self.size > 0.,
+            format!("size of job must be positive (was {})", self.size),
+        )?;
+        assert_validity(
+            self.arrival >= 0. && self.time_slot(model.delta) <= t_end,
+            format!(
+                "arrival of job must be within the time horizon (was {})",
+                self.arrival
+            ),
+        )?;
+
+        Ok(())
+    }
+}
+
+impl LoadPattern {
+    pub fn verify(&self) -> Result<()> {
+        match self {
+            LoadPattern::Diurnal { period, noise, .. } => {
+                assert_validity(
+                    *period > 0.,
+                    format!("period must be positive, is {}", period),
+                )?;
+                assert_validity(
+                    *noise >= 0.,
+                    format!("noise must be non-negative, is {}", noise),
+                )?;
+            }
+            LoadPattern::Mmpp { rates, transitions } => {
+                assert_validity(
+                    !rates.is_empty(),
+                    "at least one state is required".to_string(),
+                )?;
+                assert_validity(
+                    transitions.len() == rates.len(),
+                    format!("number of rows of transition matrix must equal number of states, {} != {}", transitions.len(), rates.len()),
+                )?;
+                for (i, (&rate, row)) in
+                    rates.iter().zip(transitions).enumerate()
+                {
+                    assert_validity(
+                        rate >= 0.,
+                        format!(
+                            "rate of state {} must be non-negative, is {}",
+                            i + 1,
+                            rate
+                        ),
+                    )?;
+                    assert_validity(
+                        row.len() == rates.len()
+                            && row.iter().all(|&p| p >= 0.)
+                            && (row.iter().sum::<f64>() - 1.).abs() < TOLERANCE,
+                        format!(
+                            "transition probabilities of state {} must be a distribution over all states",
+                            i + 1
+                        ),
+                    )?;
+                }
+            }
+            LoadPattern::FlashCrowd {
+                base,
+                probability,
+                magnitude,
+                decay,
+                noise,
+            } => {
+                assert_validity(
+                    *base >= 0.,
+                    format!("base load must be non-negative, is {}", base),
+                )?;
+                assert_validity(
+                    (0. ..=1.).contains(probability),
+                    format!(
+                        "probability must be in [0, 1], is {}",
+                        probability
+                    ),
+                )?;
+                assert_validity(
+                    *magnitude >= 0.,
+                    format!("magnitude must be non-negative, is {}", magnitude),
+                )?;
+                assert_validity(
+                    (0. ..1.).contains(decay),
+                    format!("decay must be in [0, 1), is {}", decay),
+                )?;
+                assert_validity(
+                    *noise >= 0.,
+                    format!("noise must be non-negative, is {}", noise),
+                )?;
+            }
+            LoadPattern::TrendSeasonal { seasons, noise, .. } => {
+                for (k, (_, period)) in seasons.iter().enumerate() {
+                    assert_validity(
+                        *period > 0.,
+                        format!(
+                            "period of season {} must be positive, is {}",
+                            k + 1,
+                            period
+                        ),
+                    )?;
+                }
+                assert_validity(
+                    *noise >= 0.,
+                    format!("noise must be non-negative, is {}", noise),
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl PriceGenerator {
+    pub fn verify(&self) -> Result<()> {
+        assert_validity(
+            self.mean.is_finite(),
+            format!("mean price must be finite, is {}", self.mean),
+        )?;
+        assert_validity(
+            self.std_dev >= 0.,
+            format!(
+                "standard deviation of prices must be non-negative, is {}",
+                self.std_dev
+            ),
+        )?;
+        assert_validity(
+            (-1. ..=1.).contains(&self.correlation),
+            format!("correlation must be in [-1, 1], is {}", self.correlation),
+        )?;
+
+        Ok(())
+    }
+}
+
 impl<'a, T> Config<T>
 where
     T: Value<'a>,
@@ -1,20 +1,23 @@
 #[cfg(test)]
 mod approx_graph_search {
     use crate::{
-        factories::{penalize_zero, random},
+        factories::{moving_paraboloid, penalize_zero, random},
         init,
     };
     use rand::prelude::*;
     use rand_pcg::Pcg64;
     use soco::{
         algorithms::offline::{
-            multi_dimensional::approx_graph_search::{
-                approx_graph_search, Options,
+            multi_dimensional::{
+                approx_graph_search::{approx_graph_search, Options},
+                optimal_graph_search::optimal_graph_search,
             },
-            CachedPath, OfflineAlgorithm, OfflineOptions,
+            CachedPath, Certificate, OfflineAlgorithm, OfflineOptions,
+            OfflineResult,
         },
         config::Config,
         problem::{Problem, SimplifiedSmoothedConvexOptimization},
+        result::Failure,
         schedule::Schedule,
         verifiers::VerifiableProblem,
     };
@@ -139,7 +142,7 @@ mod approx_graph_search {
         };
         p.verify().unwrap();
 
-        let CachedPath { path, cache } = approx_graph_search
+        let CachedPath { path, cache, .. } = approx_graph_search
             .solve(p.clone(), Options::new(2.), OfflineOptions::default())
             .unwrap();
         path.xs.verify(p.t_end, &p.bounds).unwrap();
@@ -157,7 +160,7 @@ mod approx_graph_search {
                 p.clone(),
                 Options {
                     cache: Some(cache),
-                    gamma: 2.,
+                    ..Options::new(2.)
                 },
                 OfflineOptions::default(),
             )
@@ -169,4 +172,81 @@ mod approx_graph_search {
             p.objective_function(&path.xs).unwrap().cost.raw()
         );
     }
+
+    #[test]
+    fn _5() {
+        init();
+
+        let p = SimplifiedSmoothedConvexOptimization {
+            d: 2,
+            t_end: 20,
+            bounds: vec![8, 8],
+            switching_cost: vec![1., 3.],
+            hitting_cost: moving_paraboloid(8),
+        };
+        p.verify().unwrap();
+
+        let result = approx_graph_search
+            .solve(
+                p.clone(),
+                Options::with_accuracy(0.5),
+                OfflineOptions::default(),
+            )
+            .unwrap();
+        let optimal_result = optimal_graph_search
+            .solve_with_default_options(p.clone(), OfflineOptions::default())
+            .unwrap();
+
+        let certificate = result.certificate().unwrap();
+        assert_abs_diff_eq!(certificate.cost, result.path.cost);
+        assert!(certificate.ratio() <= 1.5 + 1e-9);
+        assert!(certificate.lower_bound <= optimal_result.path.cost);
+        assert!(optimal_result.path.cost <= certificate.cost);
+        assert_abs_diff_eq!(optimal_result.certificate().unwrap().gap(), 0.);
+
+        // degenerate certificates
+        for cost in [0., f64::INFINITY] {
+            assert_eq!(Certificate::optimal(cost).gap(), 0.);
+            assert_eq!(Certificate::optimal(cost).ratio(), 1.);
+        }
+        for lower_bound in [0., 1.] {
+            let certificate = Certificate {
+                cost: f64::INFINITY,
+                lower_bound,
+            };
+            assert_eq!(certificate.gap(), f64::INFINITY);
+            assert_eq!(certificate.ratio(), f64::INFINITY);
+        }
+        let certificate = Certificate {
+            cost: 1.,
+            lower_bound: 0.,
+        };
+        assert_eq!(certificate.gap(), 1.);
+        assert_eq!(certificate.ratio(), f64::INFINITY);
+    }
+
+    #[test]
+    fn _6() {
+        init();
+
+        let p = SimplifiedSmoothedConvexOptimization {
+            d: 1,
+            t_end: 2,
+            bounds: vec![8],
+            switching_cost: vec![1.],
+            hitting_cost: moving_paraboloid(8),
+        };
+        p.verify().unwrap();
+
+        for options in [Options::with_accuracy(0.), Options::new(0.5)] {
+            assert!(matches!(
+                approx_graph_search.solve(
+                    p.clone(),
+                    options,
+                    OfflineOptions::default()
+                ),
+                Err(Failure::Invalid(_))
+            ));
+        }
+    }
 }
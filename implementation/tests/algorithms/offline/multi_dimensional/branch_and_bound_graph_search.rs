@@ -0,0 +1,148 @@
+#[cfg(test)]
+mod branch_and_bound_graph_search {
+    use crate::factories::{moving_paraboloid, random};
+    use crate::init;
+    use soco::algorithms::offline::multi_dimensional::branch_and_bound_graph_search::{
+        branch_and_bound_graph_search, relaxed_hitting_cost_bounds, Options,
+    };
+    use soco::algorithms::offline::multi_dimensional::optimal_graph_search::optimal_graph_search;
+    use soco::algorithms::offline::{
+        Certificate, OfflineAlgorithm, OfflineOptions, OfflineResult,
+    };
+    use soco::problem::{Problem, SimplifiedSmoothedConvexOptimization};
+    use soco::result::Failure;
+    use soco::verifiers::VerifiableProblem;
+
+    #[test]
+    fn _1() {
+        init();
+
+        let p = SimplifiedSmoothedConvexOptimization {
+            d: 3,
+            t_end: 10,
+            bounds: vec![3, 2, 4],
+            switching_cost: vec![2., 1.5, 1.],
+            hitting_cost: random(),
+        };
+        p.verify().unwrap();
+
+        let result = branch_and_bound_graph_search
+            .solve_with_default_options(p.clone(), OfflineOptions::default())
+            .unwrap();
+        result.path.xs.verify(p.t_end, &p.bounds).unwrap();
+        let optimal_result = optimal_graph_search
+            .solve_with_default_options(p.clone(), OfflineOptions::default())
+            .unwrap();
+
+        assert_eq!(result.expanded + result.pruned, 10 * 4 * 3 * 5);
+        assert_eq!(result.dense_layers, 0);
+        assert_abs_diff_eq!(result.path.cost, optimal_result.path.cost);
+        // the default bounds are admissible
+        assert_eq!(
+            result.certificate(),
+            Some(Certificate::optimal(result.path.cost))
+        );
+        assert_relative_eq!(
+            result.path.cost,
+            p.objective_function(&result.path.xs).unwrap().cost.raw(),
+            max_relative = 1e-4
+        );
+    }
+
+    #[test]
+    fn _2() {
+        init();
+
+        let p = SimplifiedSmoothedConvexOptimization {
+            d: 3,
+            t_end: 10,
+            bounds: vec![5, 5, 5],
+            switching_cost: vec![2., 1.5, 1.],
+            hitting_cost: moving_paraboloid(5),
+        };
+        p.verify().unwrap();
+        let relaxed_p = SimplifiedSmoothedConvexOptimization {
+            d: 3,
+            t_end: 10,
+            bounds: vec![5., 5., 5.],
+            switching_cost: vec![2., 1.5, 1.],
+            hitting_cost: moving_paraboloid(5),
+        };
+
+        let hitting_cost_bounds = relaxed_hitting_cost_bounds(&relaxed_p)
+            .into_iter()
+            .map(|bounds| {
+                bounds
+                    .into_iter()
+                    .map(|bounds| {
+                        bounds.into_iter().map(|b| b - 1e-3).collect()
+                    })
+                    .collect()
+            })
+            .collect();
+        let result = branch_and_bound_graph_search
+            .solve(
+                p.clone(),
+                Options {
+                    upper_bound: None,
+                    hitting_cost_bounds: Some(hitting_cost_bounds),
+                },
+                OfflineOptions::default(),
+            )
+            .unwrap();
+        result.path.xs.verify(p.t_end, &p.bounds).unwrap();
+        let inv_result = branch_and_bound_graph_search
+            .solve_with_default_options(p.clone(), OfflineOptions::inverted())
+            .unwrap();
+        let optimal_result = optimal_graph_search
+            .solve_with_default_options(p.clone(), OfflineOptions::default())
+            .unwrap();
+
+        assert!(result.pruned > 0);
+        assert_abs_diff_eq!(result.path.cost, optimal_result.path.cost);
+        // relaxed bounds are not guaranteed to be admissible
+        assert!(result.lower_bound < result.path.cost);
+        assert!(result.lower_bound <= optimal_result.path.cost);
+        assert_abs_diff_eq!(result.path.cost, inv_result.path.cost);
+        assert_relative_eq!(
+            result.path.cost,
+            p.objective_function(&result.path.xs).unwrap().cost.raw(),
+            max_relative = 1e-4
+        );
+    }
+
+    #[test]
+    fn _3() {
+        init();
+
+        let p = SimplifiedSmoothedConvexOptimization {
+            d: 2,
+            t_end: 2,
+            bounds: vec![2, 1],
+            switching_cost: vec![1., 1.],
+            hitting_cost: random(),
+        };
+        p.verify().unwrap();
+
+        let solve = |hitting_cost_bounds| {
+            branch_and_bound_graph_search.solve(
+                p.clone(),
+                Options {
+                    upper_bound: None,
+                    hitting_cost_bounds: Some(hitting_cost_bounds),
+                },
+                OfflineOptions::default(),
+            )
+        };
+        let valid = vec![vec![vec![0.; 3], vec![0.; 2]]; 2];
+        assert!(solve(valid.clone()).is_ok());
+        // bounds must be given for each time slot, dimension, and value
+        for invalid in [
+            valid[..1].to_vec(),
+            vec![vec![vec![0.; 3]]; 2],
+            vec![vec![vec![0.; 3], vec![0.; 1]]; 2],
+        ] {
+            assert!(matches!(solve(invalid), Err(Failure::Invalid(_))));
+        }
+    }
+}
@@ -137,4 +137,30 @@ mod co {
         result.verify(p.t_end, &upper_bounds(&p.bounds)).unwrap();
         assert!(p.total_movement(&result, false).unwrap().raw() <= l + epsilon);
     }
+
+    #[test]
+    fn _5() {
+        init();
+
+        let p = SmoothedConvexOptimization {
+            d: 2,
+            t_end: 10,
+            bounds: vec![(0., 2.), (0., 1.)],
+            switching_cost: manhattan_scaled(vec![1.5, 1.]),
+            hitting_cost: inv_e(),
+        };
+        p.verify().unwrap();
+
+        let result = co
+            .solve_with_default_options(
+                p.clone(),
+                OfflineOptions::time_limited(0.),
+            )
+            .unwrap();
+        assert!(result.certificate().is_none());
+        let xs = result.xs();
+        xs.verify(p.t_end, &upper_bounds(&p.bounds)).unwrap();
+
+        assert!(p.objective_function(&xs).unwrap().cost.raw().is_finite());
+    }
 }
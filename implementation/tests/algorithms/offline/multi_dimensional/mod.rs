@@ -1,6 +1,8 @@
 #[cfg(test)]
 mod approx_graph_search;
 #[cfg(test)]
+mod branch_and_bound_graph_search;
+#[cfg(test)]
 mod convex_optimization;
 #[cfg(test)]
 mod optimal_graph_search;
@@ -8,3 +10,5 @@ mod optimal_graph_search;
 mod static_fractional;
 #[cfg(test)]
 mod static_integral;
+#[cfg(test)]
+mod stochastic_convex_optimization;
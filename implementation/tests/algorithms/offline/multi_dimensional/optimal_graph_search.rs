@@ -1,17 +1,21 @@
 #[cfg(test)]
 mod optimal_graph_search {
-    use crate::factories::{penalize_zero, random};
+    use crate::factories::{constant, penalize_zero, random};
     use crate::init;
     use rand::prelude::*;
     use rand_pcg::Pcg64;
     use soco::algorithms::offline::multi_dimensional::optimal_graph_search::optimal_graph_search;
     use soco::algorithms::offline::multi_dimensional::optimal_graph_search::Options;
-    use soco::algorithms::offline::CachedPath;
-    use soco::algorithms::offline::{OfflineAlgorithm, OfflineOptions};
+    use soco::algorithms::offline::{Cache, CachedPath};
+    use soco::algorithms::offline::{
+        CancellationToken, OfflineAlgorithm, OfflineOptions, ProgressCallback,
+    };
     use soco::config::Config;
     use soco::problem::{Problem, SimplifiedSmoothedConvexOptimization};
+    use soco::result::Failure;
     use soco::schedule::Schedule;
     use soco::verifiers::VerifiableProblem;
+    use std::sync::{Arc, Mutex};
 
     #[test]
     fn _1() {
@@ -145,7 +149,7 @@ mod optimal_graph_search {
         };
         p.verify().unwrap();
 
-        let CachedPath { path, cache } = optimal_graph_search
+        let CachedPath { path, cache, .. } = optimal_graph_search
             .solve_with_default_options(p.clone(), OfflineOptions::default())
             .unwrap();
         path.xs.verify(p.t_end, &p.bounds).unwrap();
@@ -162,7 +166,10 @@ mod optimal_graph_search {
         let CachedPath { path, .. } = optimal_graph_search
             .solve(
                 p.clone(),
-                Options { cache: Some(cache) },
+                Options {
+                    cache: Some(cache),
+                    ..Options::default()
+                },
                 OfflineOptions::default(),
             )
             .unwrap();
@@ -174,4 +181,241 @@ mod optimal_graph_search {
             max_relative = 1e-4
         );
     }
+
+    #[test]
+    fn _5() {
+        init();
+
+        let d = 3;
+        let t_end = 10;
+        let p = SimplifiedSmoothedConvexOptimization {
+            d,
+            t_end,
+            bounds: vec![3, 2, 4],
+            switching_cost: vec![2., 1.5, 1.],
+            hitting_cost: random(),
+        };
+        p.verify().unwrap();
+
+        let CachedPath { path, .. } = optimal_graph_search
+            .solve_with_default_options(p.clone(), OfflineOptions::default())
+            .unwrap();
+        let spill_dir = std::env::temp_dir()
+            .join(format!("soco_optimal_graph_search_{}", std::process::id()));
+        let CachedPath {
+            path: spilled_path, ..
+        } = optimal_graph_search
+            .solve(
+                p.clone(),
+                Options {
+                    spill_dir: Some(spill_dir.clone()),
+                    ..Options::default()
+                },
+                OfflineOptions::default(),
+            )
+            .unwrap();
+        // spilled layers are removed once the result is dropped
+        assert_eq!(std::fs::read_dir(&spill_dir).unwrap().count(), 0);
+        spilled_path.xs.verify(p.t_end, &p.bounds).unwrap();
+
+        // deserialized caches neither own nor remove the spilled layers
+        let CachedPath { cache, .. } = optimal_graph_search
+            .solve(
+                p.clone(),
+                Options {
+                    spill_dir: Some(spill_dir.clone()),
+                    ..Options::default()
+                },
+                OfflineOptions::default(),
+            )
+            .unwrap();
+        let deserialized_cache: Cache =
+            bincode::deserialize(&bincode::serialize(&cache).unwrap()).unwrap();
+        drop(deserialized_cache);
+        assert_eq!(std::fs::read_dir(&spill_dir).unwrap().count(), 1);
+        assert!(cache.layers.get(t_end).is_ok());
+        drop(cache);
+        assert_eq!(std::fs::read_dir(&spill_dir).unwrap().count(), 0);
+        std::fs::remove_dir(&spill_dir).unwrap();
+
+        assert_eq!(path.xs, spilled_path.xs);
+        assert_abs_diff_eq!(path.cost, spilled_path.cost);
+        assert_relative_eq!(
+            spilled_path.cost,
+            p.objective_function(&spilled_path.xs).unwrap().cost.raw(),
+            max_relative = 1e-4
+        );
+    }
+
+    #[test]
+    fn _6() {
+        init();
+
+        let p = SimplifiedSmoothedConvexOptimization {
+            d: 2,
+            t_end: 10,
+            bounds: vec![3, 2],
+            switching_cost: vec![2., 1.5],
+            hitting_cost: random(),
+        };
+        p.verify().unwrap();
+
+        let reports = Arc::new(Mutex::new(vec![]));
+        let CachedPath { path, .. } = optimal_graph_search
+            .solve_with_default_options(
+                p.clone(),
+                OfflineOptions {
+                    progress: Some(ProgressCallback::new({
+                        let reports = reports.clone();
+                        move |progress| reports.lock().unwrap().push(progress)
+                    })),
+                    ..OfflineOptions::default()
+                },
+            )
+            .unwrap();
+        let reports = reports.lock().unwrap();
+        assert_eq!(reports.len(), p.t_end as usize);
+        for (t, progress) in reports.iter().enumerate() {
+            assert_eq!(progress.step, t as i32 + 1);
+            assert_eq!(progress.steps, p.t_end);
+            assert!(progress.cost.unwrap() <= path.cost + 1e-9);
+        }
+
+        let cancellation = CancellationToken::new();
+        cancellation.cancel();
+        assert!(matches!(
+            optimal_graph_search.solve_with_default_options(
+                p.clone(),
+                OfflineOptions::cancellable(cancellation),
+            ),
+            Err(Failure::Cancelled)
+        ));
+    }
+
+    #[test]
+    fn _7() {
+        init();
+
+        let p = SimplifiedSmoothedConvexOptimization {
+            d: 2,
+            t_end: 10,
+            bounds: vec![3, 2],
+            switching_cost: vec![2., 1.5],
+            hitting_cost: random(),
+        };
+        p.verify().unwrap();
+
+        let CachedPath { path, .. } = optimal_graph_search
+            .solve_with_default_options(p.clone(), OfflineOptions::default())
+            .unwrap();
+        for time_budget in [60., f64::MAX] {
+            let CachedPath {
+                path: limited_path, ..
+            } = optimal_graph_search
+                .solve_with_default_options(
+                    p.clone(),
+                    OfflineOptions::time_limited(time_budget),
+                )
+                .unwrap();
+            assert_eq!(path.xs, limited_path.xs);
+        }
+
+        assert!(matches!(
+            optimal_graph_search.solve_with_default_options(
+                p.clone(),
+                OfflineOptions::time_limited(0.),
+            ),
+            Err(Failure::TimeBudgetExceeded(time_budget)) if time_budget == 0.
+        ));
+        for time_budget in [-1., f64::INFINITY, f64::NAN] {
+            assert!(matches!(
+                optimal_graph_search.solve_with_default_options(
+                    p.clone(),
+                    OfflineOptions::time_limited(time_budget),
+                ),
+                Err(Failure::Invalid(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn _8() {
+        init();
+
+        let mut p = SimplifiedSmoothedConvexOptimization {
+            d: 2,
+            t_end: 5,
+            bounds: vec![3, 2],
+            switching_cost: vec![2., 1.5],
+            hitting_cost: random(),
+        };
+        p.verify().unwrap();
+        let continue_with =
+            |p: SimplifiedSmoothedConvexOptimization<_, _, _>, cache: Cache| {
+                optimal_graph_search.solve(
+                    p,
+                    Options {
+                        cache: Some(cache),
+                        ..Options::default()
+                    },
+                    OfflineOptions::default(),
+                )
+            };
+
+        let spill_dir = std::env::temp_dir().join(format!(
+            "soco_optimal_graph_search_continued_{}",
+            std::process::id()
+        ));
+        let CachedPath { cache, .. } = optimal_graph_search
+            .solve(
+                p.clone(),
+                Options {
+                    spill_dir: Some(spill_dir.clone()),
+                    ..Options::default()
+                },
+                OfflineOptions::default(),
+            )
+            .unwrap();
+
+        // caches of other values or with missing layers are rejected
+        let mut q = p.clone();
+        q.bounds = vec![3, 3];
+        assert!(matches!(
+            continue_with(q, cache.clone()),
+            Err(Failure::Invalid(_))
+        ));
+        assert!(matches!(
+            continue_with(
+                p.clone(),
+                Cache {
+                    t: cache.t + 1,
+                    ..cache.clone()
+                }
+            ),
+            Err(Failure::Invalid(_))
+        ));
+
+        // continuations of the same spilled cache do not overwrite the layers of each other
+        p.t_end = 10;
+        let CachedPath { path, .. } = optimal_graph_search
+            .solve_with_default_options(p.clone(), OfflineOptions::default())
+            .unwrap();
+        let CachedPath {
+            path: continued_path,
+            cache: continued_cache,
+            ..
+        } = continue_with(p.clone(), cache.clone()).unwrap();
+        assert_eq!(path.xs, continued_path.xs);
+        assert_abs_diff_eq!(path.cost, continued_path.cost);
+        let mut q = p.clone();
+        q.t_end = 8;
+        q.hitting_cost = constant();
+        assert_eq!(continue_with(q, cache).unwrap().path.xs.t_end(), 8);
+        assert_eq!(
+            continue_with(p, continued_cache).unwrap().path.xs,
+            continued_path.xs
+        );
+        assert_eq!(std::fs::read_dir(&spill_dir).unwrap().count(), 0);
+        std::fs::remove_dir(&spill_dir).unwrap();
+    }
 }
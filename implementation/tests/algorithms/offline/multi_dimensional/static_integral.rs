@@ -6,12 +6,14 @@ mod static_integral {
     use num::Float;
     use soco::algorithms::offline::multi_dimensional::static_integral::static_integral;
     use soco::algorithms::offline::{
-        OfflineAlgorithm, OfflineOptions, OfflineResult,
+        CancellationToken, OfflineAlgorithm, OfflineOptions, OfflineResult,
+        ProgressCallback,
     };
     use soco::config::IntegralConfig;
     use soco::distance::manhattan_scaled;
     use soco::problem::{Problem, SmoothedConvexOptimization};
     use soco::verifiers::VerifiableProblem;
+    use std::sync::{Arc, Mutex};
 
     #[test]
     fn _1() {
@@ -42,4 +44,45 @@ mod static_integral {
                 )
         );
     }
+
+    #[test]
+    fn _2() {
+        init();
+
+        let p = SmoothedConvexOptimization {
+            d: 3,
+            t_end: 10,
+            bounds: vec![(0, 2), (0, 1), (0, 5)],
+            switching_cost: manhattan_scaled(vec![1.5, 1., 4.]),
+            hitting_cost: penalize_zero(),
+        };
+        p.verify().unwrap();
+
+        let cancellation = CancellationToken::new();
+        let checked = Arc::new(Mutex::new(0));
+        let result = static_integral
+            .solve_with_default_options(
+                p.clone(),
+                OfflineOptions {
+                    progress: Some(ProgressCallback::new({
+                        let cancellation = cancellation.clone();
+                        let checked = checked.clone();
+                        move |progress| {
+                            assert_eq!(progress.steps, 3 * 2 * 6);
+                            *checked.lock().unwrap() = progress.step;
+                            cancellation.cancel();
+                        }
+                    })),
+                    cancellation: Some(cancellation),
+                    ..OfflineOptions::default()
+                },
+            )
+            .unwrap()
+            .xs();
+        result.verify(p.t_end, &upper_bounds(&p.bounds)).unwrap();
+
+        // only the first config of each dimension is checked after the cancellation
+        assert_eq!(*checked.lock().unwrap(), 1);
+        assert!(p.objective_function(&result).unwrap().cost.is_finite());
+    }
 }
@@ -0,0 +1,124 @@
+#[cfg(test)]
+mod stochastic_co {
+    use crate::factories::sampled_paraboloid;
+    use crate::init;
+    use soco::algorithms::offline::multi_dimensional::stochastic_convex_optimization::{
+        stochastic_co, Options, MAX_SCENARIOS,
+    };
+    use soco::algorithms::offline::{
+        OfflineAlgorithm, OfflineOptions, OfflineResult,
+    };
+    use noisy_float::prelude::*;
+    use soco::config::FractionalConfig;
+    use soco::cost::{FailableCost, RawCostFn, SingleCostFn};
+    use soco::problem::SimplifiedSmoothedConvexOptimization;
+    use soco::result::Failure;
+    use soco::verifiers::VerifiableProblem;
+
+    #[test]
+    fn _1() {
+        init();
+
+        let p = SimplifiedSmoothedConvexOptimization {
+            d: 1,
+            t_end: 3,
+            bounds: vec![4.],
+            switching_cost: vec![1.],
+            hitting_cost: RawCostFn::new(
+                1,
+                sampled_paraboloid(1, vec![0., 4.]),
+            ),
+        };
+        p.verify().unwrap();
+
+        let result = stochastic_co
+            .solve_with_default_options(p.clone(), OfflineOptions::default())
+            .unwrap();
+        assert_eq!(result.xs.t_end(), 1);
+        assert_eq!(result.recourse.len(), 2);
+        for ys in &result.recourse {
+            assert_eq!(ys.t_end(), 2);
+        }
+        assert!(result.cost.is_finite());
+
+        // the recourse decisions follow the respective scenario
+        assert!(result.recourse[0][1][0] + 1. < result.recourse[1][1][0]);
+
+        // deciding all time slots here-and-now optimizes against the mean
+        let deterministic_result = stochastic_co
+            .solve(
+                p.clone(),
+                Options { here_and_now: 3 },
+                OfflineOptions::default(),
+            )
+            .unwrap();
+        assert_eq!(deterministic_result.xs.t_end(), 3);
+        assert!(result.cost <= deterministic_result.cost + 1e-4);
+
+        let xs = result.xs();
+        xs.verify(p.t_end, &p.bounds).unwrap();
+    }
+
+    #[test]
+    fn _2() {
+        init();
+
+        let p = SimplifiedSmoothedConvexOptimization {
+            d: 1,
+            t_end: 2,
+            bounds: vec![4.],
+            switching_cost: vec![1.],
+            hitting_cost: RawCostFn::new(
+                1,
+                sampled_paraboloid(
+                    1,
+                    (0..=MAX_SCENARIOS).map(|s| s as f64).collect(),
+                ),
+            ),
+        };
+        p.verify().unwrap();
+
+        let result = stochastic_co
+            .solve_with_default_options(p, OfflineOptions::default());
+        assert!(matches!(
+            result,
+            Err(Failure::TooManyScenarios { scenarios, max })
+                if scenarios == MAX_SCENARIOS + 1 && max == MAX_SCENARIOS
+        ));
+    }
+
+    #[test]
+    fn _3() {
+        init();
+
+        // the $t$-th time slot returns $t$ samples
+        let p = SimplifiedSmoothedConvexOptimization {
+            d: 1,
+            t_end: 3,
+            bounds: vec![4.],
+            switching_cost: vec![1.],
+            hitting_cost: RawCostFn::new(
+                1,
+                SingleCostFn::predictive(|t, x: FractionalConfig| {
+                    (0..t)
+                        .map(|s| {
+                            FailableCost::raw(n64((x[0] - s as f64).powi(2)))
+                        })
+                        .collect()
+                }),
+            ),
+        };
+        p.verify().unwrap();
+
+        let result = stochastic_co
+            .solve_with_default_options(p, OfflineOptions::default());
+        assert!(matches!(
+            result,
+            Err(Failure::InconsistentSamples {
+                t: 2,
+                samples: 2,
+                scenarios: 3
+            })
+        ));
+    }
+}
@@ -259,6 +259,7 @@ mod optimal_graph_search {
             vec![Location {
                 key: DEFAULT_KEY.to_string(),
                 m: hash_map(&[(DEFAULT_KEY.to_string(), m)]),
+                demand_charge: None,
             }],
             vec![ServerType::default()],
             vec![Source::default()],
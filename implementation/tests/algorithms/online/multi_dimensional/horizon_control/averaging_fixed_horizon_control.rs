@@ -1,8 +1,9 @@
 #[cfg(test)]
 mod afhc {
-    use crate::{factories::inv_e};
+    use crate::factories::{inv_e, sampled_paraboloid};
+    use soco::cost::RawCostFn;
     use num::Float;
-    use soco::algorithms::online::multi_dimensional::horizon_control::averaging_fixed_horizon_control::{afhc};
+    use soco::algorithms::online::multi_dimensional::horizon_control::averaging_fixed_horizon_control::{afhc, stochastic_afhc};
     use soco::config::Config;
     use soco::convert::DiscretizableSchedule;
     use soco::problem::{Online, Problem, SimplifiedSmoothedConvexOptimization};
@@ -39,4 +40,46 @@ mod afhc {
             ])
         );
     }
+
+    #[test]
+    fn _2() {
+        init();
+
+        let p = SimplifiedSmoothedConvexOptimization {
+            d: 1,
+            t_end: 1,
+            bounds: vec![4.],
+            switching_cost: vec![1.],
+            hitting_cost: RawCostFn::new(
+                1,
+                sampled_paraboloid(1, vec![0., 4.]),
+            ),
+        };
+        let mut o = Online { p: p.clone(), w: 2 };
+        o.verify().unwrap();
+
+        // in each time slot, the current hitting cost is certain, whereas future hitting costs are uncertain
+        let t_end = 5;
+        let result = o
+            .stream(
+                &stochastic_afhc,
+                |o, _| {
+                    if o.p.t_end < t_end {
+                        o.p.t_end += 1;
+                        let t = o.p.t_end;
+                        o.p.hitting_cost
+                            .add(t, sampled_paraboloid(t, vec![0., 4.]));
+                        true
+                    } else {
+                        false
+                    }
+                },
+                (),
+            )
+            .unwrap();
+        result.0.verify(o.p.t_end, &o.p.bounds).unwrap();
+
+        assert_eq!(result.0.t_end(), t_end);
+        assert!(o.p.objective_function(&result.0).unwrap().cost.is_finite());
+    }
 }
@@ -0,0 +1,256 @@
+#[cfg(test)]
+mod risk_measure {
+    use crate::{factories::sampled_paraboloid, init};
+    use noisy_float::prelude::*;
+    use soco::{
+        config::Config,
+        cost::{Cost, RawCostFn, RiskMeasure},
+        model::{
+            data_center::{
+                DataCenterModelOutput, DataCenterModelOutputSuccess,
+            },
+            ModelOutput,
+        },
+        problem::{Problem, SimplifiedSmoothedConvexOptimization},
+        result::Failure,
+        verifiers::VerifiableProblem,
+    };
+
+    #[test]
+    fn _1() {
+        init();
+
+        let p = SimplifiedSmoothedConvexOptimization {
+            d: 1,
+            t_end: 2,
+            bounds: vec![5.],
+            switching_cost: vec![1.],
+            hitting_cost: RawCostFn::new(
+                1,
+                sampled_paraboloid(1, vec![0., 1., 2., 5.]),
+            ),
+        };
+        let hit_cost = |risk_measure| {
+            let mut p = p.clone();
+            p.hitting_cost = p.hitting_cost.with_risk_measure(risk_measure);
+            p.hit_cost(2, Config::single(1.)).cost.raw()
+        };
+
+        // sampled costs are 1, 0, 1, and 16
+        assert_abs_diff_eq!(hit_cost(RiskMeasure::Mean), 4.5);
+        assert_abs_diff_eq!(hit_cost(RiskMeasure::WorstCase), 16.);
+        assert_abs_diff_eq!(
+            hit_cost(RiskMeasure::ConditionalValueAtRisk(0.5)),
+            8.5
+        );
+        assert_abs_diff_eq!(
+            hit_cost(RiskMeasure::ConditionalValueAtRisk(0.)),
+            4.5
+        );
+        assert_abs_diff_eq!(
+            hit_cost(RiskMeasure::MeanStandardDeviation(1.)),
+            4.5 + 44.25_f64.sqrt()
+        );
+        assert_abs_diff_eq!(
+            p.hit_cost(1, Config::single(1.)).cost.raw(),
+            hit_cost(RiskMeasure::WorstCase) - 16.
+        );
+    }
+
+    #[test]
+    fn _2() {
+        init();
+
+        let costs: Vec<Cost<_, _>> = [1., 3., 2.]
+            .iter()
+            .map(|&c| {
+                Cost::new(
+                    n64(c),
+                    DataCenterModelOutput::Success(
+                        DataCenterModelOutputSuccess::new(c, 0., vec![1.]),
+                    ),
+                )
+            })
+            .collect();
+
+        let worst_case = Cost::aggregate(costs.clone(), RiskMeasure::WorstCase);
+        assert_abs_diff_eq!(worst_case.cost.raw(), 3.);
+        match worst_case.output {
+            ModelOutput::Success(output) => {
+                assert_eq!(output.samples, vec![vec![1]]);
+                assert_abs_diff_eq!(output.energy_cost, 3.);
+            }
+            _ => panic!("expected successful output"),
+        }
+
+        let cvar = Cost::aggregate(
+            costs.clone(),
+            RiskMeasure::ConditionalValueAtRisk(0.5),
+        );
+        assert_abs_diff_eq!(cvar.cost.raw(), 2.5);
+        match cvar.output {
+            ModelOutput::Success(output) => {
+                assert_eq!(output.samples, vec![vec![1, 2]])
+            }
+            _ => panic!("expected successful output"),
+        }
+
+        match Cost::mean(costs).output {
+            ModelOutput::Success(output) => {
+                assert_eq!(output.samples, vec![vec![0, 1, 2]])
+            }
+            _ => panic!("expected successful output"),
+        }
+    }
+
+    #[test]
+    fn _3() {
+        init();
+
+        let p = SimplifiedSmoothedConvexOptimization {
+            d: 1,
+            t_end: 2,
+            bounds: vec![5.],
+            switching_cost: vec![1.],
+            hitting_cost: RawCostFn::new(
+                1,
+                sampled_paraboloid(1, vec![0., 1.]),
+            )
+            .with_risk_measure(RiskMeasure::ConditionalValueAtRisk(1.)),
+        };
+        assert!(matches!(p.verify(), Err(Failure::Invalid(_))));
+        assert!(RiskMeasure::ConditionalValueAtRisk(-0.5).verify().is_err());
+        assert!(RiskMeasure::ConditionalValueAtRisk(0.).verify().is_ok());
+    }
+
+    #[test]
+    fn _4() {
+        init();
+
+        let costs: Vec<Cost<_, _>> = [1., 2., 6.]
+            .iter()
+            .map(|&c| {
+                let mut output =
+                    DataCenterModelOutputSuccess::new(c, 2. * c, vec![1.]);
+                output.emissions = 3. * c;
+                output.peak_power.insert("location".to_string(), 4. * c);
+                Cost::new(n64(c), DataCenterModelOutput::Success(output))
+            })
+            .collect();
+
+        // each of the three samples is weighed equally
+        let mean = Cost::mean(costs);
+        assert_abs_diff_eq!(mean.cost.raw(), 3.);
+        match mean.output {
+            ModelOutput::Success(output) => {
+                assert_abs_diff_eq!(output.energy_cost, 3.);
+                assert_abs_diff_eq!(output.revenue_loss, 6.);
+                assert_abs_diff_eq!(output.emissions, 9.);
+                assert_abs_diff_eq!(output.peak_power["location"], 12.);
+                assert_eq!(output.samples, vec![vec![0, 1, 2]]);
+            }
+            _ => panic!("expected successful output"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod cache {
+    use crate::init;
+    use noisy_float::prelude::*;
+    use soco::{
+        config::Config,
+        cost::{RawCost, RawCostFn, SingleCostFn},
+    };
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    /// Cost function $x_1$ which counts its evaluations.
+    fn cost_fn(
+        evaluations: Arc<AtomicUsize>,
+    ) -> RawCostFn<'static, Config<f64>> {
+        RawCostFn::new(
+            1,
+            SingleCostFn::certain(move |_, x: Config<f64>| {
+                evaluations.fetch_add(1, Ordering::SeqCst);
+                RawCost::raw(n64(x[0]))
+            }),
+        )
+    }
+
+    #[test]
+    fn _1() {
+        init();
+
+        let evaluations = Arc::new(AtomicUsize::new(0));
+        let f = cost_fn(evaluations.clone()).with_cache(10);
+        for _ in 0..3 {
+            assert_abs_diff_eq!(
+                f.call_certain(1, Config::single(2.)).cost.raw(),
+                2.
+            );
+        }
+        assert_abs_diff_eq!(
+            f.call_certain(2, Config::single(2.)).cost.raw(),
+            2.
+        );
+        assert_eq!(evaluations.load(Ordering::SeqCst), 2);
+
+        let statistics = f.cache_statistics().unwrap();
+        assert_eq!(statistics.hits, 2);
+        assert_eq!(statistics.misses, 2);
+        assert_eq!(statistics.size, 2);
+        assert_abs_diff_eq!(statistics.hit_rate(), 0.5);
+        assert!(cost_fn(evaluations).cache_statistics().is_none());
+    }
+
+    #[test]
+    fn _2() {
+        init();
+
+        // fractional configurations are only shared if they are identical and the cache is bounded
+        let evaluations = Arc::new(AtomicUsize::new(0));
+        let f = cost_fn(evaluations.clone()).with_cache(1);
+        assert_abs_diff_eq!(
+            f.call_certain(1, Config::single(1.01)).cost.raw(),
+            1.01
+        );
+        assert_abs_diff_eq!(
+            f.call_certain(1, Config::single(0.99)).cost.raw(),
+            0.99
+        );
+        assert_abs_diff_eq!(
+            f.call_certain(1, Config::single(0.99)).cost.raw(),
+            0.99
+        );
+        assert_abs_diff_eq!(
+            f.call_certain(1, Config::single(1.01)).cost.raw(),
+            1.01
+        );
+        assert_eq!(evaluations.load(Ordering::SeqCst), 3);
+        assert_eq!(f.cache_statistics().unwrap().size, 1);
+    }
+
+    #[test]
+    fn _3() {
+        init();
+
+        // mapped cost functions retain the cache which is keyed by the mapped configuration
+        let evaluations = Arc::new(AtomicUsize::new(0));
+        let f = cost_fn(evaluations.clone())
+            .with_cache(10)
+            .map_config(|x: Config<f64>| Config::single(x[0].round()));
+        assert_abs_diff_eq!(
+            f.call_certain(1, Config::single(1.2)).cost.raw(),
+            1.
+        );
+        assert_abs_diff_eq!(
+            f.call_certain(1, Config::single(0.8)).cost.raw(),
+            1.
+        );
+        assert_eq!(evaluations.load(Ordering::SeqCst), 1);
+        assert_eq!(f.cache_statistics().unwrap().hits, 1);
+    }
+}
@@ -4,11 +4,37 @@ use rand::prelude::*;
 use rand_pcg::Pcg64;
 use soco::{
     config::{Config, FractionalConfig, IntegralConfig},
-    cost::{FailableCost, FailableCostFn, RawCostFn, SingleCostFn},
-    model::{data_center::DataCenterModelOutputFailure, ModelOutputFailure},
+    cost::{
+        FailableCost, FailableCostFn, RawCostFn, RawSingleCostFn, SingleCostFn,
+    },
+    model::{
+        data_center::{
+            loads::LoadProfile,
+            model::{
+                DataCenterModel, DataCenterOfflineInput, JobType, Location,
+                ServerType, Source, DEFAULT_KEY,
+            },
+            models::{
+                energy_consumption::{
+                    EnergyConsumptionModel,
+                    SimplifiedLinearEnergyConsumptionModel,
+                },
+                energy_cost::{EnergyCostModel, LinearEnergyCostModel},
+                revenue_loss::{
+                    MinimalDetectableDelayRevenueLossModel, RevenueLossModel,
+                },
+                switching_cost::{SwitchingCost, SwitchingCostModel},
+            },
+            DataCenterModelOutputFailure, DataCenterModelOutputSuccess,
+        },
+        Model, ModelOutput, ModelOutputFailure,
+    },
+    problem::{FractionalSimplifiedSmoothedConvexOptimization, Problem},
     value::Value,
     vec_wrapper::VecWrapper,
 };
+use std::collections::HashMap;
+use std::sync::Arc;
 
 fn wrap<'a, T, D>(
     f: impl Fn(i32, T) -> f64 + Send + Sync + 'a,
@@ -96,3 +122,168 @@ where
         (ToPrimitive::to_f64(&j[0]).unwrap() - ((t % m) as f64)).powi(2)
     })
 }
+
+/// $\sum_k (x_k-((t + k) % m))^2$ for multiple dimensions.
+pub fn moving_paraboloid<T>(m: i32) -> RawCostFn<'static, Config<T>>
+where
+    T: Value<'static>,
+{
+    wrap(move |t: i32, j: Config<T>| {
+        (0..j.d())
+            .map(|k| {
+                (ToPrimitive::to_f64(&j[k as usize]).unwrap()
+                    - ((t + k) % m) as f64)
+                    .powi(2)
+            })
+            .sum()
+    })
+}
+
+/// $\sum_k (x_k - 1)^2$ in time slot $t_start$ and $\sum_k (x_k - c_s)^2$ in the $s$-th sample of all later time slots.
+pub fn sampled_paraboloid(
+    t_start: i32,
+    targets: Vec<f64>,
+) -> RawSingleCostFn<'static, FractionalConfig> {
+    let cost = |j: &FractionalConfig, c: f64| {
+        FailableCost::raw(n64(j.iter().map(|&x| (x - c).powi(2)).sum()))
+    };
+    SingleCostFn::predictive(move |t, j: FractionalConfig| {
+        if t == t_start {
+            vec![cost(&j, 1.)]
+        } else {
+            targets.iter().map(|&c| cost(&j, c)).collect()
+        }
+    })
+}
+
+/// Homogeneous data center with $m$ servers and a time slot length of $1$ where an unserved job costs `penalty`.
+pub fn data_center_model(m: i32, penalty: f64) -> DataCenterModel {
+    build_data_center_model(
+        m,
+        penalty,
+        EnergyConsumptionModel::SimplifiedLinear(by_key(
+            &[DEFAULT_KEY],
+            SimplifiedLinearEnergyConsumptionModel { phi_max: 1. },
+        )),
+        linear_energy_cost(|_| 1.),
+    )
+}
+
+/// Data center with $m$ servers of a single server type at a single location serving a single job type from a single source.
+pub fn build_data_center_model(
+    m: i32,
+    penalty: f64,
+    energy_consumption_model: EnergyConsumptionModel,
+    energy_cost_model: EnergyCostModel,
+) -> DataCenterModel {
+    DataCenterModel::new(
+        1.,
+        vec![Location {
+            key: DEFAULT_KEY.to_string(),
+            m: by_key(&[DEFAULT_KEY], m),
+            demand_charge: None,
+        }],
+        vec![ServerType::default()],
+        vec![Source::default()],
+        vec![JobType::default()],
+        energy_consumption_model,
+        energy_cost_model,
+        RevenueLossModel::MinimalDetectableDelay(by_key(
+            &[DEFAULT_KEY],
+            MinimalDetectableDelayRevenueLossModel {
+                penalty,
+                ..MinimalDetectableDelayRevenueLossModel::default()
+            },
+        )),
+        SwitchingCostModel::new(by_key(
+            &[DEFAULT_KEY],
+            SwitchingCost {
+                energy_cost: 1.,
+                phi_min: 0.5,
+                phi_max: 1.,
+                epsilon: 1.,
+                delta: 1.,
+                tau: 5.,
+                rho: 5.,
+            },
+        )),
+    )
+}
+
+/// Data center with $m$ servers of each of the given server types at a single location.
+pub fn heterogeneous_data_center_model(
+    server_types: &[&str],
+    m: i32,
+    penalty: f64,
+) -> DataCenterModel {
+    let mut model = build_data_center_model(
+        m,
+        penalty,
+        EnergyConsumptionModel::SimplifiedLinear(by_key(
+            server_types,
+            SimplifiedLinearEnergyConsumptionModel { phi_max: 1. },
+        )),
+        linear_energy_cost(|_| 1.),
+    );
+    model.server_types = server_types
+        .iter()
+        .map(|key| ServerType {
+            key: key.to_string(),
+            ..ServerType::default()
+        })
+        .collect();
+    model.locations[0].m = by_key(server_types, m);
+    model.switching_cost_model = SwitchingCostModel::new(by_key(
+        server_types,
+        SwitchingCost {
+            energy_cost: 1.,
+            phi_min: 0.5,
+            phi_max: 1.,
+            epsilon: 1.,
+            delta: 1.,
+            tau: 5.,
+            rho: 5.,
+        },
+    ));
+    model
+}
+
+/// Maps each key to $value$.
+fn by_key<V>(keys: &[&str], value: V) -> HashMap<String, V>
+where
+    V: Clone,
+{
+    keys.iter()
+        .map(|key| (key.to_string(), value.clone()))
+        .collect()
+}
+
+/// Energy at the default location costs $cost(t)$ during time slot $t$.
+pub fn linear_energy_cost(
+    cost: impl Fn(i32) -> f64 + Send + Sync + 'static,
+) -> EnergyCostModel {
+    EnergyCostModel::Linear(by_key(
+        &[DEFAULT_KEY],
+        LinearEnergyCostModel {
+            cost: Arc::new(cost),
+        },
+    ))
+}
+
+/// Loads of a single job type where the load during time slot $t$ is the $t$-th entry of `loads`.
+pub fn data_center_input(loads: &[f64]) -> DataCenterOfflineInput {
+    DataCenterOfflineInput {
+        loads: loads.iter().map(|&l| LoadProfile::raw(vec![l])).collect(),
+    }
+}
+
+/// Hitting cost of fractional configuration $x$ during the first time slot where a single job type has the given `load`.
+pub fn data_center_hit_cost(
+    model: DataCenterModel,
+    load: f64,
+    x: Vec<f64>,
+) -> ModelOutput<DataCenterModelOutputSuccess, DataCenterModelOutputFailure> {
+    let p: FractionalSimplifiedSmoothedConvexOptimization<_, _> =
+        model.to(data_center_input(&[load]));
+    p.hit_cost(1, Config::new(x)).output
+}
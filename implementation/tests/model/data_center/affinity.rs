@@ -0,0 +1,67 @@
+#[cfg(test)]
+mod affinity {
+    use crate::factories::{
+        data_center_hit_cost, heterogeneous_data_center_model,
+    };
+    use crate::{init, utils::hash_map};
+    use soco::model::{
+        data_center::{
+            model::{Affinity, DataCenterModel, DEFAULT_KEY},
+            DataCenterModelOutputFailure,
+        },
+        ModelOutput,
+    };
+
+    /// Single location with a `cpu` and a `gpu` server where jobs may only be processed by the given server types.
+    fn restricted_model(server_types: &[&str]) -> DataCenterModel {
+        heterogeneous_data_center_model(&["cpu", "gpu"], 1, f64::INFINITY)
+            .with_affinities(hash_map(&[(
+                DEFAULT_KEY.to_string(),
+                Affinity {
+                    server_types: Some(
+                        server_types
+                            .iter()
+                            .map(|key| key.to_string())
+                            .collect(),
+                    ),
+                    locations: None,
+                },
+            )]))
+    }
+
+    #[test]
+    fn _1() {
+        init();
+
+        // jobs are only processed by `gpu` servers
+        match data_center_hit_cost(
+            restricted_model(&["gpu"]),
+            0.5,
+            vec![1., 1.],
+        ) {
+            ModelOutput::Success(output) => {
+                assert_eq!(output.assignments[0][0], vec![0., 1.]);
+                assert_abs_diff_eq!(
+                    output.breakdown.revenue_loss_by_server_type["cpu"],
+                    0.
+                );
+            }
+            _ => panic!("the load can be processed by `gpu` servers"),
+        }
+
+        assert!(matches!(
+            data_center_hit_cost(restricted_model(&["gpu"]), 0.5, vec![1., 0.]),
+            ModelOutput::Failure(
+                DataCenterModelOutputFailure::LoadToInactiveServer
+            )
+        ));
+    }
+
+    #[test]
+    fn _2() {
+        init();
+
+        assert!(restricted_model(&["cpu", "gpu"]).verify().is_ok());
+        assert!(restricted_model(&["tpu"]).verify().is_err());
+    }
+}
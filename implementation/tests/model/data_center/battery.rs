@@ -0,0 +1,207 @@
+#[cfg(test)]
+mod battery {
+    use crate::factories::{
+        data_center_input, data_center_model, linear_energy_cost,
+    };
+    use crate::{init, utils::hash_map};
+    use soco::{
+        algorithms::{
+            offline::{
+                multi_dimensional::{
+                    convex_optimization::co,
+                    optimal_graph_search::optimal_graph_search,
+                },
+                OfflineAlgorithm, OfflineOptions, OfflineResult,
+            },
+            online::multi_dimensional::horizon_control::receding_horizon_control::rhc,
+        },
+        config::Config,
+        model::{
+            data_center::{
+                model::{DataCenterModel, DEFAULT_KEY},
+                models::energy_storage::Battery,
+                DataCenterModelOutputFailure, DataCenterModelOutputSuccess,
+            },
+            Model, ModelOutput,
+        },
+        problem::{
+            FractionalSimplifiedSmoothedConvexOptimization,
+            FractionalSmoothedConvexOptimization,
+            IntegralSimplifiedSmoothedConvexOptimization, Online, Problem,
+        },
+        result::Failure,
+        schedule::Schedule,
+    };
+
+    /// Data center with $10$ servers where energy is ten times more expensive during the second time slot.
+    fn peak_priced_model() -> DataCenterModel {
+        let mut model = data_center_model(10, f64::INFINITY);
+        model.energy_cost_model =
+            linear_energy_cost(|t| if t == 2 { 10. } else { 1. });
+        model
+    }
+
+    /// Adds a battery storing up to $10$ units of energy to the default location.
+    fn with_battery(
+        model: DataCenterModel,
+        charge_rate: f64,
+        efficiency: f64,
+    ) -> DataCenterModel {
+        model.with_batteries(hash_map(&[(
+            DEFAULT_KEY.to_string(),
+            Battery {
+                capacity: 10.,
+                charge_rate,
+                discharge_rate: 10.,
+                efficiency,
+                degradation_cost: 0.01,
+            },
+        )]))
+    }
+
+    #[test]
+    fn _1() {
+        init();
+
+        let p: FractionalSimplifiedSmoothedConvexOptimization<
+            DataCenterModelOutputSuccess,
+            DataCenterModelOutputFailure,
+        > = with_battery(peak_priced_model(), 10., 0.81)
+            .to(data_center_input(&[5.; 2]));
+        assert_eq!(p.d, 2);
+        assert_eq!(p.bounds, vec![10., 10.]);
+
+        let idle = p
+            .objective_function(&Schedule::new(vec![
+                Config::new(vec![10., 0.]),
+                Config::new(vec![10., 0.]),
+            ]))
+            .unwrap();
+        let shifted = p
+            .objective_function(&Schedule::new(vec![
+                Config::new(vec![10., 5.]),
+                Config::new(vec![10., 0.]),
+            ]))
+            .unwrap();
+        assert_relative_eq!(
+            shifted.cost.raw() - idle.cost.raw(),
+            5. / 0.9 - 10. * 5. * 0.9 + 0.01 * 5.,
+            max_relative = 1e-6
+        );
+        match (idle.output, shifted.output) {
+            (ModelOutput::Success(idle), ModelOutput::Success(shifted)) => {
+                assert_eq!(shifted.assignments.len(), 2);
+                assert_relative_eq!(
+                    shifted.energy_cost - idle.energy_cost,
+                    5. / 0.9 - 10. * 5. * 0.9,
+                    max_relative = 1e-6
+                );
+            }
+            _ => panic!("expected successful outputs"),
+        }
+
+        let p: FractionalSimplifiedSmoothedConvexOptimization<
+            DataCenterModelOutputSuccess,
+            DataCenterModelOutputFailure,
+        > = with_battery(peak_priced_model(), 2., 1.)
+            .to(data_center_input(&[5.; 2]));
+        let exceeded = p
+            .objective_function(&Schedule::new(vec![
+                Config::new(vec![10., 5.]),
+                Config::new(vec![10., 0.]),
+            ]))
+            .unwrap();
+        assert!(exceeded.cost.raw().is_infinite());
+        assert!(matches!(
+            exceeded.output,
+            ModelOutput::Failure(
+                DataCenterModelOutputFailure::BatteryRateExceeded
+            )
+        ));
+    }
+
+    #[test]
+    fn _2() {
+        init();
+
+        let offline = |model: DataCenterModel| {
+            let p: FractionalSmoothedConvexOptimization<
+                DataCenterModelOutputSuccess,
+                DataCenterModelOutputFailure,
+            > = model.to(data_center_input(&[5.; 2]));
+            let xs = co
+                .solve(p.clone(), (), OfflineOptions::default())
+                .unwrap()
+                .xs();
+            let cost = p.objective_function(&xs).unwrap().cost.raw();
+            (xs, cost)
+        };
+        let online = |model: DataCenterModel| {
+            let mut p: FractionalSimplifiedSmoothedConvexOptimization<
+                DataCenterModelOutputSuccess,
+                DataCenterModelOutputFailure,
+            > = model.to(data_center_input(&[5.; 3]));
+            p.t_end = 1;
+            let mut o = Online { p, w: 1 };
+            let (xs, _) = o.offline_stream(&rhc, 3, ()).unwrap();
+            o.p.t_end = 2;
+            let cost = o.p.objective_function(&xs).unwrap().cost.raw();
+            (xs, cost)
+        };
+
+        for solve in [&offline as &dyn Fn(_) -> _, &online] {
+            let (_, cost) = solve(peak_priced_model());
+            let (xs, battery_cost) =
+                solve(with_battery(peak_priced_model(), 10., 1.));
+            assert!(xs[0][1] > 1.);
+            assert!(battery_cost < cost - 10.);
+        }
+    }
+
+    #[test]
+    fn _3() {
+        init();
+
+        assert!(with_battery(peak_priced_model(), 10., 1.).verify().is_ok());
+        for efficiency in [0., 1.1] {
+            assert!(matches!(
+                with_battery(peak_priced_model(), 10., efficiency).verify(),
+                Err(Failure::Invalid(_))
+            ));
+        }
+        assert!(matches!(
+            with_battery(peak_priced_model(), -1., 1.).verify(),
+            Err(Failure::Invalid(_))
+        ));
+
+        let battery = Battery {
+            capacity: 2.5,
+            charge_rate: 1.,
+            discharge_rate: 1.,
+            efficiency: 1.,
+            degradation_cost: 0.01,
+        };
+        assert_eq!(battery.bound::<f64>(), 2.5);
+        assert_eq!(battery.bound::<i32>(), 2);
+        let infinite = peak_priced_model().with_batteries(hash_map(&[(
+            DEFAULT_KEY.to_string(),
+            Battery {
+                capacity: f64::INFINITY,
+                ..battery
+            },
+        )]));
+        assert!(matches!(infinite.verify(), Err(Failure::Invalid(_))));
+
+        // states of charge are continuous, so integral graph searches reject them
+        let p: IntegralSimplifiedSmoothedConvexOptimization<
+            DataCenterModelOutputSuccess,
+            DataCenterModelOutputFailure,
+        > = with_battery(peak_priced_model(), 10., 1.)
+            .to(data_center_input(&[5.; 2]));
+        assert!(matches!(
+            optimal_graph_search
+                .solve_with_default_options(p, OfflineOptions::default()),
+            Err(Failure::UnsupportedContinuousDimensions(dimensions)) if dimensions == vec![1]
+        ));
+    }
+}
@@ -0,0 +1,202 @@
+#[cfg(test)]
+mod boot_delay {
+    use crate::factories::{data_center_input, data_center_model};
+    use crate::{init, utils::hash_map};
+    use soco::{
+        algorithms::{
+            offline::{
+                multi_dimensional::{
+                    convex_optimization::co,
+                    optimal_graph_search::optimal_graph_search,
+                },
+                OfflineAlgorithm, OfflineOptions, OfflineResult,
+            },
+            online::multi_dimensional::horizon_control::receding_horizon_control::rhc,
+        },
+        config::Config,
+        model::{
+            data_center::{
+                model::{
+                    DataCenterModel,
+                    DEFAULT_KEY,
+                },
+                models::energy_consumption::{
+                        EnergyConsumptionModel, LinearEnergyConsumptionModel,
+                    },
+                DataCenterModelOutputFailure, DataCenterModelOutputSuccess,
+            },
+            Model, ModelOutput,
+        },
+        problem::{
+            FractionalSimplifiedSmoothedConvexOptimization,
+            FractionalSmoothedConvexOptimization,
+            IntegralSimplifiedSmoothedConvexOptimization, Online, Problem,
+        },
+        result::Failure,
+        schedule::Schedule,
+    };
+
+    /// Data center with $m$ servers which consume half of their peak power while idling or booting for `boot_delay` time slots.
+    fn booting_model(m: i32, boot_delay: i32) -> DataCenterModel {
+        let mut model = data_center_model(m, f64::INFINITY);
+        model.energy_consumption_model =
+            EnergyConsumptionModel::Linear(hash_map(&[(
+                DEFAULT_KEY.to_string(),
+                LinearEnergyConsumptionModel {
+                    phi_min: 0.5,
+                    phi_max: 1.,
+                },
+            )]));
+        model.server_types[0].boot_delay = boot_delay;
+        model
+    }
+
+    #[test]
+    fn _1() {
+        init();
+
+        let xs = Schedule::new(
+            [0, 5, 5, 2, 6].iter().map(|&j| Config::single(j)).collect(),
+        );
+        assert_eq!(
+            booting_model(10, 0).capacity_shortfall(&xs),
+            Schedule::new(vec![Config::single(0); 5])
+        );
+        assert_eq!(
+            booting_model(10, 1).capacity_shortfall(&xs),
+            Schedule::new(
+                [0, 5, 0, 0, 4].iter().map(|&j| Config::single(j)).collect()
+            )
+        );
+        assert_eq!(
+            booting_model(10, 2).capacity_shortfall(&xs),
+            Schedule::new(
+                [0, 5, 5, 0, 4].iter().map(|&j| Config::single(j)).collect()
+            )
+        );
+    }
+
+    #[test]
+    fn _2() {
+        init();
+
+        let p: FractionalSimplifiedSmoothedConvexOptimization<
+            DataCenterModelOutputSuccess,
+            DataCenterModelOutputFailure,
+        > = booting_model(10, 1).to(data_center_input(&[0., 5.]));
+        assert_eq!(p.d, 2);
+        assert_eq!(p.bounds, vec![10., 10.]);
+        assert_eq!(p.switching_cost[0], p.switching_cost[1]);
+
+        let undelayed_p: FractionalSimplifiedSmoothedConvexOptimization<
+            DataCenterModelOutputSuccess,
+            DataCenterModelOutputFailure,
+        > = booting_model(10, 0).to(data_center_input(&[0., 5.]));
+        assert_relative_eq!(
+            p.switching_cost[0] + p.switching_cost[1],
+            undelayed_p.switching_cost[0]
+        );
+
+        // servers are powered up a time slot early and idle while booting
+        let cost = p
+            .objective_function(&Schedule::new(vec![
+                Config::new(vec![10., 0.]),
+                Config::new(vec![10., 10.]),
+            ]))
+            .unwrap();
+        let undelayed_cost = undelayed_p
+            .objective_function(&Schedule::new(vec![
+                Config::single(0.),
+                Config::single(10.),
+            ]))
+            .unwrap();
+        let idle = undelayed_p.hit_cost(1, Config::single(10.)).cost;
+        assert!(idle > 0.);
+        assert_relative_eq!(
+            cost.cost.raw() - undelayed_cost.cost.raw(),
+            idle.raw(),
+            max_relative = 1e-6
+        );
+
+        // servers cannot be ready before they are powered up
+        let not_ready = p
+            .objective_function(&Schedule::new(vec![
+                Config::new(vec![0., 0.]),
+                Config::new(vec![10., 10.]),
+            ]))
+            .unwrap();
+        assert!(not_ready.cost.raw().is_infinite());
+        assert!(matches!(
+            not_ready.output,
+            ModelOutput::Failure(DataCenterModelOutputFailure::ServersNotReady)
+        ));
+
+        // booting servers cannot process load
+        let booting = p
+            .objective_function(&Schedule::new(vec![
+                Config::new(vec![0., 0.]),
+                Config::new(vec![10., 0.]),
+            ]))
+            .unwrap();
+        assert!(booting.cost.raw().is_infinite());
+    }
+
+    #[test]
+    fn _3() {
+        init();
+
+        let p: IntegralSimplifiedSmoothedConvexOptimization<
+            DataCenterModelOutputSuccess,
+            DataCenterModelOutputFailure,
+        > = booting_model(2, 1).to(data_center_input(&[0., 1., 1.]));
+        let result = optimal_graph_search
+            .solve_with_default_options(p.clone(), OfflineOptions::default())
+            .unwrap();
+        let cost = result.path.cost;
+        let xs = result.xs();
+        assert!(xs[0][0] > 0);
+        assert!(xs[1][1] > 0);
+        assert_relative_eq!(
+            p.objective_function(&xs).unwrap().cost.raw(),
+            cost,
+            max_relative = 1e-6
+        );
+
+        let p: FractionalSmoothedConvexOptimization<
+            DataCenterModelOutputSuccess,
+            DataCenterModelOutputFailure,
+        > = booting_model(2, 1).to(data_center_input(&[0., 1., 1.]));
+        let xs = co
+            .solve(p.clone(), (), OfflineOptions::default())
+            .unwrap()
+            .xs();
+        assert!(xs[0][0] > 0.5);
+        assert!(p.objective_function(&xs).unwrap().cost.raw().is_finite());
+        assert!(
+            p.objective_function(&xs).unwrap().cost.raw()
+                <= cost + 1e-3 * cost.abs()
+        );
+
+        let mut p: FractionalSimplifiedSmoothedConvexOptimization<
+            DataCenterModelOutputSuccess,
+            DataCenterModelOutputFailure,
+        > = booting_model(2, 1).to(data_center_input(&[0., 1., 1.]));
+        p.t_end = 1;
+        let mut o = Online { p, w: 1 };
+        let (xs, _) = o.offline_stream(&rhc, 3, ()).unwrap();
+        o.p.t_end = 2;
+        assert!(xs[1][1] > 0.5);
+        assert!(o.p.objective_function(&xs).unwrap().cost.raw().is_finite());
+    }
+
+    #[test]
+    fn _4() {
+        init();
+
+        assert!(booting_model(2, 1).verify().is_ok());
+        assert!(matches!(
+            booting_model(2, -1).verify(),
+            Err(Failure::Invalid(_))
+        ));
+    }
+}
@@ -0,0 +1,152 @@
+#[cfg(test)]
+mod breakdown {
+    use crate::factories::{
+        data_center_input, data_center_model, heterogeneous_data_center_model,
+    };
+    use crate::init;
+    use soco::{
+        config::Config,
+        distance::euclidean,
+        model::{
+            data_center::{
+                model::DEFAULT_KEY, DataCenterModelOutputFailure,
+                DataCenterModelOutputSuccess,
+            },
+            Model, ModelOutput,
+        },
+        problem::{
+            FractionalSimplifiedSmoothedConvexOptimization,
+            FractionalSmoothedConvexOptimization, Problem,
+        },
+        report::CostReport,
+        result::Failure,
+        schedule::Schedule,
+    };
+
+    /// Sum of the hitting, transition, and movement costs of all time slots.
+    fn total<C, D>(report: &CostReport<C, D>) -> f64 {
+        report
+            .entries
+            .iter()
+            .map(|entry| {
+                (entry.hitting_cost
+                    + entry.transition_cost
+                    + entry.movement_cost)
+                    .raw()
+            })
+            .sum()
+    }
+
+    #[test]
+    fn _1() {
+        init();
+
+        let p: FractionalSimplifiedSmoothedConvexOptimization<
+            DataCenterModelOutputSuccess,
+            DataCenterModelOutputFailure,
+        > = data_center_model(2, 10.).to(data_center_input(&[1., 0.5]));
+        let xs = Schedule::new(vec![Config::single(2.), Config::single(1.)]);
+        let report = p.cost_report(&xs).unwrap();
+
+        for entry in &report.entries {
+            match &entry.output {
+                ModelOutput::Success(output) => {
+                    let breakdown = &output.breakdown;
+                    assert_abs_diff_eq!(
+                        breakdown.energy_cost_by_location.values().sum::<f64>(),
+                        output.energy_cost,
+                        epsilon = 1e-9
+                    );
+                    assert_abs_diff_eq!(
+                        breakdown
+                            .revenue_loss_by_server_type
+                            .values()
+                            .sum::<f64>(),
+                        output.revenue_loss,
+                        epsilon = 1e-9
+                    );
+                }
+                _ => panic!("the load can be processed"),
+            }
+        }
+
+        let columns = report.columns();
+        let energy_cost = columns
+            .iter()
+            .find(|(name, _)| {
+                *name == format!("energy_cost_by_location.{}", DEFAULT_KEY)
+            })
+            .unwrap();
+        assert!(energy_cost.1[0] > energy_cost.1[1]);
+    }
+
+    #[test]
+    fn _2() {
+        init();
+
+        let p: FractionalSimplifiedSmoothedConvexOptimization<
+            DataCenterModelOutputSuccess,
+            DataCenterModelOutputFailure,
+        > = data_center_model(2, 10.).to(data_center_input(&[1., 0.5]));
+        let xs = Schedule::new(vec![Config::single(2.), Config::single(1.)]);
+
+        // reports cover the entire time horizon
+        assert!(matches!(
+            p.cost_report(&Schedule::new(vec![Config::single(2.)])),
+            Err(Failure::Invalid(_))
+        ));
+
+        // inverted and alpha-unfair movement costs are reported like they are paid by the objective function
+        let report = p.inverted_cost_report(&xs).unwrap();
+        assert_abs_diff_eq!(report.entries[0].movement_cost.raw(), 0.);
+        assert_abs_diff_eq!(
+            total(&report),
+            p.inverted_objective_function(&xs).unwrap().cost.raw(),
+            epsilon = 1e-9
+        );
+        let report = p.alpha_unfair_cost_report(&xs, 2.).unwrap();
+        assert_abs_diff_eq!(
+            report.entries[0].movement_costs[0].raw(),
+            2. * p.switching_cost[0] * 2.
+        );
+        assert_abs_diff_eq!(
+            total(&report),
+            p.alpha_unfair_objective_function(&xs, 2.)
+                .unwrap()
+                .cost
+                .raw(),
+            epsilon = 1e-9
+        );
+
+        // movement costs of dimensions do not sum to the movement cost if the norm is not separable
+        let mut p: FractionalSmoothedConvexOptimization<
+            DataCenterModelOutputSuccess,
+            DataCenterModelOutputFailure,
+        > = heterogeneous_data_center_model(&["a", "b"], 2, 10.)
+            .to(data_center_input(&[1., 0.5]));
+        p.switching_cost = euclidean();
+        let xs = Schedule::new(vec![
+            Config::new(vec![1., 1.]),
+            Config::new(vec![1., 1.]),
+        ]);
+        let report = p.cost_report(&xs).unwrap();
+        assert_abs_diff_eq!(
+            report.entries[0].movement_cost.raw(),
+            2_f64.sqrt(),
+            epsilon = 1e-9
+        );
+        assert_eq!(
+            report.entries[0]
+                .movement_costs
+                .iter()
+                .map(|cost| cost.raw())
+                .collect::<Vec<_>>(),
+            vec![1., 1.]
+        );
+        assert_abs_diff_eq!(
+            total(&report),
+            p.objective_function(&xs).unwrap().cost.raw(),
+            epsilon = 1e-9
+        );
+    }
+}
@@ -0,0 +1,89 @@
+#[cfg(test)]
+mod cache {
+    use crate::factories::{data_center_input, data_center_model};
+    use crate::init;
+    use soco::{
+        config::Config,
+        model::{
+            data_center::{
+                DataCenterModelOutputFailure, DataCenterModelOutputSuccess,
+            },
+            Model,
+        },
+        problem::{
+            FractionalSimplifiedSmoothedConvexOptimization,
+            IntegralSimplifiedSmoothedConvexOptimization, Problem,
+        },
+        result::Failure,
+    };
+
+    fn problem(
+        cache_capacity: Option<usize>,
+    ) -> IntegralSimplifiedSmoothedConvexOptimization<
+        'static,
+        DataCenterModelOutputSuccess,
+        DataCenterModelOutputFailure,
+    > {
+        let mut model = data_center_model(2, 1.);
+        model.cache_capacity = cache_capacity;
+        model.to(data_center_input(&[1.5; 2]))
+    }
+
+    fn fractional_problem(
+        cache_resolution: Option<f64>,
+    ) -> FractionalSimplifiedSmoothedConvexOptimization<
+        'static,
+        DataCenterModelOutputSuccess,
+        DataCenterModelOutputFailure,
+    > {
+        let mut model = data_center_model(2, 1.).with_cache(10);
+        model.cache_resolution = cache_resolution;
+        model.to(data_center_input(&[1.5; 2]))
+    }
+
+    #[test]
+    fn _1() {
+        init();
+
+        // generated problems cache hitting costs which are identical to the uncached hitting costs
+        let p = problem(Some(10));
+        let uncached_p = problem(None);
+        for _ in 0..2 {
+            for t in 1..=2 {
+                assert_eq!(
+                    p.hit_cost(t, Config::single(2)).cost,
+                    uncached_p.hit_cost(t, Config::single(2)).cost
+                );
+            }
+        }
+        let statistics = p.cache_statistics().unwrap();
+        assert_eq!(statistics.hits, 2);
+        assert_eq!(statistics.misses, 2);
+        assert!(uncached_p.cache_statistics().is_none());
+    }
+
+    #[test]
+    fn _2() {
+        init();
+
+        // fractional configurations which are rounded to the same multiple of the resolution share their cached hitting cost
+        let p = fractional_problem(Some(0.1));
+        let cost = p.hit_cost(1, Config::single(1.81)).cost;
+        assert_eq!(p.hit_cost(1, Config::single(1.79)).cost, cost);
+        assert_ne!(p.hit_cost(1, Config::single(1.7)).cost, cost);
+        let statistics = p.cache_statistics().unwrap();
+        assert_eq!(statistics.hits, 1);
+        assert_eq!(statistics.misses, 2);
+
+        // by default, only identical configurations share their cached hitting cost
+        let p = fractional_problem(None);
+        assert_ne!(
+            p.hit_cost(1, Config::single(1.79)).cost,
+            p.hit_cost(1, Config::single(1.81)).cost
+        );
+        assert_eq!(p.cache_statistics().unwrap().hits, 0);
+
+        let model = data_center_model(2, 1.).with_quantized_cache(10, 0.);
+        assert!(matches!(model.verify(), Err(Failure::Invalid(_))));
+    }
+}
@@ -0,0 +1,124 @@
+#[cfg(test)]
+mod calibration {
+    use crate::init;
+    use soco::model::data_center::{
+        calibration::{
+            calibrate_linear_energy_consumption_model,
+            calibrate_non_linear_energy_consumption_model,
+            calibrate_processing_times,
+            calibrate_simplified_linear_energy_consumption_model, MAX_ALPHA,
+        },
+        model::ServerType,
+    };
+
+    #[test]
+    fn _1() {
+        init();
+
+        let samples: Vec<_> = [0., 0.25, 0.5, 1.]
+            .iter()
+            .map(|&s| (s, 100. + 100. * s))
+            .collect();
+        let calibration =
+            calibrate_linear_energy_consumption_model(&samples).unwrap();
+        assert_abs_diff_eq!(calibration.model.phi_min, 100., epsilon = 1e-9);
+        assert_abs_diff_eq!(calibration.model.phi_max, 200., epsilon = 1e-9);
+        assert_abs_diff_eq!(calibration.fit.r_squared, 1., epsilon = 1e-9);
+        assert_abs_diff_eq!(calibration.fit.rmse, 0., epsilon = 1e-9);
+        assert_eq!(calibration.fit.samples, 4);
+
+        let calibration =
+            calibrate_simplified_linear_energy_consumption_model(&samples)
+                .unwrap();
+        assert_abs_diff_eq!(calibration.model.phi_max, 200., epsilon = 1e-9);
+
+        // power decreasing with utilization is fitted by a constant
+        let calibration =
+            calibrate_linear_energy_consumption_model(&[(0., 120.), (1., 80.)])
+                .unwrap();
+        assert_abs_diff_eq!(calibration.model.phi_min, 100., epsilon = 1e-9);
+        assert_abs_diff_eq!(calibration.model.phi_max, 100., epsilon = 1e-9);
+
+        assert!(
+            calibrate_linear_energy_consumption_model(&[(0., 100.)]).is_err()
+        );
+        assert!(calibrate_linear_energy_consumption_model(&[
+            (0., 100.),
+            (1.5, 200.)
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn _2() {
+        init();
+
+        let samples: Vec<_> = (0..=10)
+            .map(|i| {
+                let s = i as f64 / 10.;
+                (s, 50. + s.powi(2) / 0.01)
+            })
+            .collect();
+        let calibration =
+            calibrate_non_linear_energy_consumption_model(&samples).unwrap();
+        assert_abs_diff_eq!(calibration.model.alpha, 2., epsilon = 1e-2);
+        assert_relative_eq!(calibration.model.beta, 0.01, max_relative = 1e-2);
+        assert_abs_diff_eq!(calibration.model.phi_min, 50., epsilon = 1.);
+        assert!(calibration.fit.r_squared > 0.999);
+
+        // linear power is fitted by the smallest exponent greater than one
+        let samples: Vec<_> = (0..=10)
+            .map(|i| (i as f64 / 10., 100. + 10. * i as f64))
+            .collect();
+        let calibration =
+            calibrate_non_linear_energy_consumption_model(&samples).unwrap();
+        assert!(calibration.model.alpha > 1.);
+        assert!(calibration.model.alpha <= MAX_ALPHA);
+        assert!(calibration.model.beta.is_finite());
+
+        // constant power has no dynamic power
+        let samples: Vec<_> =
+            (0..=10).map(|i| (i as f64 / 10., 100.)).collect();
+        assert!(
+            calibrate_non_linear_energy_consumption_model(&samples).is_err()
+        );
+    }
+
+    #[test]
+    fn _3() {
+        init();
+
+        let calibration = calibrate_processing_times(
+            "batch".to_string(),
+            &[
+                ("cpu".to_string(), 1.),
+                ("cpu".to_string(), 3.),
+                ("gpu".to_string(), 0.5),
+            ],
+        )
+        .unwrap();
+        let processing_time_on = |key: &str| {
+            calibration
+                .model
+                .processing_time_on(&ServerType {
+                    key: key.to_string(),
+                    ..ServerType::default()
+                })
+                .raw()
+        };
+        assert_eq!(calibration.model.key, "batch");
+        assert_abs_diff_eq!(processing_time_on("cpu"), 2.);
+        assert_abs_diff_eq!(processing_time_on("gpu"), 0.5);
+        assert_abs_diff_eq!(
+            calibration.fit.r_squared,
+            1. - 2. / 3.5,
+            epsilon = 1e-9
+        );
+
+        assert!(calibrate_processing_times(
+            "batch".to_string(),
+            &[("cpu".to_string(), 0.)]
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,135 @@
+#[cfg(test)]
+mod carbon {
+    use crate::factories::{build_data_center_model, data_center_input};
+    use crate::{init, utils::hash_map};
+    use soco::{
+        algorithms::offline::{
+            multi_dimensional::{
+                convex_optimization::co,
+                optimal_graph_search::optimal_graph_search,
+            },
+            OfflineAlgorithm, OfflineOptions, OfflineResult,
+        },
+        config::Config,
+        model::{
+            data_center::{
+                model::{DataCenterModel, DEFAULT_KEY},
+                models::{
+                    energy_consumption::{
+                        EnergyConsumptionModel, LinearEnergyConsumptionModel,
+                    },
+                    energy_cost::{
+                        CarbonAwareEnergyCostModel, EnergyCostModel,
+                    },
+                },
+                DataCenterModelOutputFailure, DataCenterModelOutputSuccess,
+            },
+            Model, ModelOutput,
+        },
+        problem::{
+            FractionalSmoothedConvexOptimization,
+            IntegralSimplifiedSmoothedConvexOptimization, Problem,
+        },
+        result::Failure,
+    };
+    use std::sync::Arc;
+
+    /// Data center with $10$ idling servers consuming half of their peak power where the carbon intensity of the grid is $t$.
+    fn carbon_aware_model(carbon_price: f64) -> DataCenterModel {
+        build_data_center_model(
+            10,
+            f64::INFINITY,
+            EnergyConsumptionModel::Linear(hash_map(&[(
+                DEFAULT_KEY.to_string(),
+                LinearEnergyConsumptionModel {
+                    phi_min: 0.5,
+                    phi_max: 1.,
+                },
+            )])),
+            EnergyCostModel::CarbonAware(hash_map(&[(
+                DEFAULT_KEY.to_string(),
+                CarbonAwareEnergyCostModel {
+                    cost: Arc::new(|_| 1.),
+                    carbon_intensity: Arc::new(|t| t as f64),
+                    carbon_price,
+                },
+            )])),
+        )
+    }
+
+    #[test]
+    fn _1() {
+        init();
+
+        let free: FractionalSmoothedConvexOptimization<
+            DataCenterModelOutputSuccess,
+            DataCenterModelOutputFailure,
+        > = carbon_aware_model(0.).to(data_center_input(&[5.; 2]));
+        let priced: FractionalSmoothedConvexOptimization<
+            DataCenterModelOutputSuccess,
+            DataCenterModelOutputFailure,
+        > = carbon_aware_model(0.5).to(data_center_input(&[5.; 2]));
+
+        for t in 1..=2 {
+            let ModelOutput::Success(free_output) =
+                free.hit_cost(t, Config::single(10.)).output
+            else {
+                panic!("expected a successful output");
+            };
+            let ModelOutput::Success(output) =
+                priced.hit_cost(t, Config::single(10.)).output
+            else {
+                panic!("expected a successful output");
+            };
+
+            let consumption = free_output.energy_cost;
+            assert!(consumption > 0.);
+            assert_abs_diff_eq!(output.emissions, t as f64 * consumption);
+            assert_abs_diff_eq!(output.emissions, free_output.emissions);
+            assert_abs_diff_eq!(
+                output.energy_cost,
+                (1. + 0.5 * t as f64) * consumption
+            );
+        }
+    }
+
+    #[test]
+    fn _2() {
+        init();
+
+        let model = carbon_aware_model(0.);
+        let p: FractionalSmoothedConvexOptimization<
+            DataCenterModelOutputSuccess,
+            DataCenterModelOutputFailure,
+        > = model.to(data_center_input(&[5.; 2]));
+        let result = co
+            .solve(p.clone(), (), OfflineOptions::default())
+            .unwrap()
+            .xs();
+        let cost = p.objective_function(&result).unwrap();
+
+        let carbon_budget = cost.output.emissions() - 1.;
+        let constrained_result = co
+            .solve(
+                p.clone(),
+                (),
+                OfflineOptions::carbon_constrained(carbon_budget),
+            )
+            .unwrap()
+            .xs();
+        let constrained_cost =
+            p.objective_function(&constrained_result).unwrap();
+        assert!(constrained_cost.output.emissions() <= carbon_budget + 1e-3);
+        assert!(constrained_cost.cost >= cost.cost);
+
+        let p: IntegralSimplifiedSmoothedConvexOptimization<
+            DataCenterModelOutputSuccess,
+            DataCenterModelOutputFailure,
+        > = model.to(data_center_input(&[5.; 2]));
+        let result = optimal_graph_search.solve_with_default_options(
+            p,
+            OfflineOptions::carbon_constrained(carbon_budget),
+        );
+        assert!(matches!(result, Err(Failure::UnsupportedCarbonBudget)));
+    }
+}
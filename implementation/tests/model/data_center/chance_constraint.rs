@@ -0,0 +1,107 @@
+#[cfg(test)]
+mod chance_constraint {
+    use crate::factories::{data_center_input, data_center_model};
+    use crate::init;
+    use soco::{
+        config::Config,
+        model::{
+            data_center::{
+                loads::PredictedLoadProfile,
+                model::{DataCenterModel, DataCenterOnlineInput},
+                DataCenterModelOutputFailure, DataCenterModelOutputSuccess,
+            },
+            Model, ModelOutput,
+        },
+        problem::{FractionalSimplifiedSmoothedConvexOptimization, Online},
+        result::Failure,
+    };
+
+    /// Costs of all samples of the predicted time slot where one in four samples exceeds the capacity.
+    fn predicted_costs(model: DataCenterModel) -> Vec<(f64, bool)> {
+        let p: FractionalSimplifiedSmoothedConvexOptimization<
+            DataCenterModelOutputSuccess,
+            DataCenterModelOutputFailure,
+        > = model.to(data_center_input(&[5.]));
+        let mut o = Online { p, w: 1 };
+        model.update(
+            &mut o,
+            DataCenterOnlineInput {
+                loads: vec![
+                    PredictedLoadProfile::raw(vec![vec![5.]]),
+                    PredictedLoadProfile::raw(vec![vec![5., 5., 5., 50.]]),
+                ],
+            },
+        );
+        o.p.hitting_cost
+            .call_predictive(3, Config::single(10.))
+            .into_iter()
+            .map(|cost| {
+                (
+                    cost.cost.raw(),
+                    matches!(
+                        cost.output,
+                        ModelOutput::Failure(
+                            DataCenterModelOutputFailure::DemandExceedingSupply
+                        )
+                    ),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn _1() {
+        init();
+
+        let costs = predicted_costs(data_center_model(10, 100.));
+        assert_eq!(costs.len(), 4);
+        assert_eq!(costs.iter().filter(|(c, _)| c.is_infinite()).count(), 1);
+        assert!(costs.iter().all(|&(_, exceeded)| !exceeded));
+
+        let costs = predicted_costs(
+            data_center_model(10, 100.).with_chance_constraint(0.3),
+        );
+        assert!(costs.iter().all(|(c, _)| c.is_finite()));
+        let served =
+            costs.iter().map(|&(c, _)| c).fold(f64::INFINITY, f64::min);
+        let dropped = costs.iter().map(|&(c, _)| c).fold(0., f64::max);
+        assert!(dropped >= 50. * 100.);
+        assert!(served < 50. * 100.);
+
+        let costs = predicted_costs(
+            data_center_model(10, 100.).with_chance_constraint(0.2),
+        );
+        assert!(costs
+            .iter()
+            .all(|&(c, exceeded)| c.is_infinite() && exceeded));
+
+        let costs = predicted_costs(
+            data_center_model(10, f64::INFINITY).with_chance_constraint(0.3),
+        );
+        assert_eq!(costs.iter().filter(|(c, _)| c.is_infinite()).count(), 1);
+    }
+
+    #[test]
+    fn _2() {
+        init();
+
+        assert!(data_center_model(10, 100.)
+            .with_chance_constraint(0.3)
+            .verify()
+            .is_ok());
+        assert!(matches!(
+            data_center_model(10, 100.)
+                .with_chance_constraint(1.)
+                .verify(),
+            Err(Failure::Invalid(_))
+        ));
+
+        let mut deferrable_model =
+            data_center_model(10, 100.).with_chance_constraint(0.3);
+        deferrable_model.job_types[0].slack = Some(1);
+        assert!(matches!(
+            deferrable_model.verify(),
+            Err(Failure::Invalid(_))
+        ));
+    }
+}
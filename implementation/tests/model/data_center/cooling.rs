@@ -0,0 +1,150 @@
+#[cfg(test)]
+mod cooling {
+    use crate::factories::{data_center_input, data_center_model};
+    use crate::{init, utils::hash_map};
+    use soco::{
+        algorithms::offline::{
+            multi_dimensional::convex_optimization::co, OfflineAlgorithm,
+            OfflineOptions, OfflineResult,
+        },
+        config::Config,
+        model::{
+            data_center::{
+                model::{DataCenterModel, Location, DEFAULT_KEY},
+                models::{
+                    cooling::{CoolingModel, TemperatureDependentCoolingModel},
+                    energy_cost::{EnergyCostModel, LinearEnergyCostModel},
+                },
+                DataCenterModelOutputFailure, DataCenterModelOutputSuccess,
+            },
+            Model, ModelOutput,
+        },
+        problem::{FractionalSmoothedConvexOptimization, Problem},
+        result::Failure,
+    };
+    use std::sync::Arc;
+
+    /// Cooling of a location whose outside temperature is constant.
+    fn cooling(outside_temperature: f64) -> TemperatureDependentCoolingModel {
+        TemperatureDependentCoolingModel {
+            pue_min: 1.5,
+            load_factor: 0.5,
+            temperature_factor: 0.1,
+            reference_temperature: 20.,
+            outside_temperature: Arc::new(move |_| outside_temperature),
+        }
+    }
+
+    fn cooled_model(
+        cooling: TemperatureDependentCoolingModel,
+    ) -> DataCenterModel {
+        data_center_model(10, f64::INFINITY).with_cooling_model(
+            CoolingModel::TemperatureDependent(hash_map(&[(
+                DEFAULT_KEY.to_string(),
+                cooling,
+            )])),
+        )
+    }
+
+    #[test]
+    fn _1() {
+        init();
+
+        let input = data_center_input(&[5.]);
+        let p: FractionalSmoothedConvexOptimization<
+            DataCenterModelOutputSuccess,
+            DataCenterModelOutputFailure,
+        > = data_center_model(10, f64::INFINITY).to(input.clone());
+        let cooled_p: FractionalSmoothedConvexOptimization<
+            DataCenterModelOutputSuccess,
+            DataCenterModelOutputFailure,
+        > = cooled_model(cooling(30.)).to(input);
+
+        match (
+            p.hit_cost(1, Config::single(10.)).output,
+            cooled_p.hit_cost(1, Config::single(10.)).output,
+        ) {
+            (ModelOutput::Success(output), ModelOutput::Success(cooled)) => {
+                assert!(output.energy_cost > 0.);
+                // utilization of 0.5 and outside temperature 10 degrees above the reference
+                assert_relative_eq!(
+                    cooled.energy_cost,
+                    (1.5 + 0.5 * 0.5 + 0.1 * 10.) * output.energy_cost,
+                    max_relative = 1e-6
+                );
+                assert_abs_diff_eq!(cooled.revenue_loss, output.revenue_loss);
+            }
+            _ => panic!("expected successful outputs"),
+        }
+    }
+
+    #[test]
+    fn _2() {
+        init();
+
+        let mut model = data_center_model(10, f64::INFINITY);
+        model.locations.push(Location {
+            key: "hot".to_string(),
+            ..model.locations[0].clone()
+        });
+        model.energy_cost_model = EnergyCostModel::Linear(
+            [DEFAULT_KEY, "hot"]
+                .iter()
+                .map(|key| {
+                    (
+                        key.to_string(),
+                        LinearEnergyCostModel {
+                            cost: Arc::new(|_| 1.),
+                        },
+                    )
+                })
+                .collect(),
+        );
+        let model = model.with_cooling_model(
+            CoolingModel::TemperatureDependent(hash_map(&[
+                (DEFAULT_KEY.to_string(), cooling(10.)),
+                ("hot".to_string(), cooling(40.)),
+            ])),
+        );
+
+        let p: FractionalSmoothedConvexOptimization<
+            DataCenterModelOutputSuccess,
+            DataCenterModelOutputFailure,
+        > = model.to(data_center_input(&[5.; 2]));
+        let xs = co
+            .solve(p.clone(), (), OfflineOptions::default())
+            .unwrap()
+            .xs();
+        for t in 0..2 {
+            assert!(xs[t][0] > xs[t][1]);
+        }
+    }
+
+    #[test]
+    fn _3() {
+        init();
+
+        assert!(cooled_model(cooling(30.)).verify().is_ok());
+        assert!(matches!(
+            cooled_model(TemperatureDependentCoolingModel {
+                pue_min: 0.9,
+                ..cooling(30.)
+            })
+            .verify(),
+            Err(Failure::Invalid(_))
+        ));
+        assert!(matches!(
+            cooled_model(TemperatureDependentCoolingModel {
+                temperature_factor: -0.1,
+                ..cooling(30.)
+            })
+            .verify(),
+            Err(Failure::Invalid(_))
+        ));
+
+        // locations without cooling parameters are rejected
+        let mut model = cooled_model(cooling(30.));
+        model.locations[0].key = "other".to_string();
+        assert!(matches!(model.verify(), Err(Failure::Invalid(_))));
+    }
+}
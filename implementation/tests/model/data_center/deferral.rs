@@ -0,0 +1,188 @@
+#[cfg(test)]
+mod deferral {
+    use crate::factories::{
+        data_center_input, data_center_model, linear_energy_cost,
+    };
+    use crate::{init, utils::schedule};
+    use soco::{
+        algorithms::{
+            offline::{
+                multi_dimensional::{
+                    approx_graph_search::approx_graph_search,
+                    branch_and_bound_graph_search::branch_and_bound_graph_search,
+                    convex_optimization::co,
+                    optimal_graph_search::optimal_graph_search,
+                },
+                OfflineAlgorithm, OfflineOptions, OfflineResult,
+            },
+            online::multi_dimensional::horizon_control::receding_horizon_control::rhc,
+        },
+        model::{
+            data_center::{
+                model::DataCenterModel,
+                DataCenterModelOutputFailure, DataCenterModelOutputSuccess,
+            },
+            Model, ModelOutput,
+        },
+        problem::{
+            FractionalSimplifiedSmoothedConvexOptimization,
+            FractionalSmoothedConvexOptimization,
+            IntegralSimplifiedSmoothedConvexOptimization, Online, Problem,
+        },
+        result::Failure,
+    };
+
+    /// Data center with $m$ servers whose energy is expensive during the first time slot and where jobs may be deferred by `slack` time slots.
+    fn deferrable_model(m: i32, slack: Option<i32>) -> DataCenterModel {
+        let mut model = data_center_model(m, 3.);
+        model.energy_cost_model =
+            linear_energy_cost(|t| if t == 1 { 10. } else { 1. });
+        model.job_types[0].slack = slack;
+        model.job_types[0].deferral_cost = 0.1;
+        model
+    }
+
+    #[test]
+    fn _1() {
+        init();
+
+        let p: FractionalSimplifiedSmoothedConvexOptimization<
+            DataCenterModelOutputSuccess,
+            DataCenterModelOutputFailure,
+        > = deferrable_model(4, Some(1)).to(data_center_input(&[2., 0.]));
+        assert_eq!(p.d, 3);
+        assert_eq!(p.bounds, vec![4., 2., 2.]);
+
+        // processing deferred jobs once energy is cheap
+        let deferred = p
+            .objective_function(&schedule(&[[0., 2., 0.], [4., 0., 2.]]))
+            .unwrap()
+            .cost;
+        let processed = p
+            .objective_function(&schedule(&[[4., 0., 0.], [0., 0., 0.]]))
+            .unwrap()
+            .cost;
+        assert!(deferred.raw().is_finite());
+        assert!(deferred < processed);
+
+        // jobs missing their deadline are priced by the revenue loss model
+        let missed = p
+            .objective_function(&schedule(&[[0., 2., 0.], [0., 0., 0.]]))
+            .unwrap();
+        match missed.output {
+            ModelOutput::Success(output) => {
+                assert_relative_eq!(output.revenue_loss, 6.)
+            }
+            _ => panic!("jobs missing their deadline must not fail"),
+        }
+
+        // only deferred jobs can be processed later
+        let exceeded = p
+            .objective_function(&schedule(&[[4., 1., 0.], [4., 0., 2.]]))
+            .unwrap();
+        assert!(exceeded.cost.raw().is_infinite());
+        assert!(matches!(
+            exceeded.output,
+            ModelOutput::Failure(
+                DataCenterModelOutputFailure::DeferredLoadExceeded
+            )
+        ));
+    }
+
+    #[test]
+    fn _2() {
+        init();
+
+        let solve = |model: DataCenterModel| {
+            let p: FractionalSmoothedConvexOptimization<
+                DataCenterModelOutputSuccess,
+                DataCenterModelOutputFailure,
+            > = model.to(data_center_input(&[2., 0.]));
+            let xs = co
+                .solve(p.clone(), (), OfflineOptions::default())
+                .unwrap()
+                .xs();
+            let cost = p.objective_function(&xs).unwrap().cost.raw();
+            (xs, cost)
+        };
+
+        let (_, cost) = solve(deferrable_model(4, None));
+        let (xs, deferred_cost) = solve(deferrable_model(4, Some(1)));
+        assert!(xs[0][1] > 1.);
+        assert!(deferred_cost < cost - 5.);
+
+        // deferred loads are continuous, so integral graph searches reject them
+        let p: IntegralSimplifiedSmoothedConvexOptimization<
+            DataCenterModelOutputSuccess,
+            DataCenterModelOutputFailure,
+        > = deferrable_model(2, Some(1)).to(data_center_input(&[2., 0.]));
+        let is_rejected = |result: Result<(), Failure>| {
+            matches!(
+                result,
+                Err(Failure::UnsupportedContinuousDimensions(dimensions)) if dimensions == vec![1, 2]
+            )
+        };
+        assert!(is_rejected(
+            optimal_graph_search
+                .solve_with_default_options(
+                    p.clone(),
+                    OfflineOptions::default()
+                )
+                .map(|_| ())
+        ));
+        assert!(is_rejected(
+            approx_graph_search
+                .solve_with_default_options(
+                    p.clone(),
+                    OfflineOptions::default()
+                )
+                .map(|_| ())
+        ));
+        assert!(is_rejected(
+            branch_and_bound_graph_search
+                .solve_with_default_options(p, OfflineOptions::default())
+                .map(|_| ())
+        ));
+
+        let mut p: FractionalSimplifiedSmoothedConvexOptimization<
+            DataCenterModelOutputSuccess,
+            DataCenterModelOutputFailure,
+        > = deferrable_model(4, Some(1)).to(data_center_input(&[2., 0., 0.]));
+        p.t_end = 1;
+        let mut o = Online { p, w: 1 };
+        let (xs, _) = o.offline_stream(&rhc, 3, ()).unwrap();
+        o.p.t_end = 2;
+        assert!(xs[0][1] > 1.);
+        assert!(o.p.objective_function(&xs).unwrap().cost.raw().is_finite());
+    }
+
+    #[test]
+    fn _3() {
+        init();
+
+        // processing the jobs is cheaper than dropping them, but more expensive than deferring them for free
+        let mut model = data_center_model(4, 30.);
+        model.job_types[0].slack = Some(1);
+        model.job_types[0].deferral_cost = 0.1;
+
+        let p: FractionalSmoothedConvexOptimization<
+            DataCenterModelOutputSuccess,
+            DataCenterModelOutputFailure,
+        > = model.to(data_center_input(&[2.]));
+        let deferred =
+            p.objective_function(&schedule(&[[0., 2., 0.]])).unwrap();
+        match deferred.output {
+            ModelOutput::Success(output) => {
+                assert_relative_eq!(output.revenue_loss, 60.)
+            }
+            _ => panic!("jobs deferred past the time horizon must not fail"),
+        }
+
+        let xs = co
+            .solve(p.clone(), (), OfflineOptions::default())
+            .unwrap()
+            .xs();
+        assert_abs_diff_eq!(xs[0][1], 0., epsilon = 1e-2);
+        assert!(p.objective_function(&xs).unwrap().cost < deferred.cost);
+    }
+}
@@ -0,0 +1,147 @@
+#[cfg(test)]
+mod delay {
+    use crate::factories::{data_center_input, data_center_model};
+    use crate::{init, utils::hash_map};
+    use noisy_float::prelude::*;
+    use soco::{
+        config::Config,
+        model::{
+            data_center::{
+                model::{ServerType, DEFAULT_KEY},
+                models::{
+                    delay::{
+                        average_delay, percentile_delay, DelayModel,
+                        ErlangCDelayModel, FirstComeFirstServedDelayModel,
+                    },
+                    revenue_loss::{
+                        RevenueLossModel, TailLatencyRevenueLossModel,
+                    },
+                },
+                DataCenterModelOutputFailure, DataCenterModelOutputSuccess,
+            },
+            Model, ModelOutput,
+        },
+        problem::{FractionalSmoothedConvexOptimization, Problem},
+        result::Failure,
+    };
+
+    fn erlang_c(c: i32) -> DelayModel {
+        DelayModel::ErlangC(hash_map(&[(
+            DEFAULT_KEY.to_string(),
+            ErlangCDelayModel { c },
+        )]))
+    }
+
+    fn first_come_first_served(scv: f64) -> DelayModel {
+        DelayModel::FirstComeFirstServed(hash_map(&[(
+            DEFAULT_KEY.to_string(),
+            FirstComeFirstServedDelayModel { scv },
+        )]))
+    }
+
+    /// Revenue loss of exceeding `sla` at the given percentile of the response time.
+    fn tail_latency(percentile: f64, sla: f64) -> RevenueLossModel {
+        RevenueLossModel::TailLatency(hash_map(&[(
+            DEFAULT_KEY.to_string(),
+            TailLatencyRevenueLossModel {
+                gamma: 1.,
+                percentile,
+                sla,
+                penalty: f64::INFINITY,
+            },
+        )]))
+    }
+
+    #[test]
+    fn _1() {
+        init();
+
+        let server_type = ServerType::default();
+        let delay = |delay_model: DelayModel, number_of_jobs: f64| {
+            delay_model
+                .delay(1., &server_type, n64(number_of_jobs), n64(0.5))
+                .raw()
+        };
+
+        for number_of_jobs in [0., 0.5, 1., 1.5] {
+            let mm1 = average_delay(1., n64(number_of_jobs), n64(0.5)).raw();
+            assert_relative_eq!(
+                delay(erlang_c(1), number_of_jobs),
+                mm1,
+                max_relative = 1e-6
+            );
+            assert_relative_eq!(
+                delay(first_come_first_served(1.), number_of_jobs),
+                mm1,
+                max_relative = 1e-6
+            );
+            if number_of_jobs > 0. {
+                assert!(delay(erlang_c(4), number_of_jobs) < mm1);
+                assert!(
+                    delay(first_come_first_served(0.), number_of_jobs) < mm1
+                );
+                assert!(
+                    delay(first_come_first_served(4.), number_of_jobs) > mm1
+                );
+            }
+        }
+        assert!(delay(erlang_c(4), 2.).is_infinite());
+        assert!(delay(first_come_first_served(1.), 2.).is_infinite());
+
+        assert_relative_eq!(
+            percentile_delay(0.99, n64(2.)).raw(),
+            2. * 100_f64.ln(),
+            max_relative = 1e-6
+        );
+    }
+
+    #[test]
+    fn _2() {
+        init();
+
+        let revenue_loss =
+            |revenue_loss_model: Option<RevenueLossModel>,
+             delay_model: Option<DelayModel>| {
+                let mut model = data_center_model(10, f64::INFINITY);
+                if let Some(revenue_loss_model) = revenue_loss_model {
+                    model.revenue_loss_model = revenue_loss_model;
+                }
+                if let Some(delay_model) = delay_model {
+                    model = model.with_delay_model(delay_model);
+                }
+                let p: FractionalSmoothedConvexOptimization<
+                    DataCenterModelOutputSuccess,
+                    DataCenterModelOutputFailure,
+                > = model.to(data_center_input(&[5.]));
+                match p.hit_cost(1, Config::single(10.)).output {
+                    ModelOutput::Success(output) => output.revenue_loss,
+                    _ => panic!("expected a successful output"),
+                }
+            };
+        // each of the 10 servers processes 0.5 jobs of duration 1
+        let queueing_delay = average_delay(1., n64(0.5), n64(1.)).raw();
+        let loss = revenue_loss(None, None);
+        assert_relative_eq!(
+            revenue_loss(Some(tail_latency(0.99, 0.)), None) - loss,
+            5. * (100_f64.ln() - 1.) * queueing_delay,
+            max_relative = 1e-6
+        );
+        assert!(
+            revenue_loss(Some(tail_latency(0.99, 0.)), Some(erlang_c(5)))
+                < revenue_loss(Some(tail_latency(0.99, 0.)), None)
+        );
+        assert_abs_diff_eq!(
+            revenue_loss(Some(tail_latency(0.99, 100.)), None),
+            0.
+        );
+    }
+
+    #[test]
+    fn _3() {
+        init();
+
+        let mut model = data_center_model(10, f64::INFINITY);
+        model.revenue_loss_model = tail_latency(1., 0.);
+        assert!(matches!(model.verify(), Err(Failure::Invalid(_))));
+    }
+}
@@ -0,0 +1,142 @@
+#[cfg(test)]
+mod demand_charge {
+    use crate::factories::{data_center_input, data_center_model};
+    use crate::{init, utils::schedule};
+    use soco::{
+        algorithms::{
+            offline::{
+                multi_dimensional::convex_optimization::co, OfflineAlgorithm,
+                OfflineOptions, OfflineResult,
+            },
+            online::multi_dimensional::horizon_control::receding_horizon_control::rhc,
+        },
+        config::Config,
+        model::{
+            data_center::{
+                model::{DataCenterModel, DEFAULT_KEY},
+                models::energy_cost::DemandCharge,
+                DataCenterModelOutputFailure, DataCenterModelOutputSuccess,
+            },
+            Model, ModelOutput,
+        },
+        problem::{
+            FractionalSimplifiedSmoothedConvexOptimization,
+            FractionalSmoothedConvexOptimization, Online, Problem,
+        },
+        result::Failure,
+    };
+
+    /// Data center with four servers whose peak power is charged at a price of $10$ during billing windows of two time slots.
+    fn charged_model(window: i32) -> DataCenterModel {
+        let mut model = data_center_model(4, 3.);
+        model.locations = model
+            .locations
+            .into_iter()
+            .map(|location| {
+                location.with_demand_charge(DemandCharge { price: 10., window })
+            })
+            .collect();
+        model
+    }
+
+    /// Power drawn by the default location during time slot $t$ with $x$ servers.
+    fn power(
+        p: &FractionalSimplifiedSmoothedConvexOptimization<
+            DataCenterModelOutputSuccess,
+            DataCenterModelOutputFailure,
+        >,
+        t: i32,
+        x: f64,
+    ) -> f64 {
+        match p.hit_cost(t, Config::single(x)).output {
+            ModelOutput::Success(output) => output.peak_power[DEFAULT_KEY],
+            _ => panic!("expected a successful output"),
+        }
+    }
+
+    #[test]
+    fn _1() {
+        init();
+
+        let p: FractionalSimplifiedSmoothedConvexOptimization<
+            DataCenterModelOutputSuccess,
+            DataCenterModelOutputFailure,
+        > = charged_model(2).to(data_center_input(&[2., 2., 2., 2.]));
+        assert_eq!(p.d, 1);
+        assert_eq!(p.peak_charges().len(), 1);
+        assert!(power(&p, 1, 4.) > power(&p, 1, 3.));
+
+        // the peak power of each billing window is paid once
+        let q: FractionalSimplifiedSmoothedConvexOptimization<
+            DataCenterModelOutputSuccess,
+            DataCenterModelOutputFailure,
+        > = data_center_model(4, 3.).to(data_center_input(&[2., 2., 2., 2.]));
+        let xs = [[3.], [4.], [4.], [3.]];
+        let charged = p.objective_function(&schedule(&xs)).unwrap().cost;
+        let uncharged = q.objective_function(&schedule(&xs)).unwrap().cost;
+        assert_relative_eq!(
+            charged.raw() - uncharged.raw(),
+            10. * (power(&p, 2, 4.) + power(&p, 3, 4.)),
+            max_relative = 1e-6
+        );
+
+        assert!(matches!(
+            charged_model(0).verify(),
+            Err(Failure::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn _2() {
+        init();
+
+        let solve = |model: DataCenterModel| {
+            let p: FractionalSmoothedConvexOptimization<
+                DataCenterModelOutputSuccess,
+                DataCenterModelOutputFailure,
+            > = model.to(data_center_input(&[2., 2.]));
+            co.solve(p, (), OfflineOptions::default()).unwrap().xs()
+        };
+
+        let p: FractionalSmoothedConvexOptimization<
+            DataCenterModelOutputSuccess,
+            DataCenterModelOutputFailure,
+        > = charged_model(2).to(data_center_input(&[2., 2.]));
+        let xs = solve(charged_model(2));
+        let uncharged_xs = solve(data_center_model(4, 3.));
+        let cost = p.objective_function(&xs).unwrap().cost.raw();
+        let candidate = p.objective_function(&uncharged_xs).unwrap().cost.raw();
+        assert!(cost <= candidate + 1e-3);
+        for t in 0..2 {
+            assert!(xs[t][0] < uncharged_xs[t][0]);
+        }
+    }
+
+    #[test]
+    fn _3() {
+        init();
+
+        let stream = |t_end: i32| {
+            let mut p: FractionalSimplifiedSmoothedConvexOptimization<
+                DataCenterModelOutputSuccess,
+                DataCenterModelOutputFailure,
+            > = charged_model(2).to(data_center_input(&[2., 3., 1., 1.]));
+            p.t_end = 1;
+            let mut o = Online { p, w: 0 };
+            let (xs, memory) = o.offline_stream(&rhc, t_end, ()).unwrap();
+            (o.p, xs, memory.unwrap().peaks)
+        };
+
+        // the running peak is kept during the billing window
+        let (p, xs, peaks) = stream(2);
+        assert_relative_eq!(
+            peaks[0],
+            power(&p, 1, xs[0][0]).max(power(&p, 2, xs[1][0]))
+        );
+
+        // and reset once the next billing window begins
+        let (p, xs, peaks) = stream(3);
+        assert_relative_eq!(peaks[0], power(&p, 3, xs[2][0]));
+        assert!(p.objective_function(&xs).unwrap().cost.raw().is_finite());
+    }
+}
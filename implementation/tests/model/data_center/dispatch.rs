@@ -0,0 +1,143 @@
+#[cfg(test)]
+mod dispatch {
+    use crate::factories::{data_center_hit_cost, data_center_model};
+    use crate::{init, utils::hash_map};
+    use soco::{
+        config::Config,
+        model::{
+            data_center::{
+                loads::{
+                    apply_loads, LoadFractions, LoadObjective, LoadProfile,
+                },
+                model::{DataCenterModel, JobType, Location},
+                models::energy_cost::{EnergyCostModel, LinearEnergyCostModel},
+                DataCenterModelOutputSuccess, IntermediateObjective,
+                IntermediateResult,
+            },
+            ModelOutput,
+        },
+    };
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    /// Two locations `a` and `b` with two servers each where energy costs the given prices.
+    fn priced_model(price_a: f64, price_b: f64) -> DataCenterModel {
+        let mut model = data_center_model(2, f64::INFINITY);
+        model.locations = ["a", "b"]
+            .iter()
+            .map(|key| Location {
+                key: key.to_string(),
+                ..model.locations[0].clone()
+            })
+            .collect();
+        let cost = |price: f64| LinearEnergyCostModel {
+            cost: Arc::new(move |_| price),
+        };
+        model.energy_cost_model = EnergyCostModel::Linear(hash_map(&[
+            ("a".to_string(), cost(price_a)),
+            ("b".to_string(), cost(price_b)),
+        ]));
+        model
+    }
+
+    fn hit_cost(model: DataCenterModel) -> DataCenterModelOutputSuccess {
+        match data_center_hit_cost(model, 2., vec![2., 2.]) {
+            ModelOutput::Success(output) => output,
+            _ => panic!("the load can be processed"),
+        }
+    }
+
+    #[test]
+    fn _1() {
+        init();
+
+        // dispatch is deterministic
+        let output = hit_cost(priced_model(1., 2.));
+        let other_output = hit_cost(priced_model(1., 2.));
+        assert_eq!(output.energy_cost, other_output.energy_cost);
+        assert_eq!(output.revenue_loss, other_output.revenue_loss);
+
+        // more load is dispatched to the location with cheaper energy
+        let balanced = hit_cost(priced_model(50.5, 50.5));
+        let output = hit_cost(priced_model(1., 100.));
+        assert!(output.energy_cost < balanced.energy_cost);
+        assert!(output.revenue_loss > balanced.revenue_loss);
+    }
+
+    /// Counts the evaluations of the costs of the dimensions of a model, optionally hiding that its objective is separable.
+    struct Counted {
+        model: DataCenterModel,
+        separable: bool,
+        evaluations: AtomicUsize,
+    }
+    impl LoadObjective<f64> for Counted {
+        fn cost(
+            &self,
+            t: i32,
+            x: &Config<f64>,
+            lambda: &LoadProfile,
+            zs: &LoadFractions,
+        ) -> IntermediateObjective {
+            // the objective evaluates the costs of all dimensions
+            self.evaluations
+                .fetch_add(x.d() as usize, Ordering::Relaxed);
+            self.model.cost(t, x, lambda, zs)
+        }
+
+        fn dimension_cost(
+            &self,
+            t: i32,
+            x: &Config<f64>,
+            k: usize,
+            loads: &LoadProfile,
+        ) -> Option<IntermediateResult> {
+            if !self.separable {
+                return None;
+            }
+            self.evaluations.fetch_add(1, Ordering::Relaxed);
+            self.model.dimension_cost(t, x, k, loads)
+        }
+    }
+
+    #[test]
+    fn _2() {
+        init();
+
+        // water-filling of separable objectives with several job types matches projected gradient descent with fewer evaluations
+        let mut model = priced_model(1., 2.);
+        model.job_types = vec![
+            JobType::default(),
+            JobType {
+                processing_time_on: Arc::new(|_| 0.5),
+                ..JobType::default()
+            },
+        ];
+        let lambda = LoadProfile::raw(vec![1.5, 1.]);
+        let dispatch = |separable| {
+            let objective = Counted {
+                model: model.clone(),
+                separable,
+                evaluations: AtomicUsize::new(0),
+            };
+            let cost = apply_loads(
+                2,
+                2,
+                &objective,
+                &[],
+                &lambda,
+                1,
+                Config::new(vec![2., 2.]),
+            )
+            .cost
+            .raw();
+            (cost, objective.evaluations.into_inner())
+        };
+        let (cost, evaluations) = dispatch(true);
+        let (pgd_cost, pgd_evaluations) = dispatch(false);
+        assert!(cost.is_finite());
+        assert_abs_diff_eq!(cost, pgd_cost, epsilon = 1e-4);
+        assert!(evaluations < pgd_evaluations);
+    }
+}
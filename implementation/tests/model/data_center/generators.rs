@@ -0,0 +1,200 @@
+#[cfg(test)]
+mod generators {
+    use crate::init;
+    use soco::model::data_center::{
+        generators::{
+            into_energy_cost_model, LoadGenerator, LoadPattern, PriceGenerator,
+        },
+        loads::LoadProfile,
+    };
+
+    fn mean(xs: &[f64]) -> f64 {
+        xs.iter().sum::<f64>() / xs.len() as f64
+    }
+
+    fn std_dev(xs: &[f64]) -> f64 {
+        let m = mean(xs);
+        (xs.iter().map(|x| (x - m).powi(2)).sum::<f64>() / xs.len() as f64)
+            .sqrt()
+    }
+
+    #[test]
+    fn _1() {
+        init();
+
+        let generator = LoadGenerator::new(
+            vec![
+                LoadPattern::Diurnal {
+                    mean: 10.,
+                    amplitude: 5.,
+                    period: 24.,
+                    phase: 0.,
+                    noise: 0.,
+                },
+                LoadPattern::TrendSeasonal {
+                    level: 2.,
+                    trend: 0.5,
+                    seasons: vec![],
+                    noise: 0.,
+                },
+                LoadPattern::Diurnal {
+                    mean: 10.,
+                    amplitude: 5.,
+                    period: 24.,
+                    phase: 0.,
+                    noise: 1.,
+                },
+            ],
+            42,
+        );
+        let loads = generator.generate(48).unwrap();
+        assert_eq!(loads.len(), 48);
+        assert!(loads.iter().all(|load| load.e() == 3));
+
+        // patterns without noise are deterministic
+        assert_abs_diff_eq!(loads[6][0].raw(), 15., epsilon = 1e-9);
+        assert_abs_diff_eq!(loads[18][0].raw(), 5., epsilon = 1e-9);
+        assert_abs_diff_eq!(loads[10][1].raw(), 7., epsilon = 1e-9);
+
+        // noisy loads are reproducible given the seed and independent of other job types
+        assert_eq!(loads, generator.generate(48).unwrap());
+        let shifted = LoadGenerator::new(
+            vec![generator.patterns[2].clone()],
+            generator.seed + 2,
+        )
+        .generate(48)
+        .unwrap();
+        assert!(loads
+            .iter()
+            .zip(&shifted)
+            .all(|(load, shifted)| load[2] == shifted[0]));
+        assert_ne!(
+            loads,
+            LoadGenerator::new(generator.patterns.clone(), 7)
+                .generate(48)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn _2() {
+        init();
+
+        let loads = LoadGenerator::new(
+            vec![
+                LoadPattern::Mmpp {
+                    rates: vec![2., 50.],
+                    transitions: vec![vec![0.9, 0.1], vec![0.3, 0.7]],
+                },
+                LoadPattern::FlashCrowd {
+                    base: 10.,
+                    probability: 0.05,
+                    magnitude: 4.,
+                    decay: 0.5,
+                    noise: 0.,
+                },
+            ],
+            3,
+        )
+        .generate(1000)
+        .unwrap();
+
+        // arrivals are integral and bursty
+        let arrivals: Vec<_> = loads.iter().map(|load| load[0].raw()).collect();
+        assert!(arrivals.iter().all(|&l| l >= 0. && l == l.round()));
+        assert!(arrivals.iter().any(|&l| l >= 30.));
+        assert!(arrivals.iter().any(|&l| l <= 5.));
+
+        // flash crowds exceed the base load and decay
+        let crowds: Vec<_> = loads.iter().map(|load| load[1].raw()).collect();
+        assert!(crowds.iter().all(|&l| l >= 10.));
+        let peak = crowds.iter().cloned().fold(0., f64::max);
+        assert!(peak >= 50.);
+
+        // invalid patterns are rejected
+        assert!(LoadGenerator::new(
+            vec![LoadPattern::Mmpp {
+                rates: vec![1., 2.],
+                transitions: vec![vec![0.5, 0.4], vec![0., 1.]],
+            }],
+            0
+        )
+        .generate(10)
+        .is_err());
+        assert!(LoadGenerator::new(
+            vec![LoadPattern::FlashCrowd {
+                base: 1.,
+                probability: 0.1,
+                magnitude: 1.,
+                decay: 1.,
+                noise: 0.,
+            }],
+            0
+        )
+        .generate(10)
+        .is_err());
+    }
+
+    #[test]
+    fn _3() {
+        init();
+
+        let loads = LoadGenerator::new(
+            vec![
+                LoadPattern::Diurnal {
+                    mean: 10.,
+                    amplitude: 5.,
+                    period: 24.,
+                    phase: 0.,
+                    noise: 1.,
+                },
+                LoadPattern::Diurnal {
+                    mean: 5.,
+                    amplitude: 2.,
+                    period: 24.,
+                    phase: 6.,
+                    noise: 1.,
+                },
+            ],
+            1,
+        )
+        .generate(96)
+        .unwrap();
+        let totals: Vec<_> =
+            loads.iter().map(|load| load.total().raw()).collect();
+
+        let prices = PriceGenerator::new(30., 5., 0.8, 2)
+            .generate(&loads)
+            .unwrap();
+        assert_eq!(prices.len(), 96);
+        assert_abs_diff_eq!(mean(&prices), 30., epsilon = 1e-9);
+        assert_abs_diff_eq!(std_dev(&prices), 5., epsilon = 1e-9);
+        let covariance = prices
+            .iter()
+            .zip(&totals)
+            .map(|(p, l)| (p - mean(&prices)) * (l - mean(&totals)))
+            .sum::<f64>()
+            / prices.len() as f64;
+        assert_abs_diff_eq!(
+            covariance / (std_dev(&prices) * std_dev(&totals)),
+            0.8,
+            epsilon = 1e-9
+        );
+
+        // prices of a constant load are uncorrelated
+        let constant = vec![LoadProfile::raw(vec![1., 2.]); 10];
+        let prices = PriceGenerator::new(30., 5., 1., 2)
+            .generate(&constant)
+            .unwrap();
+        assert_abs_diff_eq!(std_dev(&prices), 5., epsilon = 1e-9);
+        assert!(PriceGenerator::new(30., 5., 1.5, 2)
+            .generate(&constant)
+            .is_err());
+
+        // prices become an energy cost model
+        let model = into_energy_cost_model(prices.clone()).unwrap();
+        assert_eq!((model.cost)(1), prices[0]);
+        assert_eq!((model.cost)(20), prices[9]);
+        assert!(into_energy_cost_model(vec![]).is_err());
+    }
+}
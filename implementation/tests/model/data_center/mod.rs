@@ -0,0 +1,36 @@
+#[cfg(test)]
+mod affinity;
+#[cfg(test)]
+mod battery;
+#[cfg(test)]
+mod boot_delay;
+#[cfg(test)]
+mod breakdown;
+#[cfg(test)]
+mod cache;
+#[cfg(test)]
+mod calibration;
+#[cfg(test)]
+mod carbon;
+#[cfg(test)]
+mod chance_constraint;
+#[cfg(test)]
+mod cooling;
+#[cfg(test)]
+mod deferral;
+#[cfg(test)]
+mod delay;
+#[cfg(test)]
+mod demand_charge;
+#[cfg(test)]
+mod dispatch;
+#[cfg(test)]
+mod generators;
+#[cfg(test)]
+mod rental;
+#[cfg(test)]
+mod resources;
+#[cfg(test)]
+mod simulation;
+#[cfg(test)]
+mod transfer;
@@ -0,0 +1,154 @@
+#[cfg(test)]
+mod rental {
+    use crate::factories::{
+        data_center_hit_cost, data_center_input, data_center_model,
+    };
+    use crate::{init, utils::hash_map};
+    use soco::{
+        model::{
+            data_center::{
+                model::{
+                    DataCenterModel, DataCenterOfflineInput,
+                    DataCenterOnlineInput, DEFAULT_KEY,
+                },
+                models::rental_cost::{RentalCost, RentalCostModel},
+                DataCenterModelOutputFailure, DataCenterModelOutputSuccess,
+            },
+            Model, ModelOutput,
+        },
+        problem::{
+            FractionalSimplifiedSmoothedConvexOptimization,
+            SmoothedLoadOptimization,
+        },
+        result::Failure,
+    };
+
+    /// Servers which are billed per two time slots for at least two billing periods.
+    fn rental_cost(reserved: i32) -> RentalCost {
+        RentalCost {
+            on_demand_price: 2.,
+            reserved_price: 1.,
+            billing_period: 2,
+            minimum_term: 2,
+            reserved: hash_map(&[(DEFAULT_KEY.to_string(), reserved)]),
+        }
+    }
+
+    /// Data center with four rented servers.
+    fn with_rental_cost(rental_cost: RentalCost) -> DataCenterModel {
+        data_center_model(4, 3.).with_rental_cost_model(RentalCostModel::new(
+            hash_map(&[(DEFAULT_KEY.to_string(), rental_cost)]),
+        ))
+    }
+
+    /// Data center with four rented servers of which `reserved` are reserved.
+    fn rented_model(reserved: i32) -> DataCenterModel {
+        with_rental_cost(rental_cost(reserved))
+    }
+
+    fn hit_cost(
+        model: DataCenterModel,
+        x: f64,
+    ) -> DataCenterModelOutputSuccess {
+        match data_center_hit_cost(model, 0.5, vec![x]) {
+            ModelOutput::Success(output) => output,
+            _ => panic!("the load can be processed"),
+        }
+    }
+
+    #[test]
+    fn _1() {
+        init();
+
+        // reserved instances are paid whether they are used or not
+        assert_relative_eq!(hit_cost(rented_model(1), 1.).rental_cost, 0.5);
+        assert_relative_eq!(hit_cost(rented_model(1), 3.).rental_cost, 2.5);
+        assert_relative_eq!(
+            hit_cost(rented_model(1), 3.).energy_cost,
+            hit_cost(data_center_model(4, 3.), 3.).energy_cost
+        );
+
+        // the remaining cost of the minimum term is paid when renting
+        let p: FractionalSimplifiedSmoothedConvexOptimization<
+            DataCenterModelOutputSuccess,
+            DataCenterModelOutputFailure,
+        > = rented_model(1).to(data_center_input(&[0.5]));
+        let q: FractionalSimplifiedSmoothedConvexOptimization<
+            DataCenterModelOutputSuccess,
+            DataCenterModelOutputFailure,
+        > = data_center_model(4, 3.).to(data_center_input(&[0.5]));
+        assert_relative_eq!(p.switching_cost[0] - q.switching_cost[0], 3.);
+    }
+
+    #[test]
+    fn _2() {
+        init();
+
+        let p: SmoothedLoadOptimization<i32> =
+            rented_model(0).to(data_center_input(&[0.5]));
+        let q: SmoothedLoadOptimization<i32> =
+            data_center_model(4, 3.).to(data_center_input(&[0.5]));
+        assert_relative_eq!(p.hitting_cost[0] - q.hitting_cost[0], 1.);
+        assert_relative_eq!(p.switching_cost[0] - q.switching_cost[0], 3.);
+    }
+
+    #[test]
+    fn _3() {
+        init();
+
+        let verify_slo = |model: &DataCenterModel| {
+            <DataCenterModel as Model<
+                i32,
+                SmoothedLoadOptimization<i32>,
+                DataCenterOfflineInput,
+                DataCenterOnlineInput,
+                (),
+                DataCenterModelOutputFailure,
+            >>::verify(model)
+        };
+
+        // reserved instances are only rejected by load optimization
+        assert!(rented_model(1).verify().is_ok());
+        assert!(verify_slo(&rented_model(0)).is_ok());
+        assert!(matches!(
+            verify_slo(&rented_model(1)),
+            Err(Failure::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn _4() {
+        init();
+
+        assert!(rented_model(4).verify().is_ok());
+        for rental_cost in [
+            rental_cost(-1),
+            rental_cost(5),
+            RentalCost {
+                billing_period: 0,
+                ..rental_cost(0)
+            },
+            RentalCost {
+                minimum_term: 0,
+                ..rental_cost(0)
+            },
+            RentalCost {
+                on_demand_price: -1.,
+                ..rental_cost(0)
+            },
+            RentalCost {
+                reserved_price: -1.,
+                ..rental_cost(0)
+            },
+            RentalCost {
+                reserved: hash_map(&[("other".to_string(), 1)]),
+                ..rental_cost(0)
+            },
+        ] {
+            assert!(matches!(
+                with_rental_cost(rental_cost).verify(),
+                Err(Failure::Invalid(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod resources {
+    use crate::factories::{
+        data_center_hit_cost, data_center_model,
+        heterogeneous_data_center_model,
+    };
+    use crate::{init, utils::hash_map};
+    use soco::model::{
+        data_center::{
+            model::{DataCenterModel, JobType, ServerType},
+            DataCenterModelOutputFailure,
+        },
+        ModelOutput,
+    };
+
+    /// Single location with four `small` servers with one unit of memory and four `large` servers with four units of memory
+    /// where jobs hold two units of memory.
+    fn memory_bound_model() -> DataCenterModel {
+        let mut model = heterogeneous_data_center_model(
+            &["small", "large"],
+            4,
+            f64::INFINITY,
+        );
+        for (server_type, memory) in model.server_types.iter_mut().zip([1., 4.])
+        {
+            *server_type = server_type.clone().with_resources(
+                hash_map(&[("memory".to_string(), memory)]),
+                hash_map(&[]),
+            );
+        }
+        model.job_types = vec![JobType::default()
+            .with_demands(hash_map(&[("memory".to_string(), 2.)]))];
+        model
+    }
+
+    #[test]
+    fn _1() {
+        init();
+
+        // `small` servers can hold the memory of at most two jobs
+        match data_center_hit_cost(memory_bound_model(), 3., vec![4., 4.]) {
+            ModelOutput::Success(output) => {
+                let z = output.assignments[0][0][0];
+                assert!(3. * z * 2. <= 4. + 1e-2);
+                assert_abs_diff_eq!(
+                    z + output.assignments[0][0][1],
+                    1.,
+                    epsilon = 1e-6
+                );
+            }
+            _ => panic!("the memory of `large` servers suffices"),
+        }
+
+        assert!(matches!(
+            data_center_hit_cost(memory_bound_model(), 3., vec![4., 0.]),
+            ModelOutput::Failure(
+                DataCenterModelOutputFailure::ResourceCapacityExceeded
+            )
+        ));
+    }
+
+    #[test]
+    fn _2() {
+        init();
+
+        // processing time is utilized by half and memory by $0.8$
+        let energy_cost = |utilization_weights: Option<&[(String, f64)]>| {
+            let mut model = data_center_model(1, f64::INFINITY);
+            if let Some(utilization_weights) = utilization_weights {
+                model.server_types = vec![ServerType::default()
+                    .with_resources(
+                        hash_map(&[("memory".to_string(), 1.)]),
+                        hash_map(utilization_weights),
+                    )];
+                model.job_types = vec![JobType::default()
+                    .with_demands(hash_map(&[("memory".to_string(), 1.6)]))];
+            }
+            match data_center_hit_cost(model, 0.5, vec![1.]) {
+                ModelOutput::Success(output) => output.energy_cost,
+                _ => panic!("the load can be processed"),
+            }
+        };
+
+        assert_abs_diff_eq!(energy_cost(None), 0.75, epsilon = 1e-6);
+        assert_abs_diff_eq!(energy_cost(Some(&[])), 0.9, epsilon = 1e-6);
+        assert_abs_diff_eq!(
+            energy_cost(Some(&[("memory".to_string(), 0.5)])),
+            0.825,
+            epsilon = 1e-6
+        );
+    }
+
+    #[test]
+    fn _3() {
+        init();
+
+        let mut model = memory_bound_model();
+        assert!(model.verify().is_ok());
+        model.server_types[0].utilization_weights =
+            hash_map(&[("memory".to_string(), 1.5)]);
+        assert!(model.verify().is_err());
+    }
+}
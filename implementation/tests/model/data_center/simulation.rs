@@ -0,0 +1,105 @@
+#[cfg(test)]
+mod simulation {
+    use crate::factories::data_center_model;
+    use crate::init;
+    use soco::{
+        config::Config,
+        model::{
+            data_center::{
+                model::{JobType, DEFAULT_KEY},
+                simulation::{simulate, Job},
+            },
+            ModelOutput,
+        },
+        schedule::Schedule,
+    };
+
+    fn job(arrival: f64) -> Job {
+        Job {
+            arrival,
+            source: DEFAULT_KEY.to_string(),
+            job_type: DEFAULT_KEY.to_string(),
+            size: 1.,
+        }
+    }
+
+    #[test]
+    fn _1() {
+        init();
+
+        // two jobs share a server and are completed after twice their processing time
+        let mut model = data_center_model(1, 10.);
+        model.job_types =
+            vec![JobType::from_const(DEFAULT_KEY.to_string(), 0.25)];
+        let xs = Schedule::new(vec![Config::single(1.), Config::single(1.)]);
+        let report = simulate(&model, &xs, &[job(0.), job(0.)]).unwrap();
+
+        let entry = &report.entries[0];
+        assert_eq!(entry.jobs, 2);
+        assert_eq!(entry.unserved_jobs, 0);
+        assert_eq!(entry.delays, vec![0.5, 0.5]);
+        assert_eq!(entry.sla_violations, 2);
+        assert_abs_diff_eq!(entry.utilizations[0], 0.5, epsilon = 1e-9);
+        assert_abs_diff_eq!(
+            entry.predicted_utilizations[0],
+            0.5,
+            epsilon = 1e-9
+        );
+        match &entry.predicted.output {
+            ModelOutput::Success(output) => assert_abs_diff_eq!(
+                entry.energy_cost,
+                output.energy_cost,
+                epsilon = 1e-9
+            ),
+            _ => panic!("the load can be processed"),
+        }
+
+        // the idle server consumes energy during the second time slot
+        let entry = &report.entries[1];
+        assert_eq!(entry.jobs, 0);
+        assert_abs_diff_eq!(entry.utilizations[0], 0., epsilon = 1e-9);
+        assert_abs_diff_eq!(entry.energy_cost, 0.5, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn _2() {
+        init();
+
+        // jobs are not served without active servers
+        let xs = Schedule::new(vec![Config::single(0.)]);
+        let report =
+            simulate(&data_center_model(1, 10.), &xs, &[job(0.2), job(0.7)])
+                .unwrap();
+        assert_eq!(report.entries[0].unserved_jobs, 2);
+        assert_abs_diff_eq!(report.entries[0].revenue_loss, 20.);
+
+        // a single job is completed after its processing time
+        let xs = Schedule::new(vec![Config::single(1.), Config::single(0.)]);
+        let report =
+            simulate(&data_center_model(1, 10.), &xs, &[job(0.5)]).unwrap();
+        assert_eq!(report.entries[0].delays, vec![1.]);
+        assert_abs_diff_eq!(report.entries[0].utilizations[0], 0.5);
+        assert_abs_diff_eq!(report.entries[1].utilizations[0], 0.);
+        assert_abs_diff_eq!(report.entries[1].energy_cost, 0.75);
+
+        let columns = report.columns();
+        assert_eq!(columns[0], ("t".to_string(), vec![1., 2.]));
+    }
+
+    #[test]
+    fn _3() {
+        init();
+
+        let xs = Schedule::new(vec![Config::single(1.)]);
+        assert!(simulate(&data_center_model(1, 10.), &xs, &[job(1.5)]).is_err());
+        assert!(simulate(
+            &data_center_model(1, 10.),
+            &xs,
+            &[Job {
+                job_type: "gpu".to_string(),
+                ..job(0.)
+            }]
+        )
+        .is_err());
+    }
+}
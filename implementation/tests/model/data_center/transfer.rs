@@ -0,0 +1,97 @@
+#[cfg(test)]
+mod transfer {
+    use crate::factories::{data_center_hit_cost, data_center_model};
+    use crate::init;
+    use soco::model::{
+        data_center::{
+            model::{DataCenterModel, Location, Source},
+            models::energy_cost::{EnergyCostModel, LinearEnergyCostModel},
+            DataCenterModelOutputFailure,
+        },
+        ModelOutput,
+    };
+    use std::sync::Arc;
+
+    /// Two locations `a` and `b` with two servers each, which are reached from a single source with the given links.
+    fn linked_model(
+        link_capacities: &[(&str, f64)],
+        transfer_prices: &[(&str, f64)],
+    ) -> DataCenterModel {
+        let mut model = data_center_model(2, f64::INFINITY);
+        model.locations = ["a", "b"]
+            .iter()
+            .map(|key| Location {
+                key: key.to_string(),
+                ..model.locations[0].clone()
+            })
+            .collect();
+        model.energy_cost_model = EnergyCostModel::Linear(
+            ["a", "b"]
+                .iter()
+                .map(|key| {
+                    (
+                        key.to_string(),
+                        LinearEnergyCostModel {
+                            cost: Arc::new(|_| 1.),
+                        },
+                    )
+                })
+                .collect(),
+        );
+        let links = |links: &[(&str, f64)]| {
+            links
+                .iter()
+                .map(|&(key, value)| (key.to_string(), value))
+                .collect()
+        };
+        model.sources = vec![Source::default()
+            .with_link_capacities(links(link_capacities))
+            .with_transfer_prices(links(transfer_prices))];
+        model
+    }
+
+    #[test]
+    fn _1() {
+        init();
+
+        // jobs exceeding the capacity of the link to `a` are routed to `b`
+        match data_center_hit_cost(
+            linked_model(&[("a", 1.)], &[("b", 1.)]),
+            2.,
+            vec![2., 2.],
+        ) {
+            ModelOutput::Success(output) => {
+                assert!(output.transfer_cost > 0.95)
+            }
+            _ => panic!("the load can be routed to `b`"),
+        }
+
+        assert!(matches!(
+            data_center_hit_cost(
+                linked_model(&[("a", 0.5), ("b", 0.5)], &[]),
+                2.,
+                vec![2., 2.]
+            ),
+            ModelOutput::Failure(
+                DataCenterModelOutputFailure::LinkCapacityExceeded
+            )
+        ));
+    }
+
+    #[test]
+    fn _2() {
+        init();
+
+        let routed_to_b = |transfer_price| match data_center_hit_cost(
+            linked_model(&[], &[("b", transfer_price)]),
+            2.,
+            vec![2., 2.],
+        ) {
+            ModelOutput::Success(output) => {
+                output.transfer_cost / transfer_price
+            }
+            _ => panic!("the load can be processed"),
+        };
+        assert!(routed_to_b(100.) < routed_to_b(0.01));
+    }
+}
@@ -0,0 +1,80 @@
+#[cfg(test)]
+mod cost_report {
+    use crate::{factories::constant, init};
+    use soco::{
+        config::Config,
+        problem::{Problem, SimplifiedSmoothedConvexOptimization},
+        schedule::Schedule,
+    };
+
+    #[test]
+    fn _1() {
+        init();
+
+        let p = SimplifiedSmoothedConvexOptimization {
+            d: 2,
+            t_end: 2,
+            bounds: vec![2, 2],
+            switching_cost: vec![1., 2.],
+            hitting_cost: constant(),
+        };
+        let xs = Schedule::new(vec![
+            Config::new(vec![1, 0]),
+            Config::new(vec![1, 2]),
+        ]);
+        let report = p.cost_report(&xs).unwrap();
+
+        let columns = report.columns();
+        let names: Vec<_> =
+            columns.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "t",
+                "hitting_cost",
+                "transition_cost",
+                "movement_cost",
+                "movement_cost.0",
+                "movement_cost.1"
+            ]
+        );
+        assert_eq!(columns[0].1, vec![1., 2.]);
+        assert_eq!(columns[1].1, vec![1., 1.]);
+        assert_eq!(columns[3].1, vec![1., 4.]);
+        assert_eq!(columns[4].1, vec![1., 0.]);
+        assert_eq!(columns[5].1, vec![0., 4.]);
+
+        let total: f64 = columns[1..4]
+            .iter()
+            .flat_map(|(_, values)| values.iter())
+            .sum();
+        assert_abs_diff_eq!(
+            total,
+            p.objective_function(&xs).unwrap().cost.raw()
+        );
+    }
+
+    #[test]
+    fn _2() {
+        init();
+
+        let p = SimplifiedSmoothedConvexOptimization {
+            d: 1,
+            t_end: 2,
+            bounds: vec![2],
+            switching_cost: vec![1.],
+            hitting_cost: constant(),
+        };
+        let xs = Schedule::new(vec![Config::single(1), Config::single(2)]);
+        let report = p.cost_report(&xs).unwrap();
+
+        assert_eq!(
+            report.to_csv(),
+            "t,hitting_cost,transition_cost,movement_cost,movement_cost.0\n1,1,0,1,1\n2,1,0,1,1\n"
+        );
+        assert_eq!(
+            report.to_json(),
+            "{\"t\":[1,2],\"hitting_cost\":[1,1],\"transition_cost\":[0,0],\"movement_cost\":[1,1],\"movement_cost.0\":[1,1]}"
+        );
+    }
+}
@@ -2,3 +2,5 @@
 mod offline;
 #[cfg(test)]
 mod online;
+#[cfg(test)]
+mod sweep;
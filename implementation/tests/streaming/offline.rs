@@ -61,6 +61,7 @@ fn solve() {
         vec![Location {
             key: DEFAULT_KEY.to_string(),
             m: hash_map(&[(DEFAULT_KEY.to_string(), m)]),
+            demand_charge: None,
         }],
         vec![ServerType::default()],
         vec![Source::default()],
@@ -94,7 +95,7 @@ fn solve() {
     );
     let input = DataCenterOfflineInput { loads };
 
-    let (xs, cost, _) = offline::solve(
+    let (xs, cost, certificate, _) = offline::solve(
         &model,
         &optimal_graph_search,
         Options::default(),
@@ -103,6 +104,7 @@ fn solve() {
     )
     .unwrap();
     xs.verify(t_end, &vec![m]).unwrap();
+    assert_eq!(certificate.unwrap().gap(), 0.);
 
     let online_inputs = input.into_online();
     let mut online = Online::<
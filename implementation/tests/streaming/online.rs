@@ -44,6 +44,7 @@ fn integration() {
             vec![Location {
                 key: DEFAULT_KEY.to_string(),
                 m: hash_map(&[(DEFAULT_KEY.to_string(), m)]),
+                demand_charge: None,
             }],
             vec![ServerType::default()],
             vec![Source::default()],
@@ -0,0 +1,248 @@
+use crate::{init, utils::hash_map};
+use soco::{
+    model::data_center::{
+        loads::LoadProfile,
+        model::{
+            DataCenterModel, DataCenterOfflineInput, JobType, Location,
+            ServerType, Source, DEFAULT_KEY,
+        },
+        models::{
+            energy_consumption::{
+                EnergyConsumptionModel, SimplifiedLinearEnergyConsumptionModel,
+            },
+            energy_cost::{EnergyCostModel, LinearEnergyCostModel},
+            revenue_loss::{
+                MinimalDetectableDelayRevenueLossModel, RevenueLossModel,
+            },
+            switching_cost::{SwitchingCost, SwitchingCostModel},
+        },
+    },
+    result::Failure,
+    streaming::sweep::{Design, Parameter, Point, Run, Solver, Sweep},
+};
+use std::sync::Arc;
+
+fn model() -> DataCenterModel {
+    DataCenterModel::new(
+        1.,
+        vec![Location {
+            key: DEFAULT_KEY.to_string(),
+            m: hash_map(&[(DEFAULT_KEY.to_string(), 10)]),
+            demand_charge: None,
+        }],
+        vec![ServerType::default()],
+        vec![Source::default()],
+        vec![JobType::default()],
+        EnergyConsumptionModel::SimplifiedLinear(hash_map(&[(
+            DEFAULT_KEY.to_string(),
+            SimplifiedLinearEnergyConsumptionModel { phi_max: 1. },
+        )])),
+        EnergyCostModel::Linear(hash_map(&[(
+            DEFAULT_KEY.to_string(),
+            LinearEnergyCostModel {
+                cost: Arc::new(|_| 1.),
+            },
+        )])),
+        RevenueLossModel::MinimalDetectableDelay(hash_map(&[(
+            DEFAULT_KEY.to_string(),
+            MinimalDetectableDelayRevenueLossModel::default(),
+        )])),
+        SwitchingCostModel::new(hash_map(&[(
+            DEFAULT_KEY.to_string(),
+            SwitchingCost::from_normalized(1., 4., 1., 0.5),
+        )])),
+    )
+}
+
+fn input() -> DataCenterOfflineInput {
+    DataCenterOfflineInput {
+        loads: vec![
+            LoadProfile::raw(vec![5.]),
+            LoadProfile::raw(vec![2.]),
+            LoadProfile::raw(vec![6.]),
+            LoadProfile::raw(vec![1.]),
+            LoadProfile::raw(vec![4.]),
+        ],
+    }
+}
+
+#[test]
+fn grid() {
+    init();
+
+    let table = Sweep::new(
+        model(),
+        input(),
+        Design::Grid(vec![
+            (Parameter::NormalizedSwitchingCost, vec![2., 8.]),
+            (Parameter::W, vec![0., 1.]),
+        ]),
+        vec![
+            Solver::convex_optimization(),
+            Solver::receding_horizon_control(),
+        ],
+    )
+    .with_reference("convex_optimization")
+    .run()
+    .unwrap();
+
+    assert_eq!(table.rows.len(), 8);
+    for row in &table.rows {
+        assert!(row.error.is_none(), "{:?}", row.error);
+        assert!(row.runtime.is_some());
+        if row.solver == "convex_optimization" {
+            assert_abs_diff_eq!(row.ratio, 1.);
+        } else {
+            assert!(row.ratio >= 1. - 1e-2);
+        }
+    }
+    assert_eq!(
+        table.rows[0].parameters,
+        Point(vec![
+            (Parameter::NormalizedSwitchingCost, 2.),
+            (Parameter::W, 0.)
+        ])
+    );
+
+    // higher switching costs are never cheaper for the offline optimum
+    let optimum = |point: usize| {
+        table
+            .rows
+            .iter()
+            .find(|row| {
+                row.point == point && row.solver == "convex_optimization"
+            })
+            .unwrap()
+            .cost
+    };
+    assert!(optimum(0) <= optimum(2) + 1e-6);
+
+    let columns: Vec<_> =
+        table.columns().into_iter().map(|(name, _)| name).collect();
+    assert_eq!(
+        columns,
+        vec![
+            "point",
+            "normalized_switching_cost",
+            "w",
+            "cost",
+            "ratio",
+            "runtime"
+        ]
+    );
+    let csv = table.to_csv();
+    assert_eq!(csv.lines().count(), 9);
+    assert!(csv.starts_with(
+        "point,normalized_switching_cost,w,cost,ratio,runtime,solver,error\n"
+    ));
+}
+
+#[test]
+fn random() {
+    init();
+
+    let design = Design::Random {
+        ranges: vec![
+            (Parameter::Gamma, (1.1, 2.)),
+            (Parameter::EnergyPrice, (0.5, 2.)),
+        ],
+        n: 3,
+        seed: 42,
+    };
+    let points = design.points().unwrap();
+    assert_eq!(points, design.points().unwrap());
+    assert_eq!(points.len(), 3);
+    for point in &points {
+        let gamma = point.get(&Parameter::Gamma).unwrap();
+        assert!((1.1..=2.).contains(&gamma));
+        assert!(point.get(&Parameter::W).is_none());
+    }
+
+    let table = Sweep::new(
+        model(),
+        input(),
+        design,
+        vec![Solver::approx_graph_search(), Solver::convex_optimization()],
+    )
+    .run()
+    .unwrap();
+    assert_eq!(table.rows.len(), 6);
+    for point in 0..3 {
+        let ratios: Vec<_> = table
+            .rows
+            .iter()
+            .filter(|row| row.point == point)
+            .map(|row| row.ratio)
+            .collect();
+        assert!(ratios.iter().all(|&ratio| ratio >= 1.));
+        assert!(ratios.iter().any(|&ratio| ratio == 1.));
+    }
+}
+
+#[test]
+fn failures() {
+    init();
+
+    // energy prices are scaled
+    let point = Point(vec![(Parameter::EnergyPrice, 3.)]);
+    match &point.apply(&model()).energy_cost_model {
+        EnergyCostModel::Linear(models) => {
+            assert_abs_diff_eq!((models[DEFAULT_KEY].cost)(1), 3.)
+        }
+        _ => panic!("model is linear"),
+    }
+
+    // failures of a solver are recorded
+    let name = Parameter::Option("fail".to_string());
+    let solver = Solver::new("custom", |_, _, point| {
+        if point.get(&Parameter::Option("fail".to_string())) == Some(1.) {
+            Err(Failure::Invalid("failed".to_string()))
+        } else {
+            Ok(Run {
+                cost: 2.,
+                runtime: 0,
+            })
+        }
+    });
+    let table = Sweep::new(
+        model(),
+        input(),
+        Design::Grid(vec![(name, vec![0., 1.])]),
+        vec![solver.clone()],
+    )
+    .run()
+    .unwrap();
+    assert_eq!(table.rows[0].cost, 2.);
+    assert_eq!(table.rows[0].ratio, 1.);
+    assert!(table.rows[1].cost.is_nan());
+    assert!(table.rows[1].runtime.is_none());
+    assert!(table.rows[1].error.is_some());
+    assert!(table
+        .to_csv()
+        .lines()
+        .nth(2)
+        .unwrap()
+        .ends_with(",custom,A verifier determined an invalidity: failed"));
+
+    // the reference solver must be swept
+    assert!(
+        Sweep::new(model(), input(), Design::Grid(vec![]), vec![solver])
+            .with_reference("convex_optimization")
+            .run()
+            .is_err()
+    );
+
+    // inadmissible parameters are rejected before any solver is run
+    for design in [
+        Design::Grid(vec![(Parameter::Gamma, vec![1.1, 1.])]),
+        Design::Grid(vec![(Parameter::Delta, vec![0.])]),
+        Design::Grid(vec![(Parameter::W, vec![-1.])]),
+        Design::Random {
+            ranges: vec![(Parameter::Gamma, (1., 1.5))],
+            n: 3,
+            seed: 42,
+        },
+    ] {
+        assert!(matches!(design.points(), Err(Failure::Invalid(_))));
+    }
+}
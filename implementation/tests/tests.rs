@@ -13,6 +13,12 @@ mod algorithms;
 #[cfg(test)]
 mod convert;
 #[cfg(test)]
+mod cost;
+#[cfg(test)]
+mod model;
+#[cfg(test)]
+mod report;
+#[cfg(test)]
 mod streaming;
 
 mod factories;
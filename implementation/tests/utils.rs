@@ -1,3 +1,4 @@
+use soco::{config::Config, schedule::Schedule};
 use std::collections::HashMap;
 
 /// Constructs a hash map from a slice.
@@ -16,3 +17,8 @@ where
 {
     bounds.iter().map(|&(_, m)| m).collect()
 }
+
+/// Schedule of fractional configurations.
+pub fn schedule<const D: usize>(xs: &[[f64; D]]) -> Schedule<f64> {
+    Schedule::new(xs.iter().map(|x| Config::new(x.to_vec())).collect())
+}